@@ -6,19 +6,40 @@ This is a suite of tools that perform either or both of the following:
 
 * extract data from Mongoose Traveller PDF files,
 * produced data derived from said data.
+
+Exit codes:
+
+* 0 on success (warnings may have been printed),
+* 1 if the command failed, such as when no tables could be extracted,
+* 5 if the command partially failed, such as when some tables could not be
+  extracted,
+* EX_USAGE (64) if the command line was invalid, or named a path that escapes
+  its directory,
+* EX_UNAVAILABLE (69) if an input file or Java was unavailable,
+* EX_CONFIG (78) if the configuration or project file was invalid,
+* 130 if the command was stopped early by SIGINT or SIGTERM.
+
+The values in parentheses are those used on platforms that define sysexits.
 """
 
 
 import argparse
 import sys
 
-from travdata import commontext
+from travdata import commontext, filesio
 from travdata import travdatarelease
 from travdata.cli import cliutil
 from travdata.cli.cmds import (
+    convert,
     extractcsvtables,
+    fix,
+    inittemplate,
     licenses,
     listbooks,
+    listtables,
+    package,
+    query,
+    run,
     tradetable,
 )
 from travdata.config import cfgerror
@@ -27,7 +48,7 @@ from travdata.config import cfgerror
 def main() -> None:
     """Entrypoint for the program."""
 
-    argparser = argparse.ArgumentParser(
+    argparser = cliutil.ArgumentParser(
         description=f"{__doc__}\n{commontext.DATA_USAGE}",
         formatter_class=argparse.RawTextHelpFormatter,
     )
@@ -41,9 +62,16 @@ def main() -> None:
     )
 
     subparsers = argparser.add_subparsers(required=True)
+    convert.add_subparser(subparsers)
     extractcsvtables.add_subparser(subparsers)
+    fix.add_subparser(subparsers)
+    inittemplate.add_subparser(subparsers)
     licenses.add_subparser(subparsers)
     listbooks.add_subparser(subparsers)
+    listtables.add_subparser(subparsers)
+    package.add_subparser(subparsers)
+    query.add_subparser(subparsers)
+    run.add_subparser(subparsers)
     tradetable.add_subparser(subparsers)
 
     args = argparser.parse_args()
@@ -51,7 +79,7 @@ def main() -> None:
         sys.exit(args.run(args))
     except cfgerror.ConfigurationError as exc:
         print(exc, file=sys.stderr)
-        sys.exit(cliutil.EX_CONFIG)
+        sys.exit(cliutil.ExitCode.CONFIG)
     except cliutil.UsageError as exc:
         argparser.print_usage(sys.stderr)
         print(exc, file=sys.stderr)
@@ -59,6 +87,14 @@ def main() -> None:
     except cliutil.CLIError as exc:
         print(exc, file=sys.stderr)
         sys.exit(exc.exit_code)
+    except filesio.UnsafePathError as exc:
+        # A path that escapes its directory is a problem with the request, not the
+        # environment.
+        print(exc, file=sys.stderr)
+        sys.exit(cliutil.ExitCode.USAGE)
+    except (OSError, filesio.Error) as exc:
+        print(exc, file=sys.stderr)
+        sys.exit(cliutil.ExitCode.ENVIRONMENT)
 
 
 if __name__ == "__main__":
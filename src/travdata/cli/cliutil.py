@@ -1,20 +1,146 @@
 # -*- coding: utf-8 -*-
 """CLI utilities."""
 
+import argparse
+import enum
 import os
+import signal
+import sys
+import threading
+from types import FrameType, TracebackType
+from typing import Any, Optional, Self
 
 
 EX_CONFIG = getattr(os, "EX_CONFIG", 2)
 EX_USAGE = getattr(os, "EX_USAGE", 3)
+EX_UNAVAILABLE = getattr(os, "EX_UNAVAILABLE", 4)
+
+
+class ExitCode(enum.IntEnum):
+    """Exit codes returned by the CLI, by class of failure.
+
+    Where the platform defines them, these follow the ``sysexits.h``
+    conventions.
+    """
+
+    # Success, although warnings may have been reported.
+    OK = 0
+    # The requested work failed, such as no tables being extracted.
+    FAILURE = 1
+    # The command line was invalid.
+    USAGE = EX_USAGE
+    # The configuration (or a project file) was invalid or unusable.
+    CONFIG = EX_CONFIG
+    # Something that the command needs from its environment (such as an input
+    # file, or Java) was unavailable.
+    ENVIRONMENT = EX_UNAVAILABLE
+    # Some, but not all, of the requested work failed.
+    PARTIAL_FAILURE = 5
+    # The work was stopped early by SIGINT or SIGTERM, following the shell
+    # convention of 128 + SIGINT.
+    INTERRUPTED = 130
 
 
 class CLIError(Exception):
     """Base class for CLI errors."""
 
-    exit_code: int = 1
+    exit_code: int = ExitCode.FAILURE
 
 
 class UsageError(CLIError):
     """Exception for user usage error."""
 
-    exit_code = EX_USAGE
+    exit_code = ExitCode.USAGE
+
+
+class UnavailableError(CLIError):
+    """Exception for something missing from the environment."""
+
+    exit_code = ExitCode.ENVIRONMENT
+
+
+class ArgumentParser(argparse.ArgumentParser):
+    """``argparse.ArgumentParser`` that exits with ``ExitCode.USAGE`` on error.
+
+    Subcommand parsers created from it are of the same class.
+    """
+
+    def error(self, message: str):
+        self.print_usage(sys.stderr)
+        self.exit(ExitCode.USAGE, f"{self.prog}: error: {message}\n")
+
+
+class OutputFormat(enum.StrEnum):
+    """Format of information printed by a command."""
+
+    TEXT = "text"
+    JSON = "json"
+
+
+def add_output_format_flag(argparser: argparse.ArgumentParser) -> None:
+    """Adds the ``--format`` flag, parsed as an ``OutputFormat``."""
+    argparser.add_argument(
+        "--format",
+        help="Format of the output.",
+        type=OutputFormat,
+        choices=list(OutputFormat),
+        default=OutputFormat.TEXT,
+    )
+
+
+# Signals that request a graceful stop.
+_STOP_SIGNALS = (signal.SIGINT, signal.SIGTERM)
+
+
+class GracefulStop:
+    """Context manager that turns SIGINT and SIGTERM into a request to stop.
+
+    The first signal sets ``stop_requested``, so that long running work can
+    stop at the next safe point and close its outputs cleanly. A second signal
+    raises ``KeyboardInterrupt`` as usual. The previous signal handlers are
+    restored on exit.
+
+    Signal handlers can only be installed from the main thread, so this does
+    nothing when entered from any other thread.
+    """
+
+    def __init__(self, message: str = "Stopping, interrupt again to abort.") -> None:
+        """Initialise the GracefulStop.
+
+        :param message: Printed to stderr when the first signal is received.
+        """
+        self._message = message
+        self._stop_requested = False
+        self._previous: dict[signal.Signals, Any] = {}
+
+    @property
+    def stop_requested(self) -> bool:
+        """Returns True if a stop has been requested by a signal."""
+        return self._stop_requested
+
+    def do_continue(self) -> bool:
+        """Returns True if work should continue."""
+        return not self._stop_requested
+
+    def __enter__(self) -> Self:
+        if threading.current_thread() is threading.main_thread():
+            for signum in _STOP_SIGNALS:
+                self._previous[signum] = signal.signal(signum, self._handle)
+        return self
+
+    def __exit__(
+        self,
+        exc_type: Optional[type[BaseException]],
+        exc_value: Optional[BaseException],
+        traceback: Optional[TracebackType],
+    ) -> None:
+        for signum, handler in self._previous.items():
+            signal.signal(signum, handler)
+        self._previous.clear()
+
+    def _handle(self, signum: int, frame: Optional[FrameType]) -> None:
+        del signum, frame  # unused
+        if self._stop_requested:
+            raise KeyboardInterrupt()
+        self._stop_requested = True
+        print(self._message, file=sys.stderr, flush=True)
@@ -0,0 +1,83 @@
+# -*- coding: utf-8 -*-
+"""
+Converts the output of extractcsvtables between output types.
+
+This re-packages an existing output (such as a directory into a ZIP file),
+without needing the original PDF or re-running extraction.
+"""
+
+import argparse
+import pathlib
+import textwrap
+
+from travdata import filesio
+from travdata.cli import cliutil
+from travdata.extraction import index
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "convert",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "input",
+        help="Path to the directory or ZIP file that tables were extracted into.",
+        type=pathlib.Path,
+        metavar="INPUT_PATH",
+    )
+    argparser.add_argument(
+        "output",
+        help="Path to the directory or ZIP file to create. This must not already exist.",
+        type=pathlib.Path,
+        metavar="OUTPUT_PATH",
+    )
+    argparser.add_argument(
+        "--input-type",
+        help="Controls how data is read from INPUT_PATH.",
+        type=filesio.IOType,
+        choices=filesio.IOType,
+        default=filesio.IOType.AUTO,
+    )
+    argparser.add_argument(
+        "--output-type",
+        help=textwrap.dedent(
+            """
+            Controls how data is written to OUTPUT_PATH.
+
+            * AUTO guesses, based on the path suffix ending in ".zip".
+            * DIR writes as a directory.
+            * ZIP writes as a ZIP file.
+            """
+        ),
+        type=filesio.IOType,
+        choices=filesio.IOType,
+        default=filesio.IOType.AUTO,
+    )
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    if args.output.exists():
+        raise cliutil.UsageError(f"{args.output} already exists.")
+
+    with filesio.new_reader(args.input, args.input_type) as reader:
+        try:
+            input_index = index.Index.read(reader)
+        except filesio.NotFoundError as exc:
+            raise cliutil.UsageError(f"{args.input} does not contain an index.csv.") from exc
+
+        missing = sorted(p for p in input_index.paths_with_all_tags([]) if not reader.exists(p))
+        if missing:
+            fmt_missing = ", ".join(str(p) for p in missing)
+            raise cliutil.CLIError(f"{args.input} is missing indexed tables: {fmt_missing}")
+
+        with filesio.new_read_writer(args.output, args.output_type) as read_writer:
+            paths = filesio.copy_files(reader, read_writer)
+
+    print(f"Copied {len(paths)} files into {args.output}.")
+    return cliutil.ExitCode.OK
@@ -6,14 +6,20 @@ CSV files.
 
 import argparse
 import contextlib
+import dataclasses
+import datetime
+import enum
+import json
 import pathlib
 import sys
 import textwrap
-from typing import Callable, Iterator
+import time
+from typing import IO, Callable, Iterator, Optional
 
 from progress import bar as progress  # type: ignore[import-untyped]
-from travdata import config, filesio
-from travdata.extraction import bookextract, tabulautil
+from travdata import config, csvutil, filesio
+from travdata.cli import cliutil
+from travdata.extraction import bookextract, metrics, tablestats, tabulautil
 
 
 def add_subparser(subparsers) -> None:
@@ -60,13 +66,6 @@ def add_subparser(subparsers) -> None:
 
     config.add_config_flag(argparser)
 
-    argparser.add_argument(
-        "--no-progress",
-        help="""Disable progress bar.""",
-        action="store_true",
-        default=False,
-    )
-
     argparser.add_argument(
         "--output-type",
         help=textwrap.dedent(
@@ -77,12 +76,30 @@ def add_subparser(subparsers) -> None:
               or the path suffix ending in ".zip".
             * DIR writes as a directory.
             * ZIP writes as a ZIP file.
+            * NULL discards all output, which is useful to measure extraction
+              performance without the cost of writing files.
             """
         ),
         type=filesio.IOType,
         choices=filesio.IOType,
         default=filesio.IOType.AUTO,
     )
+    argparser.add_argument(
+        "--symlink-policy",
+        help=textwrap.dedent(
+            """
+            Controls how symlinks are treated when the output is a directory.
+            Paths are never allowed to resolve outside of OUTPUT_PATH.
+
+            * FOLLOW follows symlinks.
+            * REFUSE fails to write to any path through a symlink.
+            * REPLACE replaces symlinked output files with regular files.
+            """
+        ),
+        type=filesio.SymlinkPolicy,
+        choices=filesio.SymlinkPolicy,
+        default=filesio.SymlinkPolicy.FOLLOW,
+    )
 
     outsel_grp = argparser.add_argument_group(
         "Output selection",
@@ -128,6 +145,194 @@ def add_subparser(subparsers) -> None:
         ),
     )
 
+    add_run_flags(argparser)
+
+
+def add_run_flags(argparser: argparse.ArgumentParser) -> None:
+    """Adds flags required by ``run_extraction`` to the ``argparser``."""
+    argparser.add_argument(
+        "--no-progress",
+        help=textwrap.dedent(
+            """
+            Disable progress reporting. Progress is shown as a bar when stderr
+            is a terminal, or otherwise as occasional lines of text.
+            """
+        ),
+        action="store_true",
+        default=False,
+    )
+    argparser.add_argument(
+        "--progress-format",
+        help=textwrap.dedent(
+            """
+            How to report progress.
+
+            * AUTO shows a bar when stderr is a terminal, or otherwise
+              occasional lines of text on stderr.
+            * JSONL writes one JSON object per line to stdout for each
+              progress report, table started or finished, error, and the end
+              of the run. This is for tracking progress from other programs.
+            """
+        ),
+        type=ProgressFormat,
+        choices=list(ProgressFormat),
+        default=ProgressFormat.AUTO,
+    )
+
+    argparser.add_argument(
+        "--csv-encoding",
+        help=textwrap.dedent(
+            f"""
+            Encoding of the CSV files written. Use {csvutil.CsvEncoding.UTF8_BOM}
+            for CSV files that Excel opens as UTF-8, or {csvutil.CsvEncoding.CP1252}
+            for programs that do not support UTF-8 (tables with characters that
+            it cannot represent fail to extract). Defaults to the csv_encoding
+            of any project being run, or {csvutil.CsvEncoding.UTF8}.
+            """
+        ),
+        type=csvutil.CsvEncoding,
+        choices=csvutil.CsvEncoding,
+        default=None,
+    )
+
+    argparser.add_argument(
+        "--debug-extraction",
+        help=textwrap.dedent(
+            f"""
+            Write the unconverted output from Tabula into a
+            {bookextract.TABULA_DEBUG_SUFFIX} file alongside each CSV file. This
+            is useful when adjusting the configuration of a table.
+            """
+        ),
+        action="store_true",
+        default=False,
+    )
+
+    argparser.add_argument(
+        "--dump-intermediates",
+        help=textwrap.dedent(
+            """
+            Write the rows input to each transformation step of each table as
+            JSON files into this directory, named by the table and step. This
+            is useful for seeing what each transformation actually received.
+            """
+        ),
+        type=pathlib.Path,
+        metavar="DIR",
+        default=None,
+    )
+    argparser.add_argument(
+        "--dump-tag",
+        help=textwrap.dedent(
+            """
+            Only write intermediates (see --dump-intermediates) for tables with
+            any of the given tags. Can be given multiple times.
+            """
+        ),
+        action="append",
+        metavar="TAG",
+        default=[],
+    )
+
+    argparser.add_argument(
+        "--allow-exec",
+        help=textwrap.dedent(
+            """
+            Run post-processing commands specified by the configuration on each
+            output file. Only use this with configurations that you trust.
+            """
+        ),
+        action="store_true",
+        default=False,
+    )
+    argparser.add_argument(
+        "--sandbox",
+        help=textwrap.dedent(
+            """
+            Run safely with an untrusted configuration or project. This refuses
+            to run post-processing commands, or to write anywhere other than
+            the output, including through symlinks.
+            """
+        ),
+        action="store_true",
+        default=False,
+    )
+
+    warn_grp = argparser.add_argument_group(
+        "Table warnings",
+        description="Controls when statistics about extracted tables are reported as warnings.",
+    )
+    warn_grp.add_argument(
+        "--warn-min-rows",
+        help=textwrap.dedent(
+            f"""
+            Warn about tables with fewer than this many rows. Defaults to the
+            configuration's table_warnings setting, or
+            {tablestats.Thresholds.min_rows}.
+            """
+        ),
+        type=int,
+        metavar="N",
+        default=None,
+    )
+    warn_grp.add_argument(
+        "--warn-empty-cells",
+        help=textwrap.dedent(
+            f"""
+            Warn about tables with more than this fraction of empty cells.
+            Defaults to the configuration's table_warnings setting, or
+            {tablestats.Thresholds.max_empty_fraction}.
+            """
+        ),
+        type=float,
+        metavar="FRACTION",
+        default=None,
+    )
+    warn_grp.add_argument(
+        "--no-warn-edge",
+        help=textwrap.dedent(
+            """
+            Do not warn about tables with text at the edge of their template
+            area, regardless of the configuration's table_warnings setting.
+            """
+        ),
+        dest="warn_edge",
+        action="store_false",
+        default=None,
+    )
+
+    budget_grp = argparser.add_argument_group(
+        "Time budgets",
+        description="Controls what happens when tables take too long to extract.",
+    )
+    budget_grp.add_argument(
+        "--time-budget",
+        help=textwrap.dedent(
+            """
+            Time budget for extracting each table that does not set its own
+            time_budget in the configuration.
+            """
+        ),
+        type=float,
+        metavar="SECONDS",
+        default=None,
+    )
+    budget_grp.add_argument(
+        "--over-budget",
+        help=textwrap.dedent(
+            """
+            Action to take when a table exceeds its time budget. "warn" keeps
+            the table once it is extracted, "fail" stops extracting the table
+            and discards it, and "abort" also stops extracting further tables.
+            A single read of an area by Tabula is not interrupted, so a table
+            may still overrun its budget by that long.
+            """
+        ),
+        type=bookextract.BudgetAction,
+        choices=bookextract.BudgetAction,
+        default=bookextract.BudgetAction.WARN,
+    )
+
     tab_grp = argparser.add_argument_group("Tabula")
     tab_grp.add_argument(
         "--tabula-force-subprocess",
@@ -140,39 +345,198 @@ def add_subparser(subparsers) -> None:
         action="store_true",
         default=False,
     )
+    tab_grp.add_argument(
+        "--tabula-batch",
+        help=textwrap.dedent(
+            """
+            Controls how areas in a table's Tabula template are batched into
+            calls to Tabula.
+
+            * NONE makes one call per area.
+            * PAGE_METHOD makes one call per run of areas on the same page with
+              the same extraction method.
+            """
+        ),
+        type=tabulautil.BatchStrategy,
+        choices=tabulautil.BatchStrategy,
+        default=tabulautil.BatchStrategy.NONE,
+    )
+
+
+class ProgressFormat(enum.StrEnum):
+    """How extraction progress is reported."""
+
+    AUTO = "auto"
+    JSONL = "jsonl"
+
+
+class JsonLinesProgress(bookextract.Observer):
+    """Reports extraction events as JSON objects, one per line.
+
+    Each object has an ``"event"`` key naming the kind of event, with the
+    other keys depending on the kind.
+    """
+
+    def __init__(self, out: IO[str]) -> None:
+        """Initialise the JsonLinesProgress.
+
+        :param out: Stream to write lines to.
+        """
+        self._out = out
+
+    def progress(self, event: bookextract.Progress) -> None:
+        """Implements Observer.progress."""
+        percent = 100.0 * event.completed / event.total if event.total else 100.0
+        self._write(
+            "progress",
+            completed=event.completed,
+            total=event.total,
+            percent=round(percent, 1),
+        )
+
+    def table_started(self, event: bookextract.TableStarted) -> None:
+        """Implements Observer.table_started."""
+        self._write("table_started", path=event.output_path.as_posix())
+
+    def table_finished(self, event: bookextract.TableFinished) -> None:
+        """Implements Observer.table_finished."""
+        self._write(
+            "table_finished",
+            path=event.output_path.as_posix(),
+            success=event.success,
+            duration=round(event.duration, 3),
+            stats=None if event.stats is None else dataclasses.asdict(event.stats),
+        )
+
+    def run_finished(self, event: bookextract.RunFinished) -> None:
+        """Implements Observer.run_finished."""
+        self._write("run_finished", stopped=event.stopped)
+
+    def error(self, message: str) -> None:
+        """Reports an error message."""
+        self._write("error", message=message)
+
+    def _write(self, event: str, **fields) -> None:
+        print(json.dumps({"event": event, **fields}), file=self._out, flush=True)
+
+
+# Minimum interval between progress lines when stderr is not a terminal, in
+# seconds.
+_PROGRESS_LOG_INTERVAL = 30.0
+
+
+class ProgressLog(bookextract.Observer):
+    """Reports progress as occasional lines of text.
+
+    This is for when the progress bar cannot be used, such as when stderr is
+    redirected to a log file.
+    """
+
+    def __init__(
+        self,
+        out: IO[str],
+        interval: float = _PROGRESS_LOG_INTERVAL,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        """Initialise the ProgressLog.
+
+        :param out: Stream to write progress lines to.
+        :param interval: Minimum interval between lines, in seconds. The first
+        and final progress are always written.
+        :param clock: Source of the current time, in seconds.
+        """
+        self._out = out
+        self._interval = interval
+        self._clock = clock
+        self._start: Optional[float] = None
+        self._last: Optional[float] = None
+
+    def progress(self, event: bookextract.Progress) -> None:
+        now = self._clock()
+        if self._start is None:
+            self._start = now
+        final = event.completed >= event.total
+        if self._last is not None and not final and now - self._last < self._interval:
+            return
+        self._last = now
+
+        line = f"Extracted {event.completed}/{event.total} tables"
+        if 0 < event.completed < event.total:
+            remaining = (now - self._start) / event.completed * (event.total - event.completed)
+            line += f", about {datetime.timedelta(seconds=round(remaining))} remaining"
+        print(f"{line}.", file=self._out, flush=True)
+
+
+class _ProgressBar(bookextract.Observer):
+    """Reports progress on a terminal progress bar."""
+
+    def __init__(self, bar: progress.Bar) -> None:
+        """Initialise the _ProgressBar.
+
+        :param bar: Started progress bar to update.
+        """
+        self._bar = bar
+
+    def progress(self, event: bookextract.Progress) -> None:
+        self._bar.max = event.total
+        # Advancing with next() keeps the bar's estimate of time remaining up
+        # to date.
+        self._bar.next(event.completed - self._bar.index)
 
 
 @contextlib.contextmanager
-def _progress_reporter(no_progress: bool) -> Iterator[Callable[[bookextract.Progress], None]]:
+def _progress_reporter(no_progress: bool) -> Iterator[Optional[bookextract.Observer]]:
     if no_progress:
-        progress_bar = None
+        yield None
 
-        def on_progress(p: bookextract.Progress) -> None:
-            del p  # unused
+    elif not sys.stderr.isatty():
+        yield ProgressLog(sys.stderr)
 
     else:
-        progress_bar = progress.Bar("Extracting tables")
+        progress_bar = progress.Bar(
+            "Extracting tables",
+            suffix="%(index)d/%(max)d, ETA %(eta_td)s",
+        )
         progress_bar.start()
-
-        def on_progress(p: bookextract.Progress) -> None:
-            progress_bar.index = p.completed
-            progress_bar.max = p.total
-            progress_bar.update()
-
-    try:
-        yield on_progress
-    finally:
-        if progress_bar is not None:
+        try:
+            yield _ProgressBar(progress_bar)
+        finally:
             progress_bar.finish()
 
 
 def _create_read_writer(
     args: argparse.Namespace,
 ) -> contextlib.AbstractContextManager[filesio.ReadWriter]:
-    output: pathlib.Path = args.output
-    output_type: filesio.IOType = args.output_type
-    output_type = output_type.resolve_auto(output)
-    return output_type.new_read_writer(output)
+    return filesio.new_read_writer(
+        args.output, args.output_type, output_symlink_policy(args, args.symlink_policy)
+    )
+
+
+def output_symlink_policy(
+    args: argparse.Namespace,
+    symlink_policy: filesio.SymlinkPolicy = filesio.SymlinkPolicy.FOLLOW,
+) -> filesio.SymlinkPolicy:
+    """Returns the symlink policy for the output, given the ``--sandbox`` flag.
+
+    :param args: Parsed arguments. This must have been generated from a parser
+    that included the arguments added by ``add_run_flags``.
+    :param symlink_policy: Policy requested by the user.
+    :return: Policy to use.
+    """
+    if args.sandbox and symlink_policy == filesio.SymlinkPolicy.FOLLOW:
+        return filesio.SymlinkPolicy.REFUSE
+    return symlink_policy
+
+
+def _check_sandbox(args: argparse.Namespace) -> None:
+    if not args.sandbox:
+        return
+    if args.allow_exec:
+        raise cliutil.UsageError("--allow-exec cannot be used with --sandbox.")
+    if args.dump_intermediates is not None:
+        raise cliutil.UsageError(
+            "--dump-intermediates writes outside of the output, so cannot be used with --sandbox."
+        )
 
 
 def run(args: argparse.Namespace) -> int:
@@ -182,11 +546,9 @@ def run(args: argparse.Namespace) -> int:
     without_tags = frozenset(args.without_tag)
     if intersection := with_tags & without_tags:
         fmt_inter = ", ".join(sorted(intersection))
-        print(
-            f"Tags have been specified for both inclusion and exclusion: {fmt_inter}.",
-            file=sys.stderr,
+        raise cliutil.UsageError(
+            f"Tags have been specified for both inclusion and exclusion: {fmt_inter}."
         )
-        return 1
 
     ext_cfg = bookextract.ExtractionConfig(
         cfg_reader_ctx=config.config_reader(args),
@@ -198,21 +560,90 @@ def run(args: argparse.Namespace) -> int:
         without_tags=without_tags,
     )
 
+    return run_extraction(args, ext_cfg)
+
+
+def run_extraction(args: argparse.Namespace, ext_cfg: bookextract.ExtractionConfig) -> int:
+    """Runs the extraction, reporting progress and errors to the terminal.
+
+    :param args: Parsed arguments. This must have been generated from a parser
+    that included the arguments added by ``add_run_flags``.
+    :param ext_cfg: Extraction to run.
+    :return: Exit code.
+    :raises cliutil.UsageError: If flags are used that ``--sandbox`` forbids.
+    """
+    _check_sandbox(args)
+
+    ext_cfg = dataclasses.replace(
+        ext_cfg,
+        debug_extraction=args.debug_extraction,
+        intermediates_dir=args.dump_intermediates,
+        intermediates_tags=frozenset(args.dump_tag),
+        allow_exec=args.allow_exec,
+        stats_overrides=config.TableWarnings(
+            min_rows=args.warn_min_rows,
+            max_empty_fraction=args.warn_empty_cells,
+            warn_touches_edge=args.warn_edge,
+        ),
+        time_budget=args.time_budget,
+        budget_action=args.over_budget,
+    )
+    if args.csv_encoding is not None:
+        ext_cfg = dataclasses.replace(ext_cfg, csv_encoding=args.csv_encoding)
+
+    json_progress: Optional[JsonLinesProgress] = None
+    if args.progress_format == ProgressFormat.JSONL and not args.no_progress:
+        json_progress = JsonLinesProgress(sys.stdout)
+
     def on_error(error: str) -> None:
         print(error, file=sys.stderr)
+        if json_progress is not None:
+            json_progress.error(error)
+
+    run_metrics = metrics.Metrics()
+    observers: list[Callable[[bookextract.Event], None]] = [run_metrics]
+    if json_progress is not None:
+        observers.append(json_progress)
 
+    # Stopping between tables lets the output be closed cleanly, so that a ZIP
+    # output is still written, rather than being left incomplete.
     with (
-        tabulautil.TabulaClient(force_subprocess=args.tabula_force_subprocess) as tabula_client,
-        _progress_reporter(args.no_progress) as on_progress,
+        cliutil.GracefulStop(
+            "Stopping after the current table, interrupt again to abort."
+        ) as graceful_stop,
+        tabulautil.TabulaClient(
+            force_subprocess=args.tabula_force_subprocess,
+            batch_strategy=args.tabula_batch,
+        ) as tabula_client,
+        _progress_reporter(args.no_progress or json_progress is not None) as progress_observer,
     ):
+        if progress_observer is not None:
+            observers.append(progress_observer)
         bookextract.extract_book(
-            table_reader=tabula_client,
+            table_reader=metrics.TimedTableReader(tabula_client, run_metrics),
             ext_cfg=ext_cfg,
             events=bookextract.ExtractEvents(
-                on_progress=on_progress,
                 on_error=on_error,
-                do_continue=lambda: True,
+                do_continue=graceful_stop.do_continue,
+                observers=observers,
             ),
         )
 
-    return 0
+    print(run_metrics.summary(), file=sys.stderr)
+
+    if graceful_stop.stop_requested:
+        print("Stopped before extracting all tables.", file=sys.stderr)
+        return cliutil.ExitCode.INTERRUPTED
+    return _exit_code(run_metrics)
+
+
+def _exit_code(run_metrics: metrics.Metrics) -> cliutil.ExitCode:
+    if not run_metrics.started:
+        # Extraction stopped before considering any tables, which happens when
+        # the configuration cannot be used.
+        return cliutil.ExitCode.CONFIG
+    if not run_metrics.tables_failed:
+        return cliutil.ExitCode.OK
+    if run_metrics.tables_succeeded:
+        return cliutil.ExitCode.PARTIAL_FAILURE
+    return cliutil.ExitCode.FAILURE
@@ -0,0 +1,64 @@
+# -*- coding: utf-8 -*-
+"""
+Updates configuration and project YAML files that use deprecated field names.
+
+Directories are searched for .yaml files. Other files (such as projects) can
+be given directly. Only the field names are changed, so formatting and
+comments are preserved.
+"""
+
+import argparse
+import pathlib
+import sys
+import textwrap
+
+# Imported to register the project types that may also need fixing.
+from travdata import project  # pylint: disable=unused-import
+from travdata.cli import cliutil
+from travdata.config import cfgfix
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "fix",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "paths",
+        help="YAML files, or directories containing them, to update in place.",
+        type=pathlib.Path,
+        nargs="+",
+        metavar="PATH",
+    )
+    argparser.add_argument(
+        "--check",
+        help=textwrap.dedent(
+            """
+            Report the changes that would be made without writing them, and
+            exit with a failure status if there are any.
+            """
+        ),
+        action="store_true",
+        default=False,
+    )
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    for path in args.paths:
+        if not path.exists():
+            raise cliutil.UsageError(f"{path} does not exist.")
+
+    num_renames = 0
+    for path in cfgfix.yaml_files(args.paths):
+        for rename in cfgfix.fix_file(path, write=not args.check):
+            print(f"{path}: {rename}", file=sys.stderr)
+            num_renames += 1
+
+    if args.check and num_renames:
+        return cliutil.ExitCode.FAILURE
+    return cliutil.ExitCode.OK
@@ -0,0 +1,86 @@
+# -*- coding: utf-8 -*-
+"""
+Creates a starter configuration directory for extracting tables from a new
+book.
+
+The configuration contains a version file, the book's entry in config.yaml, a
+book.yaml with an example group and table (with comments describing what to
+change), a placeholder Tabula template, and a README.
+"""
+
+import argparse
+import pathlib
+import sys
+import textwrap
+
+from travdata import filesio
+from travdata.cli import cliutil
+from travdata.config import cfgerror, cfginit
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "init-template",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "book_id",
+        help=textwrap.dedent(
+            """
+            ID of the new book, such as "core_rulebook_2022". This is also used
+            as the name of the book's directory within the configuration.
+            """
+        ),
+        metavar="BOOK_ID",
+    )
+    argparser.add_argument(
+        "--pdf",
+        help="PDF file of the book. Only its filename is recorded.",
+        type=pathlib.Path,
+        required=True,
+    )
+    argparser.add_argument(
+        "--name",
+        help="Human readable name of the book. Defaults to the PDF's filename stem.",
+    )
+    argparser.add_argument(
+        "--output",
+        "-o",
+        help=textwrap.dedent(
+            """
+            Directory to create the configuration in. This must not exist, or
+            be empty. Defaults to a directory named after BOOK_ID.
+            """
+        ),
+        type=pathlib.Path,
+        metavar="DIR",
+    )
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    try:
+        cfginit.check_book_id(args.book_id)
+    except cfgerror.ConfigurationError as exc:
+        raise cliutil.UsageError(str(exc)) from exc
+
+    output: pathlib.Path = args.output or pathlib.Path(args.book_id)
+    if output.exists() and not (output.is_dir() and not any(output.iterdir())):
+        raise cliutil.UsageError(f"{output} already exists, and is not an empty directory.")
+
+    with filesio.DirReadWriter.new_read_writer(output) as cfg_writer:
+        paths = cfginit.init_config(
+            cfg_writer,
+            book_id=args.book_id,
+            name=args.name or args.pdf.stem,
+            pdf_filename=args.pdf.name,
+        )
+
+    for path in paths:
+        print(output / path, file=sys.stderr)
+    print(f"Created configuration for {args.book_id} in {output}.")
+    return cliutil.ExitCode.OK
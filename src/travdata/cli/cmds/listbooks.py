@@ -1,11 +1,26 @@
 # -*- coding: utf-8 -*-
 """
-Lists the names of books in the configuration.
+Lists the books in the configuration.
+
+The text output has one book ID per line. The JSON output also includes each
+book's name, default PDF filename, number of tables and tags.
 """
 
 import argparse
+import dataclasses
+import json
 
 from travdata import config
+from travdata.cli import cliutil
+
+
+@dataclasses.dataclass
+class _BookInfo:
+    id: str
+    name: str
+    default_filename: str
+    num_tables: int
+    tags: list[str]
 
 
 def add_subparser(subparsers) -> None:
@@ -16,13 +31,32 @@ def add_subparser(subparsers) -> None:
         formatter_class=argparse.RawTextHelpFormatter,
     )
     config.add_config_flag(argparser)
+    cliutil.add_output_format_flag(argparser)
     argparser.set_defaults(run=run)
 
 
 def run(args: argparse.Namespace) -> None:
     """CLI entry point."""
 
+    books: list[_BookInfo] = []
     with config.config_reader(args) as cfg_reader:
         cfg = config.load_config(cfg_reader)
-    for name in sorted(cfg.books):
-        print(name)
+        for book_id in sorted(cfg.books):
+            book = cfg.books[book_id]
+            group = book.load_group(cfg_reader)
+            books.append(
+                _BookInfo(
+                    id=book_id,
+                    name=book.name,
+                    default_filename=book.default_filename,
+                    num_tables=sum(1 for t in group.all_tables() if t.extraction is not None),
+                    tags=sorted(book.tags),
+                )
+            )
+
+    match args.format:
+        case cliutil.OutputFormat.JSON:
+            print(json.dumps([dataclasses.asdict(b) for b in books], indent=2))
+        case _:
+            for b in books:
+                print(b.id)
@@ -0,0 +1,119 @@
+# -*- coding: utf-8 -*-
+"""
+Lists the tables configured for extraction from a book.
+"""
+
+import argparse
+import dataclasses
+import json
+import textwrap
+from typing import Optional
+
+from travdata import config
+from travdata.cli import cliutil
+from travdata.extraction import tabulautil
+
+
+@dataclasses.dataclass
+class _TableInfo:
+    path: str
+    pages: list[int]
+    tags: list[str]
+    title: Optional[str]
+    doc: Optional[str]
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "listtables",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+        prefix_chars="-+",
+    )
+    config.add_config_flag(argparser)
+    cliutil.add_output_format_flag(argparser)
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "--book",
+        help="ID of the book to list tables from.",
+        required=True,
+        metavar="BOOK",
+    )
+    argparser.add_argument(
+        "+t",
+        "--with-tag",
+        dest="with_tag",
+        nargs="*",
+        metavar="TAG",
+        default=[],
+        help=textwrap.dedent(
+            """
+            Only list tables that have any of these tags. --without-tag takes
+            precedence over this.
+            """
+        ),
+    )
+    argparser.add_argument(
+        "-t",
+        "--without-tag",
+        dest="without_tag",
+        nargs="*",
+        metavar="TAG",
+        default=[],
+        help=textwrap.dedent(
+            """
+            Only list tables that do not have any of these tags. This takes
+            precedence over --with-tag.
+            """
+        ),
+    )
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    with_tags = frozenset(args.with_tag)
+    without_tags = frozenset(args.without_tag)
+
+    tables: list[_TableInfo] = []
+    with config.config_reader(args) as cfg_reader:
+        cfg = config.load_config(cfg_reader)
+        try:
+            book = cfg.books[args.book]
+        except KeyError as exc:
+            raise cliutil.UsageError(f"Book {args.book} not found in configuration.") from exc
+
+        for table in book.load_group(cfg_reader).all_tables():
+            if table.extraction is None:
+                continue
+            # Matches the selection of tables by extractcsvtables.
+            if with_tags and not table.tags & with_tags:
+                continue
+            if without_tags & table.tags:
+                continue
+
+            with cfg_reader.open_read(table.tabula_template_path) as f:
+                pages = tabulautil.read_template_pages(f)
+
+            tables.append(
+                _TableInfo(
+                    path=table.file_stem.as_posix(),
+                    pages=sorted(book.page_offset + page for page in pages),
+                    tags=sorted(table.tags),
+                    title=table.title,
+                    doc=table.doc,
+                )
+            )
+
+    tables.sort(key=lambda t: t.path)
+
+    match args.format:
+        case cliutil.OutputFormat.JSON:
+            print(json.dumps([dataclasses.asdict(t) for t in tables], indent=2))
+        case _:
+            for t in tables:
+                pages_str = ",".join(str(p) for p in t.pages)
+                print(f"{t.path}\tpages {pages_str}\t{','.join(t.tags)}\t{t.title or ''}")
+
+    return cliutil.ExitCode.OK
@@ -0,0 +1,80 @@
+# -*- coding: utf-8 -*-
+"""
+Packages a configuration directory into a distributable ZIP file.
+
+The configuration is checked to load cleanly before packaging. The package
+includes a version file, a changelog (a stub is generated if the
+configuration has none), any licence files, a MANIFEST.json describing the
+books, tables and tags that the configuration provides, and a SHA256SUMS
+checksum manifest.
+"""
+
+import argparse
+import pathlib
+
+from travdata.cli import cliutil
+from travdata.config import cfgerror, cfgpackage
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "package",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "config_dir",
+        help="Path to the configuration directory to package.",
+        type=pathlib.Path,
+        metavar="CONFIG_DIR",
+    )
+    argparser.add_argument(
+        "package_zip",
+        help="Path to the ZIP file to create. This must not already exist.",
+        type=pathlib.Path,
+        metavar="PACKAGE.ZIP",
+    )
+    argparser.add_argument(
+        "--version",
+        help="Semantic version of the package, such as 1.2.3.",
+        required=True,
+    )
+    argparser.add_argument(
+        "--licence",
+        help="Licence file to include in the package. May be given multiple times.",
+        type=pathlib.Path,
+        action="append",
+        metavar="FILE",
+        default=[],
+    )
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    if not args.config_dir.is_dir():
+        raise cliutil.UsageError(f"{args.config_dir} is not a directory.")
+    if args.package_zip.exists():
+        raise cliutil.UsageError(f"{args.package_zip} already exists.")
+    if not cfgpackage.is_semantic_version(args.version):
+        raise cliutil.UsageError(f"--version {args.version!r} is not a semantic version.")
+    for licence_path in args.licence:
+        if not licence_path.is_file():
+            raise cliutil.UsageError(f"Licence file {licence_path} does not exist.")
+
+    try:
+        cfgpackage.package_config(
+            version=args.version,
+            config_dir=args.config_dir,
+            licence_paths=args.licence,
+            package_zip=args.package_zip,
+        )
+    except cfgerror.ConfigurationError as exc:
+        raise cfgerror.ConfigurationError(
+            f"Configuration is not valid for packaging:\n{exc}"
+        ) from exc
+
+    print(f"Packaged {args.config_dir} version {args.version} into {args.package_zip}.")
+    return cliutil.ExitCode.OK
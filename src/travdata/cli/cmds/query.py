@@ -0,0 +1,124 @@
+# -*- coding: utf-8 -*-
+"""
+Searches extracted tables for cells matching a pattern.
+
+Reads the output of extractcsvtables, and prints each matching row along with
+the table and row number that it was found in.
+"""
+
+import argparse
+import csv
+import dataclasses
+import json
+import pathlib
+import re
+import textwrap
+from typing import Iterator, Optional
+
+from travdata import csvutil, filesio
+from travdata.cli import cliutil
+from travdata.extraction import index
+
+
+@dataclasses.dataclass
+class _Match:
+    table_path: str
+    # 1-based row number within the CSV file, including any header row.
+    row_number: int
+    row: list[str]
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "query",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "output",
+        help="Path to the directory or ZIP file that tables were extracted into.",
+        type=pathlib.Path,
+        metavar="OUTPUT_PATH",
+    )
+    argparser.add_argument(
+        "pattern",
+        help="Regular expression to search for within cells.",
+        metavar="PATTERN",
+    )
+    argparser.add_argument(
+        "--ignore-case",
+        "-i",
+        help="Match the pattern case-insensitively.",
+        action="store_true",
+        default=False,
+    )
+    argparser.add_argument(
+        "--column",
+        help=textwrap.dedent(
+            """
+            Only match cells in columns with this name in the table's first
+            row.
+            """
+        ),
+        metavar="NAME",
+        default=None,
+    )
+    argparser.add_argument(
+        "--tag",
+        help="Only search tables that have all of these tags.",
+        nargs="*",
+        metavar="TAG",
+        default=[],
+    )
+    cliutil.add_output_format_flag(argparser)
+
+
+def _search_table(
+    reader: filesio.Reader,
+    path: pathlib.PurePath,
+    rx: re.Pattern,
+    column: Optional[str],
+) -> Iterator[_Match]:
+    with csvutil.open_by_reader(reader, path) as f:
+        rows = csv.reader(f)
+        column_index: Optional[int] = None
+        for row_number, row in enumerate(rows, start=1):
+            if column is not None and row_number == 1:
+                if column not in row:
+                    return
+                column_index = row.index(column)
+                continue
+
+            cells = row if column_index is None else row[column_index : column_index + 1]
+            if any(rx.search(cell) for cell in cells):
+                yield _Match(table_path=path.as_posix(), row_number=row_number, row=row)
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    try:
+        rx = re.compile(args.pattern, re.IGNORECASE if args.ignore_case else 0)
+    except re.error as exc:
+        raise cliutil.UsageError(f"Invalid pattern: {exc}") from exc
+
+    matches: list[_Match] = []
+    with filesio.new_reader(args.output) as reader:
+        try:
+            output_index = index.Index.read(reader)
+        except filesio.NotFoundError as exc:
+            raise cliutil.UsageError(f"{args.output} does not contain an index.csv.") from exc
+
+        for path in sorted(output_index.paths_with_all_tags(args.tag)):
+            matches.extend(_search_table(reader, path, rx, args.column))
+
+    match args.format:
+        case cliutil.OutputFormat.JSON:
+            print(json.dumps([dataclasses.asdict(m) for m in matches], indent=2))
+        case _:
+            for m in matches:
+                print(f"{m.table_path}:{m.row_number}: {' | '.join(m.row)}")
+
+    return cliutil.ExitCode.OK
@@ -0,0 +1,45 @@
+# -*- coding: utf-8 -*-
+"""
+Runs the extraction described by a project file.
+
+The project's book ID and paths may refer to environment variables as
+${NAME}, or ${NAME:-default} to use a default value if NAME is unset.
+"""
+
+import argparse
+import os
+import pathlib
+import sys
+
+from travdata import envinterp, project
+from travdata.cli.cmds import extractcsvtables
+
+
+def add_subparser(subparsers) -> None:
+    """Adds a subcommand parser to ``subparsers``."""
+    argparser: argparse.ArgumentParser = subparsers.add_parser(
+        "run",
+        description=__doc__,
+        formatter_class=argparse.RawTextHelpFormatter,
+    )
+    argparser.set_defaults(run=run)
+
+    argparser.add_argument(
+        "project",
+        help=f"Path to the project file (usually with a {project.PROJECT_SUFFIX} suffix).",
+        type=pathlib.Path,
+        metavar="PROJECT",
+    )
+
+    extractcsvtables.add_run_flags(argparser)
+
+
+def run(args: argparse.Namespace) -> int:
+    """CLI entry point."""
+    interpolator = envinterp.Interpolator(os.environ)
+    proj = project.load_project(args.project).interpolated(interpolator)
+    proj = proj.resolved(args.project.parent)
+    for line in interpolator.report():
+        print(f"Substituted {line}", file=sys.stderr)
+    ext_cfg = proj.extraction_config(extractcsvtables.output_symlink_policy(args))
+    return extractcsvtables.run_extraction(args, ext_cfg)
@@ -751,9 +751,11 @@ def process(args: argparse.Namespace) -> None:
     )
 
 
-def run(args: argparse.Namespace) -> None:
+def run(args: argparse.Namespace) -> int:
     """Entrypoint for the program."""
     try:
         process(args)
     except UserError as e:
         print(f"Error: {e}", file=sys.stderr)
+        return cliutil.ExitCode.USAGE
+    return cliutil.ExitCode.OK
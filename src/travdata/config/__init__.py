@@ -14,18 +14,72 @@ from __future__ import annotations
 import argparse
 import contextlib
 import dataclasses
+import enum
 import pathlib
 import sys
 import textwrap
 from typing import Any, ClassVar, Iterator, Optional, Self
 
 from travdata import filesio, travdatarelease, yamlutil
-from travdata.config import yamlreg
-from travdata.config import cfgextract
+from travdata.config import cfgerror, cfgextract, yamlreg
 
 
 TABULA_TEMPLATE_SUFFIX = ".tabula-template.json"
-_VERSION_FILE = pathlib.PurePath("version.txt")
+VERSION_PATH = pathlib.PurePath("version.txt")
+OUTPUT_EXT = "csv"
+
+
+def format_output_path(pattern: str, file_stem: pathlib.PurePath) -> pathlib.PurePath:
+    """Formats an output path pattern for a table.
+
+    The pattern is formatted as by ``str.format``, with the following
+    variables:
+
+    * ``book_id``: ID of the book containing the table.
+    * ``group_path``: Path of the group containing the table, including the
+      book ID, e.g. "core/trade".
+    * ``group_name``: Name of the group containing the table.
+    * ``table_name``: Name of the table.
+    * ``ext``: Extension of the output file, without the leading ".".
+
+    :param pattern: Pattern to format, e.g. "{group_path}/{table_name}.{ext}".
+    :param file_stem: ``Table.file_stem`` of the table.
+    :return: Output path, relative to the output directory.
+    :raises cfgerror.ConfigurationError: If the pattern is invalid, or does not
+    produce a relative path within the output directory.
+    """
+    try:
+        output = pattern.format(
+            book_id=file_stem.parts[0],
+            group_path=file_stem.parent.as_posix(),
+            group_name=file_stem.parent.name,
+            table_name=file_stem.name,
+            ext=OUTPUT_EXT,
+        )
+    except (KeyError, IndexError, ValueError) as exc:
+        raise cfgerror.ConfigurationError(
+            f"invalid output pattern {pattern!r} for table {file_stem}: {exc!r}"
+        ) from exc
+    path = pathlib.PurePosixPath(output)
+    if not output or path.is_absolute() or ".." in path.parts:
+        raise cfgerror.ConfigurationError(
+            f"output pattern {pattern!r} for table {file_stem} produced {output!r}, "
+            "which is not a relative path within the output"
+        )
+    return pathlib.PurePath(path)
+
+
+class CachePolicy(enum.StrEnum):
+    """Whether a table that already exists in the output is extracted again."""
+
+    # Kept unless extraction is set to overwrite existing tables.
+    AUTO = "auto"
+    # Always kept, such as for tables that are slow to extract, but whose
+    # output does not change.
+    ALWAYS = "always"
+    # Never kept, such as for tables whose output depends on more than the PDF
+    # and their configuration.
+    NEVER = "never"
 
 
 @dataclasses.dataclass
@@ -33,8 +87,8 @@ class Table:
     """Defines metadata and extraction configuration relating to a single table.
 
     The "path" of group names and the table name form the path for both the
-    ``.tabula-template.json`` file within the configuration directory and the
-    output ``.csv`` file in the output directory.
+    ``.tabula-template.json`` file within the configuration directory and (by
+    default) the output ``.csv`` file in the output directory.
     """
 
     file_stem: pathlib.PurePath
@@ -42,12 +96,32 @@ class Table:
     extraction: Optional[cfgextract.TableExtraction] = dataclasses.field(
         default_factory=cfgextract.TableExtraction
     )
+    # Pattern for ``output_path``, as accepted by ``format_output_path``.
+    output_pattern: Optional[str] = None
+    # Expected maximum time to extract the table, in seconds.
+    time_budget: Optional[float] = None
+    # Whether an existing output of the table is kept, rather than extracted
+    # again.
+    cache: CachePolicy = CachePolicy.AUTO
+    # Tuning of how Tabula reads the table.
+    tabula_options: Optional[cfgextract.TabulaOptions] = None
+    # Human readable name of the table, such as its caption in the book.
+    title: Optional[str] = None
+    # Notes about the table for configuration authors and users of the output.
+    doc: Optional[str] = None
 
     @property
     def tabula_template_path(self) -> pathlib.PurePath:
         """Path to the Tabula template, assuming that it exists."""
         return self.file_stem.with_suffix(TABULA_TEMPLATE_SUFFIX)
 
+    @property
+    def output_path(self) -> pathlib.PurePath:
+        """Path to the output file, relative to the output directory."""
+        if self.output_pattern is None:
+            return self.file_stem.with_suffix(f".{OUTPUT_EXT}")
+        return format_output_path(self.output_pattern, self.file_stem)
+
 
 @dataclasses.dataclass
 class Group:
@@ -62,6 +136,19 @@ class Group:
     tags: set[str] = dataclasses.field(default_factory=set)
     tables: dict[str, Table] = dataclasses.field(default_factory=dict)
     groups: dict[str, "Group"] = dataclasses.field(default_factory=dict)
+    # If true, the tables within this group and its descendent groups are also
+    # combined into a single JSON document in the output.
+    aggregate_json: bool = False
+    # If true, each table in the aggregate JSON document is an array of objects
+    # keyed by the table's header row, rather than an array of rows.
+    json_objects: bool = False
+    # If true, numeric cells in the aggregate JSON document are converted to
+    # numbers, and empty cells to null.
+    json_coerce_types: bool = False
+    # Human readable name of the group, such as a chapter title.
+    title: Optional[str] = None
+    # Notes about the group for configuration authors.
+    doc: Optional[str] = None
 
     def all_tables(self) -> Iterator[Table]:
         """Iterates over all tables in this group and its child groups.
@@ -72,6 +159,79 @@ class Group:
         for group in self.groups.values():
             yield from group.all_tables()
 
+    def all_groups(self) -> Iterator["Group"]:
+        """Iterates over this group and all of its descendent groups.
+
+        :yield: This group, then descendent groups.
+        """
+        yield self
+        for group in self.groups.values():
+            yield from group.all_groups()
+
+    @property
+    def aggregate_json_path(self) -> pathlib.PurePath:
+        """Path to the output aggregate JSON document, if ``aggregate_json`` is set."""
+        return self.rel_dir.with_suffix(".json")
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class BookMetadata(yamlutil.YamlMappingMixin):
+    """Bibliographic metadata about a book, and its configuration."""
+
+    yaml_tag: ClassVar = "!BookMetadata"
+    publisher: Optional[str] = None
+    edition: Optional[str] = None
+    isbn: Optional[str] = None
+    template_authors: list[str] = dataclasses.field(default_factory=list)
+
+    def validate(self, book_id: str) -> None:
+        """Checks the metadata for errors.
+
+        :param book_id: ID of the book, for error messages.
+        :raises cfgerror.ConfigurationError: If the metadata is invalid.
+        """
+        if self.isbn is not None and not _is_valid_isbn(self.isbn):
+            raise cfgerror.ConfigurationError(
+                f"{self.yaml_tag}.isbn of book {book_id} is not a valid ISBN: {self.isbn!r}"
+            )
+
+
+def _is_valid_isbn(isbn: str) -> bool:
+    digits = isbn.replace("-", "").replace(" ", "").upper()
+    if len(digits) == 10:
+        if not digits[:9].isdigit() or not (digits[9].isdigit() or digits[9] == "X"):
+            return False
+        values = [int(d) for d in digits[:9]] + [10 if digits[9] == "X" else int(digits[9])]
+        return sum((10 - i) * v for i, v in enumerate(values)) % 11 == 0
+    if len(digits) == 13:
+        if not digits.isdigit():
+            return False
+        return sum((3 if i % 2 else 1) * int(d) for i, d in enumerate(digits)) % 10 == 0
+    return False
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class PostProcessCommand(yamlutil.YamlMappingMixin):
+    """Command to run on each output file after it has been written.
+
+    Commands are only run when explicitly allowed at extraction time, as they
+    can do anything that the user running the extraction can do.
+    """
+
+    yaml_tag: ClassVar = "!PostProcessCommand"
+    # Command and its arguments. Occurrences of "{path}" within arguments are
+    # replaced with the path to the output file. The command is run from the
+    # top-level output directory.
+    command: list[str]
+    # Maximum time in seconds that the command may run for.
+    timeout: Optional[float] = None
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(command=[])
+
 
 @dataclasses.dataclass
 class Book:
@@ -82,6 +242,8 @@ class Book:
     default_filename: str
     tags: set[str] = dataclasses.field(default_factory=set)
     page_offset: int = 1
+    metadata: BookMetadata = dataclasses.field(default_factory=BookMetadata)
+    post_process: list[PostProcessCommand] = dataclasses.field(default_factory=list)
     _group: Optional[Group] = None
 
     def load_group(self, cfg_reader: filesio.Reader) -> Group:
@@ -91,11 +253,111 @@ class Book:
         return self._group
 
 
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class TableWarnings(yamlutil.YamlMappingMixin):
+    """Thresholds at which statistics about extracted tables produce warnings.
+
+    Unset fields use the built-in defaults. Any set on the command line take
+    precedence.
+    """
+
+    yaml_tag: ClassVar = "!TableWarnings"
+    min_rows: Optional[int] = dataclasses.field(default=None, metadata=yamlutil.INT_METADATA)
+    max_empty_fraction: Optional[float] = dataclasses.field(
+        default=None, metadata={yamlutil.FROM_YAML: float}
+    )
+    warn_touches_edge: Optional[bool] = dataclasses.field(
+        default=None, metadata=yamlutil.BOOL_METADATA
+    )
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class Systems(yamlutil.YamlMappingMixin):
+    """Settings for parts of the extraction process that apply to all books.
+
+    This allows settings that would otherwise need command line flags to be
+    kept with the configuration that needs them.
+    """
+
+    yaml_tag: ClassVar = "!Systems"
+    table_warnings: TableWarnings = dataclasses.field(default_factory=TableWarnings)
+    # Attribution or licence notice to include with every extracted book, such
+    # as the terms under which the extracted data may be shared.
+    attribution: Optional[str] = None
+
+
 @dataclasses.dataclass
 class Config:
     """Top-level configuration."""
 
     books: dict[str, Book] = dataclasses.field(default_factory=dict)
+    systems: Systems = dataclasses.field(default_factory=Systems)
+
+
+class _Inherit:
+    """Type of ``_INHERIT``."""
+
+    def __bool__(self) -> bool:
+        # Falsy, so that it is omitted when serialising.
+        return False
+
+    def __repr__(self) -> str:
+        return "_INHERIT"
+
+
+# Marks a field that was not specified, and so inherits its value from any
+# applicable ``TableDefaults``.
+_INHERIT = _Inherit()
+
+
+def _parse_cache_policy(value: str) -> CachePolicy:
+    try:
+        return CachePolicy(value)
+    except ValueError as exc:
+        choices = ", ".join(policy.value for policy in CachePolicy)
+        raise cfgerror.ConfigurationError(
+            f"cache must be one of {choices}, but is {value!r}"
+        ) from exc
+
+
+_CACHE_POLICY_METADATA = {yamlutil.TO_YAML: str, yamlutil.FROM_YAML: _parse_cache_policy}
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class TableDefaults(yamlutil.YamlMappingMixin):
+    """Default values for tables within a group and its descendent groups.
+
+    Fields that are unset inherit from any ``TableDefaults`` of a parent group.
+    """
+
+    yaml_tag: ClassVar = "!TableDefaults"
+    extraction: Optional[cfgextract.TableExtraction] = None
+    # See ``format_output_path`` for the variables available.
+    output_pattern: Optional[str] = None
+    time_budget: Optional[float] = dataclasses.field(
+        default=None, metadata={yamlutil.FROM_YAML: float}
+    )
+    cache: Optional[CachePolicy] = dataclasses.field(
+        default=None, metadata=_CACHE_POLICY_METADATA
+    )
+    tabula_options: Optional[cfgextract.TabulaOptions] = None
+
+    def merged_over(self, parent: "TableDefaults") -> "TableDefaults":
+        """Returns the defaults that result from overriding ``parent`` with self."""
+        return TableDefaults(
+            extraction=self.extraction if self.extraction is not None else parent.extraction,
+            output_pattern=(
+                self.output_pattern if self.output_pattern is not None else parent.output_pattern
+            ),
+            time_budget=self.time_budget if self.time_budget is not None else parent.time_budget,
+            cache=self.cache if self.cache is not None else parent.cache,
+            tabula_options=(
+                self.tabula_options if self.tabula_options is not None else parent.tabula_options
+            ),
+        )
 
 
 @dataclasses.dataclass
@@ -103,13 +365,24 @@ class Config:
 class _YamlTable(yamlutil.YamlMappingMixin):
     yaml_tag: ClassVar = "!Table"
     tags: set[str] = dataclasses.field(default_factory=set, metadata=yamlutil.SET_METADATA)
-    extraction: Optional[cfgextract.TableExtraction] = None
+    extraction: Optional[cfgextract.TableExtraction] | _Inherit = _INHERIT
+    output_pattern: Optional[str] = None
+    time_budget: Optional[float] = dataclasses.field(
+        default=None, metadata={yamlutil.FROM_YAML: float}
+    )
+    cache: Optional[CachePolicy] = dataclasses.field(
+        default=None, metadata=_CACHE_POLICY_METADATA
+    )
+    tabula_options: Optional[cfgextract.TabulaOptions] = None
+    title: Optional[str] = None
+    doc: Optional[str] = None
 
     def prepare(
         self,
         name: str,
         rel_group_dir: pathlib.PurePath,
         parent_tags: set[str],
+        defaults: TableDefaults,
     ) -> Table:
         """Creates a ``Table`` from self.
 
@@ -117,14 +390,38 @@ class _YamlTable(yamlutil.YamlMappingMixin):
         :param rel_group_dir: Path to the directory of the table's parent
         group's directory, relative to the top-level config directory.
         :param parent_tags: Tags to inherit from parent ``Group``.
+        :param defaults: Defaults for fields not set on the table.
         :return: Prepared ``Table``.
+        :raises cfgerror.ConfigurationError: If the output pattern is invalid.
         """
         tags = self.tags | parent_tags
-        return Table(
+        extraction = self.extraction
+        if isinstance(extraction, _Inherit):
+            extraction = defaults.extraction
+        output_pattern = self.output_pattern
+        if output_pattern is None:
+            output_pattern = defaults.output_pattern
+        time_budget = self.time_budget
+        if time_budget is None:
+            time_budget = defaults.time_budget
+        cache = self.cache or defaults.cache or CachePolicy.AUTO
+        tabula_options = self.tabula_options
+        if tabula_options is None:
+            tabula_options = defaults.tabula_options
+        table = Table(
             file_stem=rel_group_dir / name,
             tags=tags,
-            extraction=self.extraction,
+            extraction=extraction,
+            output_pattern=output_pattern,
+            time_budget=time_budget,
+            cache=cache,
+            tabula_options=tabula_options,
+            title=self.title,
+            doc=self.doc,
         )
+        # Validates the pattern early, rather than when first extracting.
+        _ = table.output_path
+        return table
 
 
 @dataclasses.dataclass
@@ -133,6 +430,12 @@ class _YamlGroup(yamlutil.YamlMappingMixin):
     yaml_tag: ClassVar = "!Group"
     tags: set[str] = dataclasses.field(default_factory=set, metadata=yamlutil.SET_METADATA)
     templates: Optional[list[cfgextract.TableExtraction]] = None
+    table_defaults: Optional[TableDefaults] = None
+    aggregate_json: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+    json_objects: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+    json_coerce_types: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+    title: Optional[str] = None
+    doc: Optional[str] = None
     groups: dict[str, "_YamlGroup"] = dataclasses.field(default_factory=dict)
     tables: dict[str, _YamlTable] = dataclasses.field(default_factory=dict)
 
@@ -140,26 +443,38 @@ class _YamlGroup(yamlutil.YamlMappingMixin):
         self,
         rel_group_dir: pathlib.PurePath,
         parent_tags: set[str],
+        parent_defaults: TableDefaults,
     ) -> Group:
         """Creates a ``Group`` from self.
 
         :param rel_group_dir: Path to the directory of this group's directory,
         relative to the top-level config directory.
         :param parent_tags: Tags to inherit from parent ``Group``.
+        :param parent_defaults: Table defaults to inherit from parent ``Group``.
         :return: Prepared ``Group``.
         """
         tags = self.tags | parent_tags
+        defaults = parent_defaults
+        if self.table_defaults is not None:
+            defaults = self.table_defaults.merged_over(parent_defaults)
         return Group(
             rel_dir=rel_group_dir,
             tags=tags,
             tables={
-                name: table.prepare(name, rel_group_dir, parent_tags=tags)
+                name: table.prepare(name, rel_group_dir, parent_tags=tags, defaults=defaults)
                 for name, table in self.tables.items()
             },
             groups={
-                name: group.prepare(rel_group_dir / name, parent_tags=tags)
+                name: group.prepare(
+                    rel_group_dir / name, parent_tags=tags, parent_defaults=defaults
+                )
                 for name, group in self.groups.items()
             },
+            aggregate_json=self.aggregate_json,
+            json_objects=self.json_objects,
+            json_coerce_types=self.json_coerce_types,
+            title=self.title,
+            doc=self.doc,
             # templates not included, as it is only for use in anchoring and
             # aliasing by the cfgyaml.YAML.file author at the time of YAML parsing.
         )
@@ -173,6 +488,8 @@ class _YamlBook(yamlutil.YamlMappingMixin):
     default_filename: str
     tags: set[str] = dataclasses.field(default_factory=set, metadata=yamlutil.SET_METADATA)
     page_offset: int = 1
+    metadata: Optional[BookMetadata] = None
+    post_process: list[PostProcessCommand] = dataclasses.field(default_factory=list)
 
     @classmethod
     def yaml_create_empty(cls) -> Self:
@@ -189,12 +506,16 @@ class _YamlBook(yamlutil.YamlMappingMixin):
         :return: Prepared ``Book``.
         """
         tags = self.tags | {f"book/{book_id}"}
+        metadata = self.metadata or BookMetadata()
+        metadata.validate(book_id)
         return Book(
             id_=book_id,
             name=self.name,
             default_filename=self.default_filename,
             tags=tags,
             page_offset=self.page_offset,
+            metadata=metadata,
+            post_process=self.post_process,
         )
 
 
@@ -203,6 +524,7 @@ class _YamlBook(yamlutil.YamlMappingMixin):
 class _YamlConfig(yamlutil.YamlMappingMixin):
     yaml_tag: ClassVar = "!Config"
     books: dict[str, _YamlBook]
+    systems: Optional[Systems] = None
 
     @classmethod
     def yaml_create_empty(cls) -> Self:
@@ -217,7 +539,7 @@ class _YamlConfig(yamlutil.YamlMappingMixin):
         books: dict[str, Book] = {}
         for book_id, yaml_book in self.books.items():
             books[book_id] = yaml_book.prepare(book_id=book_id)
-        return Config(books=books)
+        return Config(books=books, systems=self.systems or Systems())
 
 
 def _prepare_group(
@@ -230,6 +552,7 @@ def _prepare_group(
     return yaml_group.prepare(
         rel_group_dir=rel_book_dir,
         parent_tags=parent_tags,
+        parent_defaults=TableDefaults(),
     )
 
 
@@ -270,7 +593,7 @@ def _prepare_config(cfg: Any | _YamlConfig) -> Config:
 def load_config_version(cfg_reader: filesio.Reader) -> Optional[str]:
     """Loads the configuration version from the ``cfg_reader``."""
     try:
-        with cfg_reader.open_read(_VERSION_FILE) as f:
+        with cfg_reader.open_read(VERSION_PATH) as f:
             return f.read().rstrip()
     except filesio.NotFoundError:
         return None
@@ -278,7 +601,7 @@ def load_config_version(cfg_reader: filesio.Reader) -> Optional[str]:
 
 def save_config_version(cfg_writer: filesio.ReadWriter, version: str) -> None:
     """Writes the configuration version to the ``cfg_writer``."""
-    with cfg_writer.open_write(_VERSION_FILE) as f:
+    with cfg_writer.open_write(VERSION_PATH) as f:
         f.write(version)
 
 
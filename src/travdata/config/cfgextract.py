@@ -13,6 +13,106 @@ class TableTransform(abc.ABC):
     """Marker base class for configuration of table transformations."""
 
 
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class CellType(yamlutil.YamlMappingMixin):
+    """A type of cell value to recognise by ``AnnotateCellTypes``."""
+
+    yaml_tag: ClassVar = "!CellType"
+    # Name of the type, as given for matching cells.
+    name: str
+    # Matched against the whole of the cell text, using `Pattern.fullmatch`.
+    pattern: str
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(name="", pattern="")
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class AnnotateCellTypes(TableTransform, yamlutil.YamlMappingMixin):
+    """Classifies the type of each cell, leaving the rows unchanged.
+
+    Each non-empty cell after the header is classified as the first of `types`
+    that matches it, or "text" if none match. The types are output as a table
+    parallel to the rows, with empty cells and the header given an empty type.
+    Extraction writes this alongside the table's CSV file, with the suffix
+    ".types.csv".
+
+    This must be the last transform, so that the types are parallel to the
+    rows that are output.
+    """
+
+    yaml_tag: ClassVar = "!AnnotateCellTypes"
+    # Types to recognise, in order of precedence. If empty, a built-in set of
+    # types is used: integer, number, range (such as "2-5" or "12+"), dice
+    # (such as "2D" or "1D+3"), credits (such as "Cr500" or "1.5MCr") and
+    # tonnage (such as "20 tons").
+    types: list[CellType] = dataclasses.field(default_factory=list)
+    # Number of rows at the start of the table that form the header.
+    header_rows: int = 1
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class CellPattern(yamlutil.YamlMappingMixin):
+    """A pattern that cells in a column must match, checked by ``AssertTable``."""
+
+    yaml_tag: ClassVar = "!CellPattern"
+    # Index of the column to check.
+    column: int = dataclasses.field(metadata=yamlutil.INT_METADATA)
+    # Matched against the whole of the cell text, using `Pattern.fullmatch`.
+    pattern: str
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(column=0, pattern="")
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class AssertTable(TableTransform, yamlutil.YamlMappingMixin):
+    """Checks invariants of the table, leaving it unchanged.
+
+    If any check fails, then extraction of the table fails with a description
+    of the rows and columns that failed, and the table is not written. This
+    turns changes in the extracted data into explicit errors.
+    """
+
+    yaml_tag: ClassVar = "!AssertTable"
+    # Number of rows at the start of the table that form the header.
+    header_rows: int = 1
+    # Minimum number of rows after the header.
+    min_rows: int = 0
+    # Names that must each be the text of a cell in the header.
+    required_columns: list[str] = dataclasses.field(default_factory=list)
+    # Columns that must not be empty in any row after the header.
+    non_empty_columns: list[int] = dataclasses.field(default_factory=list)
+    # Patterns that the non-empty cells in rows after the header must match.
+    cell_patterns: list[CellPattern] = dataclasses.field(default_factory=list)
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class DropRepeatedHeader(TableTransform, yamlutil.YamlMappingMixin):
+    """Drops repeats of the leading header rows.
+
+    This is useful for tables that span multiple pages, where the header is
+    repeated at the top of each page.
+    """
+
+    yaml_tag: ClassVar = "!DropRepeatedHeader"
+    # Number of rows at the start of the table that form the header.
+    header_rows: int = 1
+    # If set, the first row after a repeated header is merged into the last
+    # row before it when its cell in this column is empty, as the rest of a
+    # record split across the page break. Cells are joined with a space.
+    merge_split_rows_column: Optional[int] = dataclasses.field(
+        default=None, metadata=yamlutil.INT_METADATA
+    )
+
+
 @dataclasses.dataclass
 @yamlreg.YAML.register_class
 class ExpandColumnOnRegex(TableTransform, yamlutil.YamlMappingMixin):
@@ -42,6 +142,100 @@ class ExpandColumnOnRegex(TableTransform, yamlutil.YamlMappingMixin):
         )
 
 
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class KeyValueField(yamlutil.YamlMappingMixin):
+    """A labelled value to extract from each record by ``ExtractKeyValues``."""
+
+    yaml_tag: ClassVar = "!KeyValueField"
+    # Column name of the value in the output.
+    key: str
+    # Searched for (using `Pattern.search`, with `re.MULTILINE`) within the
+    # text of the record. The value is the first group of the match if the
+    # pattern has groups, otherwise the whole match. The value is empty if the
+    # pattern does not match.
+    pattern: str
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(key="", pattern="")
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class ExtractKeyValues(TableTransform, yamlutil.YamlMappingMixin):
+    """Extracts labelled values from non-tabular text, such as stat blocks.
+
+    Each input row is joined into a line of text. The lines are divided into
+    records, and each record produces an output row of the values of `fields`,
+    following a header row of their keys.
+    """
+
+    yaml_tag: ClassVar = "!ExtractKeyValues"
+    fields: list[KeyValueField] = dataclasses.field(default_factory=list)
+    # Matches (using `Pattern.match`) the first line of each record. Lines
+    # before the first match are discarded. If unset, all lines form a single
+    # record.
+    record_start: Optional[str] = None
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class FillDown(TableTransform, yamlutil.YamlMappingMixin):
+    """Fills empty cells in columns with the value from the row above.
+
+    This restores values from cells that span several rows, which are only
+    extracted into the first of those rows.
+    """
+
+    yaml_tag: ClassVar = "!FillDown"
+    # Columns to fill. Rows without the column are extended to include it.
+    columns: list[int] = dataclasses.field(default_factory=list)
+    # Number of rows at the start of the table (such as headers) that are left
+    # unchanged, and are not filled from.
+    skip_rows: int = 0
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class FillRight(TableTransform, yamlutil.YamlMappingMixin):
+    """Fills empty cells in columns with the value from the cell to the left.
+
+    This restores values from cells that span several columns, which are only
+    extracted into the first of those columns.
+    """
+
+    yaml_tag: ClassVar = "!FillRight"
+    # Columns to fill, which are filled in ascending order so that a value can
+    # spread across several adjacent columns. Column 0 has nothing to its left,
+    # and is never filled.
+    columns: list[int] = dataclasses.field(default_factory=list)
+    # Number of rows at the start of the table (such as headers) that are left
+    # unchanged.
+    skip_rows: int = 0
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class FilterRows(TableTransform, yamlutil.YamlMappingMixin):
+    """Keeps only the rows where a column matches a regex."""
+
+    yaml_tag: ClassVar = "!FilterRows"
+    column: int
+    # Matched against the whole of the cell text, using `Pattern.fullmatch`.
+    # Rows without the column are treated as having an empty cell.
+    pattern: str
+    # If true, drops the matching rows instead of keeping them.
+    invert: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+    # Number of rows at the start of the table (such as headers) that are always
+    # kept.
+    skip_rows: int = 0
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(column=0, pattern="")
+
+
 @dataclasses.dataclass
 @yamlreg.YAML.register_class
 class PrependRow(TableTransform, yamlutil.YamlSequenceMixin):
@@ -135,6 +329,41 @@ class SplitColumn(TableTransform, yamlutil.YamlMappingMixin):
         return cls(column=0, pattern="")
 
 
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class SortKey(yamlutil.YamlMappingMixin):
+    """Specifies a column to sort rows by."""
+
+    yaml_tag: ClassVar = "!SortKey"
+    column: int
+    # If true, compares the first number in each cell rather than the text.
+    # Cells without a number sort after those with a number, even if
+    # descending.
+    numeric: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+    descending: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(column=0)
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class SortRows(TableTransform, yamlutil.YamlMappingMixin):
+    """Sorts rows by one or more columns.
+
+    The sort is stable, so rows that compare equal by all keys retain their
+    order.
+    """
+
+    yaml_tag: ClassVar = "!SortRows"
+    # Keys to sort by, in order of precedence.
+    keys: list[SortKey] = dataclasses.field(default_factory=list)
+    # Number of rows at the start of the table (such as headers) that are left
+    # in place.
+    skip_rows: int = 0
+
+
 @dataclasses.dataclass
 @yamlreg.YAML.register_class
 class WrapRowEveryN(TableTransform, yamlutil.YamlScalarMixin):
@@ -155,3 +384,33 @@ class TableExtraction(yamlutil.YamlSequenceMixin):
 
     yaml_tag: ClassVar = "!TableExtraction"
     transforms: list[TableTransform] = dataclasses.field(default_factory=list)
+
+
+def _floats(values: list) -> list[float]:
+    return [float(value) for value in values]
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class TabulaOptions(yamlutil.YamlMappingMixin):
+    """Tunes how Tabula reads the areas of a table's template.
+
+    Some tables only extract cleanly with these set, where Tabula's own
+    detection of columns or cells goes wrong.
+
+    These are the tuning options that Tabula accepts. Tabula fixes the minimum
+    ruling line length of the "lattice" method and the text tolerances of the
+    "stream" method internally, so they cannot be set here.
+    """
+
+    yaml_tag: ClassVar = "!TabulaOptions"
+    # X coordinates of the boundaries between columns, in PDF points. Areas
+    # using the "stream" method are split into columns at these positions,
+    # rather than where Tabula guesses. Areas using other methods are
+    # unaffected.
+    columns: list[float] = dataclasses.field(
+        default_factory=list, metadata={yamlutil.FROM_YAML: _floats}
+    )
+    # If true, line breaks within a cell are kept as newlines, rather than
+    # being replaced by spaces.
+    use_line_returns: bool = dataclasses.field(default=False, metadata=yamlutil.BOOL_METADATA)
@@ -0,0 +1,124 @@
+# -*- coding: utf-8 -*-
+"""Rewrites YAML files that use deprecated field names.
+
+Only the names of the fields are replaced within the text, so formatting and
+comments are preserved.
+"""
+
+import dataclasses
+import pathlib
+from typing import Any, Iterable, Iterator
+
+from ruamel import yaml
+from travdata import yamlutil
+from travdata.config import cfgerror, yamlreg
+
+
+@dataclasses.dataclass(frozen=True)
+class Rename:
+    """A deprecated field name that was replaced.
+
+    :field line: Line number of the field, starting from 1.
+    :field yaml_tag: Tag of the mapping containing the field.
+    :field old_name: Deprecated name of the field.
+    :field new_name: Current name of the field.
+    """
+
+    line: int
+    yaml_tag: str
+    old_name: str
+    new_name: str
+
+    def __str__(self) -> str:
+        return (
+            f"line {self.line}: renamed field {self.old_name} in {self.yaml_tag} "
+            f"to {self.new_name}"
+        )
+
+
+def renames_by_tag() -> dict[str, dict[str, str]]:
+    """Returns the field renames of each registered YAML mapping type, by tag."""
+    renames: dict[str, dict[str, str]] = {}
+    for cls in yamlreg.YAML.representer.yaml_representers:
+        if not (isinstance(cls, type) and issubclass(cls, yamlutil.YamlMappingMixin)):
+            continue
+        if cls_renames := yamlutil.renamed_fields(cls):
+            renames[cls.yaml_tag] = cls_renames
+    return renames
+
+
+def _find_renames(
+    node: Any,
+    renames: dict[str, dict[str, str]],
+    visited: set[int],
+) -> Iterator[tuple[yaml.Node, Rename]]:
+    # Anchored nodes are visited once, however many times they are aliased.
+    if id(node) in visited:
+        return
+    visited.add(id(node))
+
+    if isinstance(node, yaml.MappingNode):
+        tag_renames = renames.get(node.tag, {})
+        keys = {key_node.value for key_node, _ in node.value}
+        for key_node, value_node in node.value:
+            new_name = tag_renames.get(key_node.value)
+            if new_name is not None and new_name not in keys:
+                rename = Rename(
+                    line=key_node.start_mark.line + 1,
+                    yaml_tag=node.tag,
+                    old_name=key_node.value,
+                    new_name=new_name,
+                )
+                yield key_node, rename
+            yield from _find_renames(value_node, renames, visited)
+    elif isinstance(node, yaml.SequenceNode):
+        for item_node in node.value:
+            yield from _find_renames(item_node, renames, visited)
+
+
+def fix_text(text: str) -> tuple[str, list[Rename]]:
+    """Replaces deprecated field names in YAML text.
+
+    :param text: YAML document.
+    :raises cfgerror.ConfigurationError: If the text is not valid YAML.
+    :return: Updated YAML document, and the renames made.
+    """
+    try:
+        root = yaml.YAML().compose(text)
+    except yaml.YAMLError as exc:
+        raise cfgerror.ConfigurationError(str(exc)) from exc
+
+    found = list(_find_renames(root, renames_by_tag(), set()))
+    # Replace from the end, so that earlier indices remain valid.
+    for key_node, rename in sorted(found, key=lambda f: f[0].start_mark.index, reverse=True):
+        text = (
+            text[: key_node.start_mark.index] + rename.new_name + text[key_node.end_mark.index :]
+        )
+    return text, [rename for _, rename in found]
+
+
+def fix_file(path: pathlib.Path, write: bool) -> list[Rename]:
+    """Replaces deprecated field names in a YAML file.
+
+    :param path: YAML file to fix.
+    :param write: If true, write the updated file in place.
+    :raises cfgerror.ConfigurationError: If the file is not valid YAML.
+    :return: Renames made (or that would be made).
+    """
+    text = path.read_text(encoding="utf-8")
+    try:
+        fixed, renames = fix_text(text)
+    except cfgerror.ConfigurationError as exc:
+        raise cfgerror.ConfigurationError(f"{path}: {exc}") from exc
+    if write and renames:
+        path.write_text(fixed, encoding="utf-8")
+    return renames
+
+
+def yaml_files(paths: Iterable[pathlib.Path]) -> Iterator[pathlib.Path]:
+    """Yields the given files, and the ``.yaml`` files within the given directories."""
+    for path in paths:
+        if path.is_dir():
+            yield from sorted(path.rglob("*.yaml"))
+        else:
+            yield path
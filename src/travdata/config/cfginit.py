@@ -0,0 +1,146 @@
+# -*- coding: utf-8 -*-
+"""Creates a starter configuration for a new book."""
+
+import pathlib
+import re
+
+from travdata import config, filesio
+from travdata.config import cfgerror
+
+
+README_PATH = pathlib.PurePath("README.md")
+INITIAL_VERSION = "0.1.0"
+
+# Book IDs are used as directory names, and in output paths.
+_BOOK_ID_RX = re.compile(r"[a-z0-9][a-z0-9_-]*")
+
+_CONFIG_YAML = """\
+!Config
+books:
+  # The book ID is also the name of the directory containing its tables.
+  {book_id}: !Book
+    name: {name}
+    # Filename of the PDF, used as the default when extracting.
+    default_filename: {pdf_filename}
+    # Added to PDF page numbers when reporting where tables are, so that they
+    # match the page numbers printed in the book.
+    # page_offset: 1
+    metadata: !BookMetadata
+      publisher: null
+      edition: null
+      template_authors: []
+"""
+
+_BOOK_YAML = """\
+!Group
+# Defaults for all tables within this group and its descendent groups.
+table_defaults: !TableDefaults
+  # Treats the first row as the header, and joins rows that continue onto the
+  # next line (where the first column is empty).
+  extraction: !TableExtraction
+    - !FoldRows
+      - !StaticRowCounts [1]
+      - !EmptyColumn 0
+groups:
+  # Each group is a directory of tables. Rename or replace this example.
+  example-group: !Group
+    tags: [example]
+    tables:
+      # Each table needs a Tabula template named
+      # "<table name>.tabula-template.json" within the group's directory,
+      # which selects the region(s) of the PDF to extract.
+      example-table: !Table
+        title: Example Table
+        doc: Replace with a description of the table.
+"""
+
+# Covers the top half of an A4 page, as a placeholder for a real region.
+_TABULA_TEMPLATE_JSON = """\
+[
+  {
+    "page": 1,
+    "extraction_method": "stream",
+    "x1": 0.0,
+    "x2": 595.0,
+    "y1": 0.0,
+    "y2": 421.0,
+    "width": 595.0,
+    "height": 421.0
+  }
+]
+"""
+
+_README_MD = """\
+# {name} configuration
+
+Configuration for extracting tables from `{pdf_filename}` with travdata.
+
+- `config.yaml` lists the books that this configuration supports.
+- `{book_id}/book.yaml` describes the groups and tables of the book, and how
+  to extract each table.
+- `{book_id}/<group>/<table>.tabula-template.json` selects the regions of the
+  PDF to extract for each table. These can be created by selecting regions in
+  [Tabula](https://tabula.technology/) and exporting them as a template.
+
+To try extracting the tables:
+
+```
+travdata_cli extractcsvtables --config . {book_id} "{pdf_filename}" output
+```
+"""
+
+
+def check_book_id(book_id: str) -> None:
+    """Checks that ``book_id`` can be used as the ID of a new book.
+
+    :raises cfgerror.ConfigurationError: If the ID is not suitable.
+    """
+    if _BOOK_ID_RX.fullmatch(book_id) is None:
+        raise cfgerror.ConfigurationError(
+            f"book ID {book_id!r} must be lowercase letters, digits, '-' and '_', "
+            "starting with a letter or digit"
+        )
+
+
+def init_config(
+    cfg_writer: filesio.ReadWriter,
+    *,
+    book_id: str,
+    name: str,
+    pdf_filename: str,
+) -> list[pathlib.PurePath]:
+    """Writes a starter configuration for a single book.
+
+    The configuration contains an example group and table, with comments
+    describing what to change.
+
+    :param cfg_writer: Collection to write the configuration into.
+    :param book_id: ID of the book.
+    :param name: Human readable name of the book.
+    :param pdf_filename: Filename of the book's PDF.
+    :raises cfgerror.ConfigurationError: If ``book_id`` is not suitable.
+    :return: Paths of the files written.
+    """
+    check_book_id(book_id)
+    book_dir = pathlib.PurePath(book_id)
+    files = {
+        pathlib.PurePath("config.yaml"): _CONFIG_YAML.format(
+            book_id=book_id,
+            name=_yaml_string(name),
+            pdf_filename=_yaml_string(pdf_filename),
+        ),
+        book_dir / "book.yaml": _BOOK_YAML,
+        book_dir / "example-group" / "example-table.tabula-template.json": _TABULA_TEMPLATE_JSON,
+        README_PATH: _README_MD.format(book_id=book_id, name=name, pdf_filename=pdf_filename),
+    }
+    for path, content in files.items():
+        with cfg_writer.open_write(path) as f:
+            f.write(content)
+    config.save_config_version(cfg_writer, INITIAL_VERSION)
+    return sorted([*files, config.VERSION_PATH])
+
+
+def _yaml_string(value: str) -> str:
+    """Returns ``value`` as a double quoted YAML string."""
+    escaped = value.replace("\\", "\\\\").replace('"', '\\"')
+    return f'"{escaped}"'
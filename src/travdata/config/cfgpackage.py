@@ -0,0 +1,330 @@
+# -*- coding: utf-8 -*-
+"""Packages a configuration directory into a distributable ZIP file."""
+
+import dataclasses
+import hashlib
+import json
+import pathlib
+import re
+from typing import Iterable, Iterator, Optional
+
+from travdata import config, filesio, travdatarelease
+from travdata.config import cfgerror
+
+
+# File within a packaged configuration that lists the checksum of every other
+# file, in the format used by ``sha256sum``.
+CHECKSUMS_PATH = pathlib.PurePath("SHA256SUMS")
+CHANGELOG_PATH = pathlib.PurePath("CHANGELOG.md")
+# File within a packaged configuration that describes what it produces, for
+# tools that show or index packages without loading the configuration.
+MANIFEST_PATH = pathlib.PurePath("MANIFEST.json")
+# Incremented when a change to the manifest would prevent older readers from
+# understanding it.
+MANIFEST_FORMAT_VERSION = 1
+
+_SEMVER_RX = re.compile(
+    r"""
+    (0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)
+    (?:-[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*)?
+    (?:\+[0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*)?
+    """,
+    re.VERBOSE,
+)
+
+
+def is_semantic_version(version: str) -> bool:
+    """Returns True if ``version`` is a semantic version, such as "1.2.3"."""
+    return _SEMVER_RX.fullmatch(version) is not None
+
+
+def validate_config(cfg_reader: filesio.Reader) -> Iterator[str]:
+    """Checks that the configuration loads, and refers only to files that exist.
+
+    :param cfg_reader: Configuration to check.
+    :yield: Descriptions of any problems found.
+    """
+    for path in sorted(cfg_reader.iter_files()):
+        with cfg_reader.open_read_binary(path) as f:
+            try:
+                f.read().decode("utf-8")
+            except UnicodeDecodeError:
+                yield f"{path} is not UTF-8 text."
+
+    try:
+        cfg = config.load_config(cfg_reader)
+    except (cfgerror.ConfigurationError, filesio.NotFoundError) as exc:
+        yield f"Could not load configuration: {exc}"
+        return
+
+    for book_id, book in sorted(cfg.books.items()):
+        try:
+            book.metadata.validate(book_id)
+            group = book.load_group(cfg_reader)
+        except (cfgerror.ConfigurationError, filesio.NotFoundError) as exc:
+            yield f"Could not load book {book_id}: {exc}"
+            continue
+
+        for table in group.all_tables():
+            if table.extraction is None:
+                continue
+            if not cfg_reader.exists(table.tabula_template_path):
+                yield f"Table {_describe_table(table)} is missing {table.tabula_template_path}."
+
+
+def _describe_table(table: config.Table) -> str:
+    """Returns the table's path, followed by its title if it has one."""
+    if table.title is None:
+        return str(table.file_stem)
+    return f"{table.file_stem} ({table.title})"
+
+
+@dataclasses.dataclass
+class TableManifest:
+    """Describes a table extracted by a packaged configuration.
+
+    :field path: Path of the table within the configuration, such as
+    "core_rulebook/chapter/table".
+    :field output: Path of the output file, relative to the output directory.
+    :field title: Human readable name of the table, if configured.
+    :field tags: Tags of the table, including those inherited from its groups.
+    """
+
+    path: str
+    output: str
+    title: Optional[str]
+    tags: list[str]
+
+
+@dataclasses.dataclass
+class BookManifest:
+    """Describes a book supported by a packaged configuration.
+
+    :field book_id: ID of the book, as given to ``travdata_cli extractcsvtables``.
+    :field title: Name of the book.
+    :field default_filename: Usual filename of the book's PDF, which must be
+    provided to extract it.
+    :field output_formats: Formats of the output files, "csv" for tables and
+    "json" if any group is aggregated into a JSON document.
+    :field requires_allow_exec: True if the book configures post-processing
+    commands, which are only run when explicitly allowed.
+    :field tags: All tags used by the book's tables, for selecting tables.
+    :field tables: Tables extracted from the book.
+    """
+
+    book_id: str
+    title: str
+    default_filename: str
+    output_formats: list[str]
+    requires_allow_exec: bool
+    tags: list[str]
+    tables: list[TableManifest]
+
+
+@dataclasses.dataclass
+class Manifest:
+    """Describes what a packaged configuration produces.
+
+    :field format_version: Format of the manifest, ``MANIFEST_FORMAT_VERSION``
+    when written.
+    :field version: Version of the package.
+    :field travdata_version: Version of travdata that validated and packaged
+    the configuration.
+    :field books: Books supported by the configuration, sorted by ID.
+    """
+
+    format_version: int
+    version: str
+    travdata_version: str
+    books: list[BookManifest]
+
+
+def build_manifest(cfg_reader: filesio.Reader, version: str) -> Manifest:
+    """Describes what the configuration produces.
+
+    :param cfg_reader: Configuration to describe.
+    :param version: Version of the package.
+    :raises cfgerror.ConfigurationError: If the configuration is invalid.
+    :raises filesio.NotFoundError: If a configuration file is missing.
+    :return: Manifest of the configuration.
+    """
+    cfg = config.load_config(cfg_reader)
+    books: list[BookManifest] = []
+    for book_id, book in sorted(cfg.books.items()):
+        group = book.load_group(cfg_reader)
+        tables = [
+            TableManifest(
+                path=table.file_stem.as_posix(),
+                output=table.output_path.as_posix(),
+                title=table.title,
+                tags=sorted(table.tags),
+            )
+            for table in group.all_tables()
+            if table.extraction is not None
+        ]
+        output_formats = [config.OUTPUT_EXT]
+        if any(g.aggregate_json for g in group.all_groups()):
+            output_formats.append("json")
+        books.append(
+            BookManifest(
+                book_id=book_id,
+                title=book.name,
+                default_filename=book.default_filename,
+                output_formats=output_formats,
+                requires_allow_exec=bool(book.post_process),
+                tags=sorted(set[str]().union(*(table.tags for table in tables))),
+                tables=tables,
+            )
+        )
+    return Manifest(
+        format_version=MANIFEST_FORMAT_VERSION,
+        version=version,
+        travdata_version=travdatarelease.EXECUTABLE_VERSION,
+        books=books,
+    )
+
+
+def write_manifest(read_writer: filesio.ReadWriter, manifest: Manifest) -> None:
+    """Writes ``MANIFEST_PATH`` as JSON.
+
+    :param read_writer: Collection to write the manifest into.
+    :param manifest: Manifest to write.
+    """
+    with read_writer.open_write(MANIFEST_PATH) as f:
+        json.dump(dataclasses.asdict(manifest), f, indent=2)
+        f.write("\n")
+
+
+def file_checksum(reader: filesio.Reader, path: pathlib.PurePath) -> str:
+    """Returns the hex SHA-256 checksum of a file's bytes.
+
+    This matches the output of ``sha256sum``, so packages can also be checked
+    with standard tools.
+    """
+    digest = hashlib.sha256()
+    with reader.open_read_binary(path) as f:
+        while data := f.read(64 * 1024):
+            digest.update(data)
+    return digest.hexdigest()
+
+
+def write_checksums(read_writer: filesio.ReadWriter, paths: Iterable[pathlib.PurePath]) -> None:
+    """Writes ``CHECKSUMS_PATH`` for the given files.
+
+    :param read_writer: Collection containing the files, and to write the
+    checksums into.
+    :param paths: Files to checksum.
+    """
+    lines = [
+        f"{file_checksum(read_writer, path)}  {path.as_posix()}\n" for path in sorted(paths)
+    ]
+    with read_writer.open_write(CHECKSUMS_PATH) as f:
+        f.writelines(lines)
+
+
+def verify_checksums(reader: filesio.Reader) -> Iterator[str]:
+    """Verifies files against ``CHECKSUMS_PATH``, if present.
+
+    Configurations without a checksum manifest (such as those used during
+    development) are not checked.
+
+    :param reader: Configuration to verify.
+    :yield: Descriptions of any files that are missing, modified, or not listed
+    in the manifest.
+    """
+    if not reader.exists(CHECKSUMS_PATH):
+        return
+
+    expected: dict[pathlib.PurePath, str] = {}
+    with reader.open_read(CHECKSUMS_PATH) as f:
+        for line_num, line in enumerate(f, start=1):
+            checksum, sep, path_str = line.rstrip("\n").partition("  ")
+            if not sep or not path_str:
+                yield f"{CHECKSUMS_PATH} line {line_num} is malformed."
+                continue
+            expected[pathlib.PurePath(path_str)] = checksum
+
+    for path, checksum in sorted(expected.items()):
+        if not reader.exists(path):
+            yield f"{path} is listed in {CHECKSUMS_PATH} but is missing."
+        elif file_checksum(reader, path) != checksum:
+            yield f"{path} does not match its checksum in {CHECKSUMS_PATH}."
+
+    for path in sorted(reader.iter_files()):
+        if path != CHECKSUMS_PATH and path not in expected:
+            yield f"{path} is not listed in {CHECKSUMS_PATH}."
+
+
+def _check_licence_names(cfg_reader: filesio.Reader, licence_paths: list[pathlib.Path]) -> None:
+    """Checks that the licence files would not replace other files in the package.
+
+    :raises cfgerror.ConfigurationError: If a licence file has the same name as
+    a configuration file, a file generated by packaging, or another licence.
+    """
+    taken = set(cfg_reader.iter_files())
+    taken.update([CHECKSUMS_PATH, CHANGELOG_PATH, MANIFEST_PATH, config.VERSION_PATH])
+    for licence_path in licence_paths:
+        path = pathlib.PurePath(licence_path.name)
+        if path in taken:
+            raise cfgerror.ConfigurationError(
+                f"Licence file {licence_path} would replace {path} in the package."
+            )
+        taken.add(path)
+
+
+def package_config(
+    *,
+    version: str,
+    config_dir: pathlib.Path,
+    licence_paths: list[pathlib.Path],
+    package_zip: pathlib.Path,
+) -> None:
+    """Packages a configuration directory into a ZIP file.
+
+    The package contains the configuration files, a version file, a
+    ``CHANGELOG.md`` (a stub is generated if the configuration has none), any
+    licence files, a ``MANIFEST.json`` describing what the configuration
+    produces, and a checksum manifest.
+
+    :param version: Semantic version of the package.
+    :param config_dir: Configuration directory to package.
+    :param licence_paths: Licence files to include at the top level of the
+    package.
+    :param package_zip: ZIP file to create.
+    :raises cfgerror.ConfigurationError: If the version is invalid, the
+    configuration does not load cleanly, or a licence file has the same name
+    as another file in the package.
+    """
+    if not is_semantic_version(version):
+        raise cfgerror.ConfigurationError(f"{version!r} is not a semantic version")
+
+    with filesio.DirReader.new_reader(config_dir) as cfg_reader:
+        if errors := list(validate_config(cfg_reader)):
+            raise cfgerror.ConfigurationError("\n".join(errors))
+        _check_licence_names(cfg_reader, licence_paths)
+
+        with filesio.ZipReadWriter.new_read_writer(package_zip) as pkg_writer:
+            paths = set(filesio.copy_files(cfg_reader, pkg_writer))
+
+            config.save_config_version(pkg_writer, version)
+            paths.add(config.VERSION_PATH)
+
+            write_manifest(pkg_writer, build_manifest(cfg_reader, version))
+            paths.add(MANIFEST_PATH)
+
+            if CHANGELOG_PATH not in paths:
+                with pkg_writer.open_write(CHANGELOG_PATH) as f:
+                    f.write(f"# Changelog\n\n## {version}\n\n* Initial release.\n")
+                paths.add(CHANGELOG_PATH)
+
+            for licence_path in licence_paths:
+                path = pathlib.PurePath(licence_path.name)
+                with (
+                    licence_path.open("rt", encoding="utf-8", newline="") as fr,
+                    pkg_writer.open_write(path, newline="") as fw,
+                ):
+                    fw.write(fr.read())
+                paths.add(path)
+
+            paths.discard(CHECKSUMS_PATH)
+            write_checksums(pkg_writer, paths)
@@ -2,24 +2,54 @@
 """CSV utilities."""
 
 import contextlib
+import enum
+import io
 import pathlib
 
-from typing import IO
+from typing import IO, Iterator
 
 from travdata import filesio
 
 
+# Byte order mark, which some spreadsheet programs (notably Excel) require in
+# order to recognise a CSV file as UTF-8.
+_BOM = "\ufeff"
+# Python codec of CsvEncoding.CP1252.
+_CP1252_CODEC = "cp1252"
+
+
+class CsvEncoding(enum.StrEnum):
+    """Text encoding of CSV files written by ``open_by_read_writer``."""
+
+    UTF8 = "utf-8"
+    # UTF-8, starting with a byte order mark.
+    UTF8_BOM = "utf-8-bom"
+    # Windows-1252, for older spreadsheet programs that do not support UTF-8.
+    # Writing text that it cannot represent raises UnicodeEncodeError.
+    CP1252 = "windows-1252"
+
+
+@contextlib.contextmanager
 def open_by_reader(
     reader: filesio.Reader,
     path: pathlib.PurePath,
-) -> contextlib.AbstractContextManager[IO[str]]:
+) -> Iterator[IO[str]]:
     """Opens a file in a manner suitable for reading CSV data from.
 
+    Any byte order mark at the start of the file is skipped. Files that are not
+    valid UTF-8 are read as Windows-1252, as written for ``CsvEncoding.CP1252``.
+
     :param reader: Reader to use.
     :param path: Path to the file to read.
     :return: Opened file.
     """
-    return reader.open_read(path, newline="")
+    with reader.open_read_binary(path) as f:
+        data = f.read()
+    try:
+        text = data.decode("utf-8-sig")
+    except UnicodeDecodeError:
+        text = data.decode(_CP1252_CODEC, errors="replace")
+    yield io.StringIO(text, newline="")
 
 
 def open_read(
@@ -27,23 +57,42 @@ def open_read(
 ) -> contextlib.AbstractContextManager[IO[str]]:
     """Opens a file in a manner suitable for reading CSV data from.
 
+    Any byte order mark at the start of the file is skipped.
+
     :param path: Path to the file to read.
     :return: Opened file.
     """
-    return path.open(mode="rt", encoding="utf-8", newline="")
+    return path.open(mode="rt", encoding="utf-8-sig", newline="")
 
 
+@contextlib.contextmanager
 def open_by_read_writer(
     read_writer: filesio.ReadWriter,
     path: pathlib.PurePath,
-) -> contextlib.AbstractContextManager[IO[str]]:
+    encoding: CsvEncoding = CsvEncoding.UTF8,
+) -> Iterator[IO[str]]:
     """Opens a file in a manner suitable for writing CSV data into.
 
     :param read_writer: ReadWriter to use.
     :param path: Path to the file to write.
+    :param encoding: Encoding of the file.
     :return: Opened file.
+    :raises UnicodeEncodeError: If the text written cannot be represented in
+    ``encoding``. The file is not written in this case.
     """
-    return read_writer.open_write(path, newline="")
+    if encoding == CsvEncoding.CP1252:
+        with read_writer.open_write_binary(path) as fb:
+            f = io.TextIOWrapper(fb, encoding=_CP1252_CODEC, newline="")
+            yield f
+            f.flush()
+            # Leave closing the underlying file to read_writer.
+            f.detach()
+        return
+
+    with read_writer.open_write(path, newline="") as f:
+        if encoding == CsvEncoding.UTF8_BOM:
+            f.write(_BOM)
+        yield f
 
 
 def open_write(
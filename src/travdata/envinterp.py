@@ -0,0 +1,82 @@
+# -*- coding: utf-8 -*-
+"""Interpolates environment variables into strings from configuration files.
+
+``${NAME}`` is replaced by the value of the environment variable ``NAME``, and
+``${NAME:-default}`` falls back to ``default`` if ``NAME`` is unset or empty.
+``$$`` is replaced by a literal ``$``.
+"""
+
+import dataclasses
+import re
+from typing import Mapping
+
+from travdata.config import cfgerror
+
+
+_VAR_RX = re.compile(r"\$(?:\$|\{([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\})")
+
+
+@dataclasses.dataclass(frozen=True)
+class Substitution:
+    """Record of a variable substituted by an ``Interpolator``.
+
+    :field name: Name of the environment variable.
+    :field value: Value that was substituted.
+    :field used_default: True if the variable was unset, and the default from
+    the reference was used.
+    """
+
+    name: str
+    value: str
+    used_default: bool
+
+
+class Interpolator:
+    """Interpolates environment variables into strings, recording what was substituted."""
+
+    _environ: Mapping[str, str]
+    substitutions: list[Substitution]
+
+    def __init__(self, environ: Mapping[str, str]) -> None:
+        """Initialise the Interpolator.
+
+        :param environ: Environment variables to substitute.
+        """
+        self._environ = environ
+        self.substitutions = []
+
+    def __call__(self, value: str) -> str:
+        """Returns ``value`` with variable references replaced.
+
+        :param value: String to interpolate into.
+        :raises cfgerror.ConfigurationError: If a variable is unset, and its
+        reference has no default.
+        :return: Interpolated string.
+        """
+        return _VAR_RX.sub(self._replace, value)
+
+    def _replace(self, match: re.Match[str]) -> str:
+        name, default = match.group(1, 2)
+        if name is None:
+            return "$"
+        if value := self._environ.get(name):
+            self.substitutions.append(Substitution(name, value, used_default=False))
+            return value
+        if default is None:
+            raise cfgerror.ConfigurationError(
+                f"Environment variable {name} is not set, and {match.group(0)} has no default."
+            )
+        self.substitutions.append(Substitution(name, default, used_default=True))
+        return default
+
+    def report(self) -> list[str]:
+        """Returns a human readable line for each substitution made."""
+        return [
+            f"${{{sub.name}}} = {sub.value!r}{' (default)' if sub.used_default else ''}"
+            for sub in self.substitutions
+        ]
+
+
+def has_references(value: str) -> bool:
+    """Returns True if ``value`` contains anything that ``Interpolator`` replaces."""
+    return _VAR_RX.search(value) is not None
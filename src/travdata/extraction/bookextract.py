@@ -4,11 +4,23 @@
 import contextlib
 import csv
 import dataclasses
+import enum
+import functools
+import json
 import pathlib
-from typing import Callable, Iterator, Optional
+import time
+from typing import Any, Callable, Iterator, Optional, TypeAlias
 
-from travdata import config, csvutil, filesio
-from travdata.extraction import index, tableextract
+from travdata import config, csvutil, filesio, travdatarelease
+from travdata.config import cfgpackage
+from travdata.extraction import (
+    index,
+    jsonrows,
+    postprocess,
+    tableextract,
+    tablestats,
+    tabulautil,
+)
 
 
 @dataclasses.dataclass
@@ -19,6 +31,146 @@ class Progress:
     total: int
 
 
+@dataclasses.dataclass(frozen=True)
+class TableStarted:
+    """Extraction of a table has started."""
+
+    output_path: pathlib.PurePath
+
+
+@dataclasses.dataclass(frozen=True)
+class TableFinished:
+    """Extraction of a table has finished.
+
+    :field output_path: Path to the table file within the output.
+    :field success: True if the table was written to the output.
+    :field duration: Time taken to extract and write the table, in seconds.
+    :field stats: Statistics about the table, if it was extracted.
+    :field title: Title of the table, if configured.
+    :field doc: Documentation of the table, if configured.
+    """
+
+    output_path: pathlib.PurePath
+    success: bool
+    duration: float
+    stats: Optional[tablestats.TableStats] = None
+    title: Optional[str] = None
+    doc: Optional[str] = None
+
+
+@dataclasses.dataclass(frozen=True)
+class RunFinished:
+    """Extraction of the book has ended.
+
+    :field stopped: True if extraction ended before all tables were attempted,
+    such as when it was cancelled, or the configuration could not be used.
+    """
+
+    stopped: bool
+
+
+# Events emitted by ``extract_book`` via ``ExtractEvents.observers``.
+Event: TypeAlias = Progress | TableStarted | TableFinished | RunFinished
+
+
+class Observer:
+    """Base class for observers of the events emitted by ``extract_book``.
+
+    Subclasses override the methods for the events that they are interested in.
+    An ``Observer`` is also callable with any ``Event``, so it can be used
+    wherever an event callback is accepted (such as wrapped by
+    ``RateLimited``).
+    """
+
+    def progress(self, event: Progress) -> None:
+        """Called when progress is reported, at the start and after each table."""
+
+    def table_started(self, event: TableStarted) -> None:
+        """Called when extraction of a table starts."""
+
+    def table_finished(self, event: TableFinished) -> None:
+        """Called when extraction of a table finishes, whether or not it succeeded."""
+
+    def run_finished(self, event: RunFinished) -> None:
+        """Called once, when extraction of the book ends."""
+
+    def __call__(self, event: Event) -> None:
+        match event:
+            case Progress():
+                self.progress(event)
+            case TableStarted():
+                self.table_started(event)
+            case TableFinished():
+                self.table_finished(event)
+            case RunFinished():
+                self.run_finished(event)
+
+
+class RateLimited:
+    """Wraps an observer (or other event callback), dropping events that arrive too frequently.
+
+    ``Progress`` events are dropped if they arrive within ``min_interval`` of
+    the last forwarded ``Progress`` event, except for the initial and final
+    ``Progress`` events, which are always forwarded. Other events are always
+    forwarded.
+
+    This is suitable for use by embedders whose event handling is relatively
+    expensive (such as crossing thread boundaries in a GUI).
+    """
+
+    _callback: Callable[[Event], None]
+    _min_interval: float
+    _clock: Callable[[], float]
+    _last_sent: Optional[float]
+
+    def __init__(
+        self,
+        callback: Callable[[Event], None],
+        min_interval: float,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        """Initialise the RateLimited.
+
+        :param callback: Observer to forward events to.
+        :param min_interval: Minimum interval between ``Progress`` events, in
+        seconds.
+        :param clock: Source of the current time, in seconds.
+        """
+        self._callback = callback
+        self._min_interval = min_interval
+        self._clock = clock
+        self._last_sent = None
+
+    def __call__(self, event: Event) -> None:
+        if isinstance(event, Progress):
+            now = self._clock()
+            is_boundary = event.completed in (0, event.total)
+            if (
+                not is_boundary
+                and self._last_sent is not None
+                and now - self._last_sent < self._min_interval
+            ):
+                return
+            self._last_sent = now
+        self._callback(event)
+
+
+class BudgetAction(enum.StrEnum):
+    """Action taken when a table takes longer than its time budget to extract."""
+
+    # Report a warning once the table is extracted, and keep the table.
+    WARN = "warn"
+    # Stop extracting the table once over budget, report an error, and do not
+    # write the table.
+    FAIL = "fail"
+    # As FAIL, and also stop extracting further tables.
+    ABORT = "abort"
+
+
+class TimeBudgetExceeded(Exception):
+    """Raised when a table exceeds its time budget, and the action is not ``WARN``."""
+
+
 @dataclasses.dataclass(frozen=True)
 class ExtractionConfig:
     """Extraction configuration.
@@ -30,6 +182,22 @@ class ExtractionConfig:
     :field with_tags: Only extracts tables that have any of these these tags.
     :field without_tags: Only extracts tables that do not include any of these
     tags (takes precedence over with_tags).
+    :field debug_extraction: If true, write the unconverted tables from Tabula
+    alongside each CSV file, for debugging the configuration.
+    :field allow_exec: If true, run any post-processing commands from the
+    configuration on each output file.
+    :field stats_thresholds: Thresholds at which statistics about each table
+    are reported as warnings, unless set by the configuration's ``systems``.
+    :field stats_overrides: Thresholds that take precedence over those set by
+    the configuration's ``systems``.
+    :field intermediates_dir: If set, write the rows input to each
+    transformation step of each table as JSON files within this directory.
+    :field intermediates_tags: Only write intermediate rows for tables that
+    have any of these tags. All tables are written if empty.
+    :field time_budget: Time budget in seconds for tables that do not set their
+    own ``time_budget``. If None, such tables have no budget.
+    :field budget_action: Action to take when a table exceeds its time budget.
+    :field csv_encoding: Encoding of the CSV files written.
     """
 
     cfg_reader_ctx: contextlib.AbstractContextManager[filesio.Reader]
@@ -39,6 +207,35 @@ class ExtractionConfig:
     overwrite_existing: bool
     with_tags: frozenset[str]
     without_tags: frozenset[str]
+    debug_extraction: bool = False
+    allow_exec: bool = False
+    stats_thresholds: tablestats.Thresholds = tablestats.Thresholds()
+    stats_overrides: config.TableWarnings = dataclasses.field(
+        default_factory=config.TableWarnings
+    )
+    intermediates_dir: Optional[pathlib.Path] = None
+    intermediates_tags: frozenset[str] = frozenset()
+    time_budget: Optional[float] = None
+    budget_action: BudgetAction = BudgetAction.WARN
+    csv_encoding: csvutil.CsvEncoding = csvutil.CsvEncoding.UTF8
+
+    def table_time_budget(self, table: config.Table) -> Optional[float]:
+        """Returns the time budget for extracting the table, if any."""
+        if table.time_budget is not None:
+            return table.time_budget
+        return self.time_budget
+
+    def new_intermediates_writer(self) -> contextlib.AbstractContextManager[filesio.ReadWriter]:
+        """Opens the writer for intermediate rows, which discards them if not requested."""
+        if self.intermediates_dir is None:
+            return filesio.NullReadWriter.new_read_writer()
+        return filesio.DirReadWriter.new_read_writer(self.intermediates_dir)
+
+    def dumps_intermediates(self, table: config.Table) -> bool:
+        """Returns True if the table's intermediate rows should be written."""
+        if self.intermediates_dir is None:
+            return False
+        return not self.intermediates_tags or bool(table.tags & self.intermediates_tags)
 
 
 @dataclasses.dataclass(frozen=True)
@@ -47,15 +244,13 @@ class _OutputTable:
     table: config.Table
 
 
-def _filter_tables(
+def _selected_tables(
     ext_cfg: ExtractionConfig,
     book_group: config.Group,
-    out_writer: filesio.ReadWriter,
 ) -> Iterator[_OutputTable]:
     for table in book_group.all_tables():
         if table.extraction is None:
             continue
-        out_filepath = table.file_stem.with_suffix(".csv")
 
         if ext_cfg.with_tags and not table.tags & ext_cfg.with_tags:
             continue
@@ -63,10 +258,155 @@ def _filter_tables(
         if ext_cfg.without_tags and table.tags & ext_cfg.without_tags:
             continue
 
-        if not ext_cfg.overwrite_existing and out_writer.exists(out_filepath):
+        yield _OutputTable(table.output_path, table)
+
+
+def _filter_tables(
+    ext_cfg: ExtractionConfig,
+    book_group: config.Group,
+    out_writer: filesio.ReadWriter,
+) -> Iterator[_OutputTable]:
+    for output_table in _selected_tables(ext_cfg, book_group):
+        exists = out_writer.exists(output_table.out_filepath)
+        if exists and _keeps_existing(ext_cfg, output_table.table):
             continue
 
-        yield _OutputTable(out_filepath, table)
+        yield output_table
+
+
+def _keeps_existing(ext_cfg: ExtractionConfig, table: config.Table) -> bool:
+    """Returns True if an existing output of the table should not be replaced."""
+    match table.cache:
+        case config.CachePolicy.ALWAYS:
+            return True
+        case config.CachePolicy.NEVER:
+            return False
+        case _:
+            return not ext_cfg.overwrite_existing
+
+
+# Suffix of the files written when ``ExtractionConfig.debug_extraction`` is set.
+TABULA_DEBUG_SUFFIX = ".tabula.json"
+
+
+def _write_tabula_debug(
+    tables: list[tabulautil.TabulaTable],
+    *,
+    cfg_reader: filesio.Reader,
+    out_writer: filesio.ReadWriter,
+    output_table: _OutputTable,
+) -> None:
+    """Writes the template and unconverted Tabula output for the table."""
+    with cfg_reader.open_read(output_table.table.tabula_template_path) as f:
+        template = json.load(f)
+    debug_path = output_table.out_filepath.with_suffix(TABULA_DEBUG_SUFFIX)
+    with out_writer.open_write(debug_path) as f:
+        json.dump({"template": template, "tables": tables}, f, indent=2)
+
+
+# Suffix of the files written for tables that end with an ``AnnotateCellTypes``
+# transform.
+CELL_TYPES_SUFFIX = ".types.csv"
+
+
+def _write_intermediate(
+    intermediates_writer: filesio.ReadWriter,
+    output_table: _OutputTable,
+    step_id: str,
+    rows: list[list[str]],
+    boxes: Optional[tableextract.CellBoxes],
+) -> None:
+    """Writes the rows input to a transformation step of the table.
+
+    Each step is written to ``<table file stem>/<step ID>.json``. The bounding
+    boxes of the cells are included for the steps that have them, as a parallel
+    list of rows of ``{"top", "left", "width", "height"}`` objects (or null for
+    cells without geometry).
+    """
+    step: dict[str, Any] = {
+        "table": output_table.table.file_stem.as_posix(),
+        "step": step_id,
+        "rows": rows,
+    }
+    if boxes is not None:
+        step["boxes"] = [
+            [dataclasses.asdict(box) if box is not None else None for box in row] for row in boxes
+        ]
+    path = output_table.table.file_stem / f"{step_id}.json"
+    with intermediates_writer.open_write(path) as f:
+        json.dump(step, f, indent=2)
+
+
+def _apply_table_warnings(
+    thresholds: tablestats.Thresholds,
+    table_warnings: config.TableWarnings,
+) -> tablestats.Thresholds:
+    """Returns ``thresholds`` with any fields set in ``table_warnings`` replaced."""
+    # The fields of TableWarnings have the same names as those of Thresholds.
+    overrides = {
+        field.name: value
+        for field in dataclasses.fields(table_warnings)
+        if (value := getattr(table_warnings, field.name)) is not None
+    }
+    return dataclasses.replace(thresholds, **overrides)
+
+
+# Name of the book metadata file, within the book's directory in the output.
+BOOK_METADATA_FILENAME = "metadata.json"
+# Name of the file holding the configured attribution notice, within the book's
+# directory in the output. CSV has no standard way to include comments, so the
+# notice is written alongside the tables instead.
+ATTRIBUTION_FILENAME = "ATTRIBUTION.txt"
+# Key of the configured attribution notice within each aggregate JSON document.
+# The other keys are the paths of tables, so a table with this name is left out
+# of the document.
+ATTRIBUTION_JSON_KEY = "_attribution"
+
+
+def _book_metadata_paths(cfg: config.Config, book_cfg: config.Book) -> list[pathlib.PurePath]:
+    """Returns the paths of the files written by ``_write_book_metadata``."""
+    book_dir = pathlib.PurePath(book_cfg.id_)
+    paths = [book_dir / BOOK_METADATA_FILENAME]
+    if cfg.systems.attribution is not None:
+        paths.append(book_dir / ATTRIBUTION_FILENAME)
+    return paths
+
+
+def _write_book_metadata(
+    *,
+    cfg_reader: filesio.Reader,
+    out_writer: filesio.ReadWriter,
+    cfg: config.Config,
+    book_cfg: config.Book,
+) -> None:
+    """Writes metadata describing the book and its configuration into the output.
+
+    If the configuration sets an attribution notice, it is also written to its
+    own file alongside the book's tables.
+    """
+    attribution = cfg.systems.attribution
+    if attribution is not None:
+        with out_writer.open_write(pathlib.PurePath(book_cfg.id_) / ATTRIBUTION_FILENAME) as f:
+            f.write(attribution.rstrip("\n") + "\n")
+
+    metadata = book_cfg.metadata
+    path = pathlib.PurePath(book_cfg.id_) / BOOK_METADATA_FILENAME
+    with out_writer.open_write(path) as f:
+        json.dump(
+            {
+                "id": book_cfg.id_,
+                "title": book_cfg.name,
+                "publisher": metadata.publisher,
+                "edition": metadata.edition,
+                "isbn": metadata.isbn,
+                "template_authors": metadata.template_authors,
+                "config_version": config.load_config_version(cfg_reader),
+                "travdata_version": travdatarelease.EXECUTABLE_VERSION,
+                "attribution": attribution,
+            },
+            f,
+            indent=2,
+        )
 
 
 def _extract_single_table(
@@ -76,33 +416,243 @@ def _extract_single_table(
     table_reader: tableextract.TableReader,
     input_pdf: pathlib.Path,
     output_table: _OutputTable,
-) -> set[int]:
-    """Helper wrapper of `extract_table` for `extract_book`, returning page numbers."""
+    debug_extraction: bool,
+    intermediates_writer: Optional[filesio.ReadWriter],
+    time_budget: Optional[float],
+    budget_action: BudgetAction,
+    csv_encoding: csvutil.CsvEncoding,
+    on_error: Optional[Callable[[str], None]],
+) -> tuple[set[int], tablestats.TableStats]:
+    """Helper wrapper of `extract_table` for `extract_book`, returning pages and statistics.
+
+    :raises TimeBudgetExceeded: If extraction exceeded ``time_budget``, and
+    ``budget_action`` is not ``WARN``. Extraction is stopped once the budget is
+    exceeded (checked between calls to Tabula and as rows pass between
+    transformation steps), and the table is not written in this case.
+    """
+    start = time.monotonic()
+
+    def check_deadline() -> None:
+        assert time_budget is not None
+        elapsed = time.monotonic() - start
+        if elapsed > time_budget:
+            raise TimeBudgetExceeded(
+                f"Table {output_table.table.file_stem} was stopped after {elapsed:.1f}s, "
+                f"exceeding its time budget of {time_budget:.1f}s."
+            )
+
+    tabula_tables: list[tabulautil.TabulaTable] = []
+
+    def on_tabula_tables(tables: list[tabulautil.TabulaTable]) -> None:
+        tabula_tables.extend(tables)
+        if on_error and not tabulautil.tables_have_text(tables):
+            on_error(
+                f"Table {output_table.table.file_stem}: no text found in the configured "
+                "region, which appears to be an image; consider OCR extraction."
+            )
+        if debug_extraction:
+            _write_tabula_debug(
+                tables,
+                cfg_reader=cfg_reader,
+                out_writer=out_writer,
+                output_table=output_table,
+            )
+
+    on_intermediate: Optional[tableextract.IntermediateCallback] = None
+    if intermediates_writer is not None:
+        on_intermediate = functools.partial(_write_intermediate, intermediates_writer, output_table)
+
+    cell_types: Optional[list[list[str]]] = None
+
+    def on_cell_types(types: list[list[str]]) -> None:
+        nonlocal cell_types
+        cell_types = types
+
     pages, rows = tableextract.extract_table(
         cfg_reader=cfg_reader,
         table=output_table.table,
         pdf_path=input_pdf,
         table_reader=table_reader,
+        on_tabula_tables=on_tabula_tables,
+        on_intermediate=on_intermediate,
+        check_deadline=(
+            check_deadline
+            if time_budget is not None and budget_action != BudgetAction.WARN
+            else None
+        ),
+        on_cell_types=on_cell_types,
     )
-    with csvutil.open_by_read_writer(out_writer, output_table.out_filepath) as f:
-        csv.writer(f).writerows(rows)
-    return pages
+    out_rows = list(rows)
+
+    duration = time.monotonic() - start
+    if time_budget is not None and duration > time_budget:
+        message = (
+            f"Table {output_table.table.file_stem} took {duration:.1f}s to extract, "
+            f"exceeding its time budget of {time_budget:.1f}s."
+        )
+        if budget_action != BudgetAction.WARN:
+            raise TimeBudgetExceeded(message)
+        if on_error:
+            on_error(message)
+
+    with csvutil.open_by_read_writer(out_writer, output_table.out_filepath, csv_encoding) as f:
+        csv.writer(f).writerows(out_rows)
+    if cell_types is not None:
+        types_path = output_table.out_filepath.with_suffix(CELL_TYPES_SUFFIX)
+        with csvutil.open_by_read_writer(out_writer, types_path, csv_encoding) as f:
+            csv.writer(f).writerows(cell_types)
+
+    with cfg_reader.open_read(output_table.table.tabula_template_path) as f:
+        areas = tabulautil.read_template_areas(f)
+    return pages, tablestats.compute(out_rows, tabula_tables, areas)
 
 
 @dataclasses.dataclass
 class ExtractEvents:
     """Extraction event callbacks.
 
-    :field on_progress: Called at the start and after each extraction attempt.
     :field on_error: Called on any errors.
     :field do_continue: Called at intervals. If it returns False, then no
     further processing is attempted.
+    :field observers: Called with all events, including ``Progress`` at the
+    start and after each extraction attempt. Register with ``add_observer``.
+    Wrap with ``RateLimited`` to reduce the frequency of ``Progress`` events.
     """
 
-    on_progress: Optional[Callable[[Progress], None]] = None
     on_output: Optional[Callable[[pathlib.PurePath], None]] = None
     on_error: Optional[Callable[[str], None]] = None
     do_continue: Optional[Callable[[], bool]] = None
+    observers: list[Callable[[Event], None]] = dataclasses.field(default_factory=list)
+
+    def add_observer(self, observer: Callable[[Event], None]) -> None:
+        """Registers an ``Observer`` (or other event callback) for all events."""
+        self.observers.append(observer)
+
+    def event(self, event: Event) -> None:
+        """Reports an event to the observers."""
+        for observer in self.observers:
+            observer(event)
+
+
+def _report_renamed_output(
+    out_writer: filesio.ReadWriter,
+    output_path: pathlib.PurePath,
+    events: ExtractEvents,
+) -> None:
+    if not isinstance(out_writer, filesio.DirReadWriter) or not events.on_error:
+        return
+    if renamed_path := out_writer.renamed_paths.get(output_path):
+        events.on_error(
+            f"Output {output_path} was written as {renamed_path}, "
+            "as its name is not portable to all filesystems."
+        )
+
+
+def _write_aggregates(
+    book_group: config.Group,
+    out_writer: filesio.ReadWriter,
+    attribution: Optional[str],
+    events: ExtractEvents,
+) -> None:
+    """Writes the aggregate JSON document of each group that requests one.
+
+    Each document is an object mapping the path of each table (relative to the
+    group) to the rows of the table, or to objects keyed by the table's header
+    if the group sets ``json_objects``. Tables that have not been extracted into
+    the output are omitted. If ``attribution`` is set, it is included under
+    ``ATTRIBUTION_JSON_KEY``.
+    """
+    for group in book_group.all_groups():
+        if not group.aggregate_json:
+            continue
+        tables: dict[str, Any] = {}
+        if attribution is not None:
+            tables[ATTRIBUTION_JSON_KEY] = attribution
+        for table in group.all_tables():
+            if table.extraction is None or not out_writer.exists(table.output_path):
+                continue
+            key = table.file_stem.relative_to(group.rel_dir).as_posix()
+            if key in tables:
+                if events.on_error:
+                    events.on_error(
+                        f"Table {table.file_stem} is left out of {group.aggregate_json_path}, "
+                        f"as its name is reserved for the attribution notice."
+                    )
+                continue
+            with csvutil.open_by_reader(out_writer, table.output_path) as f:
+                rows = list(csv.reader(f))
+            if group.json_objects:
+                tables[key] = jsonrows.rows_to_objects(rows, group.json_coerce_types)
+            else:
+                tables[key] = jsonrows.rows_to_arrays(rows, group.json_coerce_types)
+        with out_writer.open_write(group.aggregate_json_path) as f:
+            json.dump(tables, f, indent=2)
+        if events.on_output:
+            events.on_output(group.aggregate_json_path)
+
+
+@dataclasses.dataclass(frozen=True)
+class PlannedOutputs:
+    """Files that ``extract_book`` would write into an existing output.
+
+    Extraction never deletes files from the output, and ``index.csv`` is
+    updated rather than replaced, so neither is listed.
+
+    :field created: Files that do not yet exist.
+    :field overwritten: Files that exist, and would be replaced.
+    :field kept: Tables that exist, and would not be extracted again, as
+    ``ExtractionConfig.overwrite_existing`` is false or the table's ``cache``
+    policy keeps it.
+    """
+
+    created: list[pathlib.PurePath]
+    overwritten: list[pathlib.PurePath]
+    kept: list[pathlib.PurePath]
+
+
+def plan_outputs(
+    *,
+    ext_cfg: ExtractionConfig,
+    cfg_reader: filesio.Reader,
+    out_reader: filesio.Reader,
+) -> PlannedOutputs:
+    """Determines the files that ``extract_book`` would write, without extracting.
+
+    Output files from post-processing commands are not known in advance, so are
+    not included.
+
+    :param ext_cfg: Extraction to plan. Its reader and writer contexts are not
+    used.
+    :param cfg_reader: Configuration to read.
+    :param out_reader: Existing output to compare against.
+    :raises cfgerror.ConfigurationError: If the configuration is invalid.
+    :raises KeyError: If the book is not in the configuration.
+    :return: Planned outputs, each sorted by path.
+    """
+    cfg = config.load_config(cfg_reader)
+    book_cfg = cfg.books[ext_cfg.book_id]
+    book_group = book_cfg.load_group(cfg_reader)
+
+    planned = PlannedOutputs(created=[], overwritten=[], kept=[])
+    paths = _book_metadata_paths(cfg, book_cfg)
+    for output_table in _selected_tables(ext_cfg, book_group):
+        exists = out_reader.exists(output_table.out_filepath)
+        if not exists or not _keeps_existing(ext_cfg, output_table.table):
+            paths.append(output_table.out_filepath)
+        else:
+            planned.kept.append(output_table.out_filepath)
+    paths.extend(
+        group.aggregate_json_path for group in book_group.all_groups() if group.aggregate_json
+    )
+
+    for path in paths:
+        if out_reader.exists(path):
+            planned.overwritten.append(path)
+        else:
+            planned.created.append(path)
+    for path_list in (planned.created, planned.overwritten, planned.kept):
+        path_list.sort()
+    return planned
 
 
 def extract_book(
@@ -115,16 +665,42 @@ def extract_book(
 
     :param table_reader: Extractor for individual tables from a PDF.
     :param cfg: Configuration for extraction.
-    :param events: Event hooks to feed back progress, etc.
+    :param events: Event hooks to feed back progress, etc. ``RunFinished`` is
+    always the last event, even if an exception is raised.
     :raises RuntimeError: If ``cfg.book_cfg.group`` was not set.
     """
+    completed = False
+    try:
+        completed = _extract_book(table_reader=table_reader, ext_cfg=ext_cfg, events=events)
+    finally:
+        events.event(RunFinished(stopped=not completed))
+
+
+def _extract_book(
+    *,
+    table_reader: tableextract.TableReader,
+    ext_cfg: ExtractionConfig,
+    events: ExtractEvents,
+) -> bool:
+    """Implements ``extract_book``, returning True if all tables were attempted."""
 
     with (
         ext_cfg.cfg_reader_ctx as cfg_reader,
         ext_cfg.out_writer_ctx as out_writer,
+        ext_cfg.new_intermediates_writer() as intermediates_writer,
         index.writer(out_writer) as indexer,
     ):
+        if checksum_errors := list(cfgpackage.verify_checksums(cfg_reader)):
+            if events.on_error:
+                for error in checksum_errors:
+                    events.on_error(f"Configuration failed verification: {error}")
+            return False
+
         cfg = config.load_config(cfg_reader)
+        stats_thresholds = _apply_table_warnings(
+            _apply_table_warnings(ext_cfg.stats_thresholds, cfg.systems.table_warnings),
+            ext_cfg.stats_overrides,
+        )
         try:
             book_cfg = cfg.books[ext_cfg.book_id]
         except KeyError:
@@ -132,29 +708,55 @@ def extract_book(
                 events.on_error(
                     f"Book {ext_cfg.book_id} not found in configuration.",
                 )
-            return
+            return False
 
         book_group = book_cfg.load_group(cfg_reader)
+        _write_book_metadata(
+            cfg_reader=cfg_reader, out_writer=out_writer, cfg=cfg, book_cfg=book_cfg
+        )
+
+        post_process = book_cfg.post_process
+        if post_process and not ext_cfg.allow_exec:
+            post_process = []
+            if events.on_error:
+                events.on_error(
+                    f"Book {ext_cfg.book_id} configures post-processing commands, "
+                    "which will not be run unless explicitly allowed.",
+                )
 
         output_tables = sorted(
             _filter_tables(ext_cfg, book_group, out_writer),
             key=lambda ft: ft.out_filepath,
         )
 
-        if events.on_progress:
-            events.on_progress(Progress(0, len(output_tables)))
+        events.event(Progress(0, len(output_tables)))
 
         for i, output_table in enumerate(output_tables, start=1):
             if events.do_continue and not events.do_continue():
-                return
+                return False
 
+            events.event(TableStarted(output_table.out_filepath))
+            start = time.monotonic()
+            success = False
+            aborted = False
+            stats: Optional[tablestats.TableStats] = None
             try:
-                pages = _extract_single_table(
+                pages, stats = _extract_single_table(
                     cfg_reader=cfg_reader,
                     out_writer=out_writer,
                     table_reader=table_reader,
                     input_pdf=ext_cfg.input_pdf,
                     output_table=output_table,
+                    debug_extraction=ext_cfg.debug_extraction,
+                    intermediates_writer=(
+                        intermediates_writer
+                        if ext_cfg.dumps_intermediates(output_table.table)
+                        else None
+                    ),
+                    time_budget=ext_cfg.table_time_budget(output_table.table),
+                    budget_action=ext_cfg.budget_action,
+                    csv_encoding=ext_cfg.csv_encoding,
+                    on_error=events.on_error,
                 )
             except tableextract.ConfigurationError as exc:
                 if events.on_error:
@@ -162,16 +764,61 @@ def extract_book(
                         f"Configuration error while processing table "
                         f"{output_table.table.file_stem}: {exc}"
                     )
+            except tableextract.TableAssertionError as exc:
+                if events.on_error:
+                    events.on_error(
+                        f"Table {output_table.table.file_stem} failed its assertions: {exc}"
+                    )
+            except UnicodeEncodeError as exc:
+                if events.on_error:
+                    events.on_error(
+                        f"Table {output_table.table.file_stem} cannot be written as "
+                        f"{ext_cfg.csv_encoding}: {exc}"
+                    )
+            except TimeBudgetExceeded as exc:
+                if events.on_error:
+                    events.on_error(str(exc))
+                if ext_cfg.budget_action == BudgetAction.ABORT:
+                    aborted = True
             else:
+                success = True
                 if events.on_output:
                     events.on_output(output_table.out_filepath)
 
+                _report_renamed_output(out_writer, output_table.out_filepath, events)
+                if events.on_error:
+                    for warning in stats_thresholds.warnings(stats):
+                        events.on_error(f"Table {output_table.table.file_stem} {warning}.")
+
                 indexer.write_entry(
                     output_path=output_table.out_filepath,
                     table=output_table.table,
                     book_cfg=book_cfg,
                     pages=pages,
                 )
+
+                for error in postprocess.run_commands(
+                    post_process, out_writer, output_table.out_filepath
+                ):
+                    if events.on_error:
+                        events.on_error(error)
             finally:
-                if events.on_progress:
-                    events.on_progress(Progress(i, len(output_tables)))
+                events.event(
+                    TableFinished(
+                        output_path=output_table.out_filepath,
+                        success=success,
+                        duration=time.monotonic() - start,
+                        stats=stats,
+                        title=output_table.table.title,
+                        doc=output_table.table.doc,
+                    )
+                )
+                events.event(Progress(i, len(output_tables)))
+
+            if aborted:
+                if events.on_error:
+                    events.on_error("Stopping extraction, as a table exceeded its time budget.")
+                return False
+
+        _write_aggregates(book_group, out_writer, cfg.systems.attribution, events)
+        return True
@@ -15,10 +15,12 @@ from travdata import config, csvutil, filesio
 _INDEX_TABLE_PATH = "table_path"
 _INDEX_PAGES = "pages"
 _INDEX_TAGS = "tags"
+_INDEX_TITLE = "title"
 _INDEX_COLUMNS = [
     _INDEX_TABLE_PATH,
     _INDEX_PAGES,
     _INDEX_TAGS,
+    _INDEX_TITLE,
 ]
 
 _INDEX_PATH = pathlib.PurePath("index.csv")
@@ -154,6 +156,7 @@ class _WriterImpl:
                 _INDEX_TABLE_PATH: path,
                 _INDEX_PAGES: ";".join(str(book_cfg.page_offset + page) for page in sorted(pages)),
                 _INDEX_TAGS: ";".join(sorted(table.tags)),
+                _INDEX_TITLE: table.title or "",
             }
         )
         self.seen_paths.add(path)
@@ -0,0 +1,71 @@
+# -*- coding: utf-8 -*-
+"""Converts extracted table rows into JSON values."""
+
+import re
+from typing import Any
+
+
+_RX_INT = re.compile(r"[-+]?\d+")
+_RX_FLOAT = re.compile(r"[-+]?(?:\d+\.\d*|\.\d+)")
+
+
+def coerce_cell(cell: str) -> Any:
+    """Converts cell text to the JSON value that it appears to represent.
+
+    :param cell: Text of the cell.
+    :return: None for an empty cell, an int or float for a cell containing only
+    a number, otherwise the original text.
+    """
+    if not cell:
+        return None
+    if _RX_INT.fullmatch(cell):
+        return int(cell)
+    if _RX_FLOAT.fullmatch(cell):
+        return float(cell)
+    return cell
+
+
+def header_keys(header: list[str]) -> list[str]:
+    """Returns unique object keys for the cells of a header row.
+
+    Empty header cells are named by their column number (e.g. "column_3"), and
+    repeated names are suffixed with their occurrence (e.g. "name_2").
+    """
+    keys: list[str] = []
+    seen: set[str] = set()
+    for i, cell in enumerate(header, start=1):
+        base = cell or f"column_{i}"
+        key = base
+        occurrence = 1
+        while key in seen:
+            occurrence += 1
+            key = f"{base}_{occurrence}"
+        seen.add(key)
+        keys.append(key)
+    return keys
+
+
+def rows_to_objects(rows: list[list[str]], coerce_types: bool) -> list[dict[str, Any]]:
+    """Converts rows into objects, keyed by the first (header) row.
+
+    :param rows: Rows of the table, starting with the header row.
+    :param coerce_types: If true, cell values are converted by ``coerce_cell``.
+    :return: One object per row after the header. Cells beyond the width of
+    the header are keyed as if the header had empty cells there, and missing
+    cells are omitted.
+    """
+    if not rows:
+        return []
+    width = max(len(row) for row in rows)
+    keys = header_keys(rows[0] + [""] * (width - len(rows[0])))
+    return [
+        {key: coerce_cell(cell) if coerce_types else cell for key, cell in zip(keys, row)}
+        for row in rows[1:]
+    ]
+
+
+def rows_to_arrays(rows: list[list[str]], coerce_types: bool) -> list[list[Any]]:
+    """Returns the rows, with cell values converted by ``coerce_cell`` if ``coerce_types``."""
+    if not coerce_types:
+        return rows
+    return [[coerce_cell(cell) for cell in row] for row in rows]
@@ -0,0 +1,96 @@
+# -*- coding: utf-8 -*-
+"""Collects summary statistics from an extraction run."""
+
+import dataclasses
+import pathlib
+import time
+from typing import IO, Callable, Optional
+
+from travdata.config import cfgextract
+from travdata.extraction import bookextract, tableextract, tabulautil
+
+
+@dataclasses.dataclass
+class Metrics(bookextract.Observer):
+    """Counters collected over an extraction run, by observing its events.
+
+    :field started: True once extraction of tables has started, i.e. the
+    configuration was loaded.
+    :field tables_succeeded: Number of tables extracted and written.
+    :field tables_failed: Number of tables that failed extraction.
+    :field table_seconds: Total time spent extracting tables.
+    :field tabula_calls: Number of calls to read tables from the PDF.
+    :field tabula_seconds: Total time spent reading tables from the PDF.
+    """
+
+    started: bool = False
+    tables_succeeded: int = 0
+    tables_failed: int = 0
+    table_seconds: float = 0.0
+    tabula_calls: int = 0
+    tabula_seconds: float = 0.0
+
+    def progress(self, event: bookextract.Progress) -> None:
+        """Implements Observer.progress."""
+        self.started = True
+
+    def table_finished(self, event: bookextract.TableFinished) -> None:
+        """Implements Observer.table_finished."""
+        if event.success:
+            self.tables_succeeded += 1
+        else:
+            self.tables_failed += 1
+        self.table_seconds += event.duration
+
+    def summary(self) -> str:
+        """Returns a human readable summary of the metrics."""
+        return (
+            f"Extracted {self.tables_succeeded} tables ({self.tables_failed} failed) "
+            f"in {self.table_seconds:.1f}s, "
+            f"of which {self.tabula_seconds:.1f}s was spent in {self.tabula_calls} Tabula calls."
+        )
+
+
+class TimedTableReader:
+    """Wraps a ``TableReader``, recording its use in ``Metrics``."""
+
+    _delegate: tableextract.TableReader
+    _metrics: Metrics
+    _clock: Callable[[], float]
+
+    def __init__(
+        self,
+        delegate: tableextract.TableReader,
+        metrics: Metrics,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        """Initialise the TimedTableReader.
+
+        :param delegate: Reader to time calls to.
+        :param metrics: Metrics to record into.
+        :param clock: Source of the current time, in seconds.
+        """
+        self._delegate = delegate
+        self._metrics = metrics
+        self._clock = clock
+
+    def read_pdf_with_template(
+        self,
+        *,
+        pdf_path: pathlib.Path,
+        template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
+    ) -> tuple[set[int], list[tabulautil.TabulaTable]]:
+        """Implements TableReader.read_pdf_with_template."""
+        start = self._clock()
+        try:
+            return self._delegate.read_pdf_with_template(
+                pdf_path=pdf_path,
+                template_file=template_file,
+                options=options,
+                check_deadline=check_deadline,
+            )
+        finally:
+            self._metrics.tabula_calls += 1
+            self._metrics.tabula_seconds += self._clock() - start
@@ -0,0 +1,50 @@
+# -*- coding: utf-8 -*-
+"""Runs post-processing commands on extracted output files."""
+
+import pathlib
+import subprocess
+from typing import Iterator
+
+from travdata import config, filesio
+
+
+def run_commands(
+    commands: list[config.PostProcessCommand],
+    out_writer: filesio.ReadWriter,
+    output_path: pathlib.PurePath,
+) -> Iterator[str]:
+    """Runs the commands on a single output file.
+
+    :param commands: Commands to run, in order.
+    :param out_writer: Writer that the output file was written to. This must be
+    a directory-based writer, as commands need a filesystem path to operate on.
+    :param output_path: Path to the file within ``out_writer``.
+    :yield: Errors from commands that could not be run or that failed.
+    """
+    if not commands:
+        return
+    if not isinstance(out_writer, filesio.DirReader):
+        yield "Post-processing commands can only be run when the output is a directory."
+        return
+
+    output_dir = out_writer.dir_path
+    full_path = str(output_dir / filesio.sanitise_path(output_path))
+    for cmd in commands:
+        args = [arg.replace("{path}", full_path) for arg in cmd.command]
+        try:
+            result = subprocess.run(
+                args,
+                cwd=output_dir,
+                capture_output=True,
+                text=True,
+                timeout=cmd.timeout,
+                check=False,
+            )
+        except (OSError, subprocess.TimeoutExpired) as exc:
+            yield f"Post-processing command {args!r} on {output_path} could not complete: {exc}"
+            continue
+        if result.returncode != 0:
+            yield (
+                f"Post-processing command {args!r} on {output_path} failed with exit code "
+                f"{result.returncode}:\n{result.stdout}{result.stderr}"
+            )
@@ -5,7 +5,7 @@ import functools
 import itertools
 import pathlib
 import re
-from typing import IO, Iterable, Iterator, Protocol, TypeAlias
+from typing import IO, Callable, Iterable, Iterator, Optional, Protocol, TypeAlias
 
 from travdata import config, filesio
 from travdata.config import cfgextract
@@ -13,6 +13,7 @@ from travdata.extraction import parseutil, tabulautil
 
 
 _RX_ANYTHING = re.compile(".*")
+_RX_NUMBER = re.compile(r"[-+]?\d[\d,]*(?:\.\d+)?")
 
 
 class TableReader(Protocol):
@@ -26,12 +27,17 @@ class TableReader(Protocol):
         *,
         pdf_path: pathlib.Path,
         template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
     ) -> tuple[set[int], list[tabulautil.TabulaTable]]:
         """Reads tables from a PDF file, using the named template file.
 
         :param pdf_path: Path to the PDF file.
         :param template_file: File-like reader for the Tabula template JSON
         file.
+        :param options: Tuning of how Tabula reads the template's areas.
+        :param check_deadline: If set, called between reads of the template's
+        areas, and may raise an exception to stop reading.
         :return: Set of page numbers and list of extracted tables.
         """
         raise NotImplementedError
@@ -41,11 +47,28 @@ class ConfigurationError(Exception):
     """Exception indication error in the given configuration."""
 
 
+class TableAssertionError(Exception):
+    """Exception indicating that a table failed the checks of an ``AssertTable``."""
+
+
+# Bounding boxes of the cells of rows, parallel to the text of the rows.
+CellBoxes: TypeAlias = list[list[Optional[tabulautil.CellBox]]]
+# Called with a step ID, the rows input to the step, and the bounding boxes of
+# the cells of the rows, if known.
+IntermediateCallback: TypeAlias = Callable[[str, list[list[str]], Optional[CellBoxes]], None]
+# Called with the type of each cell, parallel to the rows output.
+CellTypesCallback: TypeAlias = Callable[[list[list[str]]], None]
+
+
 def extract_table(
     cfg_reader: filesio.Reader,
     table: config.Table,
     pdf_path: pathlib.Path,
     table_reader: TableReader,
+    on_tabula_tables: Optional[Callable[[list[tabulautil.TabulaTable]], None]] = None,
+    on_intermediate: Optional[IntermediateCallback] = None,
+    check_deadline: Optional[Callable[[], None]] = None,
+    on_cell_types: Optional[CellTypesCallback] = None,
 ) -> tuple[set[int], Iterator[list[str]]]:
     """Extracts a table from the PDF.
 
@@ -54,8 +77,27 @@ def extract_table(
     must not be None.
     :param pdf_path: Path to the PDF to extract from.
     :param tabula_reader: Used to read the table from the PDF.
+    :param on_tabula_tables: If set, called with the tables as returned by
+    Tabula, prior to any transformation.
+    :param on_intermediate: If set, called with a step ID and the rows as they
+    are input to each transformation step, and as output by the last. Step IDs
+    are of the form "00-tabula", "01-<transform type>", "02-<transform type>",
+    etc. The "00-tabula" step also has the bounding boxes of the cells from
+    Tabula. Later steps have None, as transformed cells no longer correspond to
+    areas of the page.
+    :param check_deadline: If set, called while reading the table from the PDF
+    and for each row output by each transformation step. Any exception it
+    raises stops extraction of the table, and is propagated from this function
+    or when reading the rows.
+    :param on_cell_types: If set, and the table ends with an
+    ``AnnotateCellTypes`` transform, called with the types of the cells once
+    the rows have been read.
     :returns: Set of page numbers and iterator over rows from the table.
     :raises ValueError: ``table.extraction`` is None.
+    :raises ConfigurationError: If the Tabula template is invalid, or an
+    ``AnnotateCellTypes`` transform is not the last transform.
+    :raises TableAssertionError: When reading the rows, if the table fails the
+    checks of an ``AssertTable`` transform.
     """
     if table.extraction is None:
         raise ValueError(
@@ -63,16 +105,42 @@ def extract_table(
         )
 
     with cfg_reader.open_read(table.tabula_template_path) as tmpl_file:
-        pages, tables = table_reader.read_pdf_with_template(
-            pdf_path=pdf_path,
-            template_file=tmpl_file,
-        )
-        tabula_rows: Iterator[tabulautil.TabulaRow] = tabulautil.table_rows_concat(tables)
-        rows = tabulautil.table_rows_text(tabula_rows)
-
-        for transform_cfg in table.extraction.transforms:
-            rows = _transform(transform_cfg, rows)
+        try:
+            pages, tables = table_reader.read_pdf_with_template(
+                pdf_path=pdf_path,
+                template_file=tmpl_file,
+                options=table.tabula_options,
+                check_deadline=check_deadline,
+            )
+        except tabulautil.TemplateError as exc:
+            raise ConfigurationError(f"{table.tabula_template_path}: {exc}") from exc
+        if on_tabula_tables:
+            on_tabula_tables(tables)
+        tabula_rows = list(tabulautil.table_rows_concat(tables))
+        rows: Iterable[_Row] = tabulautil.table_rows_text(tabula_rows)
+        if on_intermediate:
+            boxes = list(tabulautil.table_rows_boxes(tabula_rows))
+            rows = _capture_intermediate(on_intermediate, "00-tabula", rows, boxes)
+
+        transforms = table.extraction.transforms
+        for i, transform_cfg in enumerate(transforms, start=1):
+            if check_deadline:
+                rows = _check_deadline_per_row(check_deadline, rows)
+            if isinstance(transform_cfg, cfgextract.AnnotateCellTypes):
+                if i != len(transforms):
+                    raise ConfigurationError(
+                        f"{transform_cfg.yaml_tag} must be the last transform, "
+                        f"but is number {i} of {len(transforms)}"
+                    )
+                rows = _annotate_cell_types(transform_cfg, rows, on_cell_types)
+            else:
+                rows = _transform(transform_cfg, rows)
+            if on_intermediate:
+                step_id = f"{i:02d}-{type(transform_cfg).__name__}"
+                rows = _capture_intermediate(on_intermediate, step_id, rows)
 
+        if check_deadline:
+            rows = _check_deadline_per_row(check_deadline, rows)
         return pages, _clean_rows(rows)
 
 
@@ -80,11 +148,44 @@ _Row: TypeAlias = list[str]
 _RowGroup: TypeAlias = list[_Row]
 
 
+def _capture_intermediate(
+    on_intermediate: IntermediateCallback,
+    step_id: str,
+    rows: Iterable[_Row],
+    boxes: Optional[CellBoxes] = None,
+) -> list[_Row]:
+    captured = [list(row) for row in rows]
+    on_intermediate(step_id, captured, boxes)
+    # Copied to isolate the captured rows from any changes by later steps.
+    return [list(row) for row in captured]
+
+
+def _check_deadline_per_row(
+    check_deadline: Callable[[], None],
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    for row in rows:
+        check_deadline()
+        yield row
+
+
 def _transform(cfg: cfgextract.TableTransform, rows: Iterable[_Row]) -> Iterator[_Row]:
     # pylint: disable=too-many-return-statements
     match cfg:
+        case cfgextract.AssertTable():
+            return _assert_table(cfg, rows)
+        case cfgextract.DropRepeatedHeader():
+            return _drop_repeated_header(cfg, rows)
         case cfgextract.ExpandColumnOnRegex():
             return _expand_column_on_regex(cfg, rows)
+        case cfgextract.ExtractKeyValues():
+            return _extract_key_values(cfg, rows)
+        case cfgextract.FillDown():
+            return _fill_down(cfg, rows)
+        case cfgextract.FillRight():
+            return _fill_right(cfg, rows)
+        case cfgextract.FilterRows():
+            return _filter_rows(cfg, rows)
         case cfgextract.JoinColumns():
             return _join_columns(cfg, rows)
         case cfgextract.PrependRow():
@@ -93,6 +194,8 @@ def _transform(cfg: cfgextract.TableTransform, rows: Iterable[_Row]) -> Iterator
             return _fold_rows(cfg, rows)
         case cfgextract.SplitColumn():
             return _split_column(cfg, rows)
+        case cfgextract.SortRows():
+            return _sort_rows(cfg, rows)
         case cfgextract.Transpose():
             return _transpose(rows)
         case cfgextract.WrapRowEveryN():
@@ -104,6 +207,136 @@ def _transform(cfg: cfgextract.TableTransform, rows: Iterable[_Row]) -> Iterator
     # pylint: enable=too-many-return-statements
 
 
+# Used by AnnotateCellTypes when it does not specify any types.
+_DEFAULT_CELL_TYPES = [
+    cfgextract.CellType(name="integer", pattern=r"[-+]?\d+"),
+    cfgextract.CellType(name="number", pattern=r"[-+]?(?:\d+\.\d*|\.\d+)"),
+    cfgextract.CellType(name="range", pattern=r"\d+\s*[-\u2013]\s*\d+|\d+\+"),
+    cfgextract.CellType(name="dice", pattern=r"(?:\d+D\d*|D\d+)(?:\s*[-+x\u00d7]\s*\d+)?"),
+    cfgextract.CellType(
+        name="credits",
+        pattern=r"(?:[kKM]?Cr)[\d,]+(?:\.\d+)?|[\d,]+(?:\.\d+)?\s*[kKM]?Cr",
+    ),
+    cfgextract.CellType(name="tonnage", pattern=r"[\d,]+(?:\.\d+)?\s*(?:tons?|dtons?)"),
+]
+_TEXT_CELL_TYPE = "text"
+
+
+def _annotate_cell_types(
+    cfg: cfgextract.AnnotateCellTypes,
+    rows: Iterable[_Row],
+    on_cell_types: Optional[CellTypesCallback],
+) -> Iterator[_Row]:
+    cell_types = cfg.types or _DEFAULT_CELL_TYPES
+    try:
+        patterns = [(cell_type.name, re.compile(cell_type.pattern)) for cell_type in cell_types]
+    except re.error as exc:
+        raise ConfigurationError(f"{cfg.yaml_tag} has an invalid type pattern: {exc}") from exc
+
+    def classify(cell: str) -> str:
+        # Cleaned as the output will be.
+        if not (cell := parseutil.clean_text(cell)):
+            return ""
+        for name, rx in patterns:
+            if rx.fullmatch(cell):
+                return name
+        return _TEXT_CELL_TYPE
+
+    rows = list(rows)
+    if on_cell_types:
+        on_cell_types(
+            [
+                [""] * len(row) if i < cfg.header_rows else [classify(cell) for cell in row]
+                for i, row in enumerate(rows)
+            ]
+        )
+    yield from rows
+
+
+def _drop_repeated_header(
+    cfg: cfgextract.DropRepeatedHeader,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    if cfg.header_rows < 1:
+        raise ConfigurationError(
+            f"{cfg.yaml_tag}.header_rows must be at least 1, but is {cfg.header_rows}"
+        )
+    merge_column = cfg.merge_split_rows_column
+    if merge_column is not None and merge_column < 0:
+        raise ConfigurationError(
+            f"{cfg.yaml_tag}.merge_split_rows_column must not be negative, but is {merge_column}"
+        )
+
+    rows_and_breaks = iter(_drop_header_repeats(cfg.header_rows, rows))
+    if merge_column is None:
+        yield from (row for row in rows_and_breaks if row is not None)
+    else:
+        # The header is never merged into.
+        yield from itertools.islice(rows_and_breaks, cfg.header_rows)
+        yield from _merge_split_rows(merge_column, rows_and_breaks)
+
+
+def _drop_header_repeats(header_rows: int, rows: Iterable[_Row]) -> Iterator[Optional[_Row]]:
+    """Drops repeats of the leading header rows, yielding None in their place."""
+
+    def key(row: _Row) -> list[str]:
+        return [parseutil.clean_text(text) for text in row]
+
+    rows = iter(rows)
+    header = list(itertools.islice(rows, header_rows))
+    yield from header
+    header_keys = [key(row) for row in header]
+
+    # Rows that might be a repeat of the header, pending confirmation.
+    pending: list[_Row] = []
+    for row in rows:
+        to_check = [row]
+        while to_check:
+            candidate = to_check.pop(0)
+            if key(candidate) == header_keys[len(pending)]:
+                pending.append(candidate)
+                if len(pending) == len(header_keys):
+                    pending = []
+                    yield None
+            elif pending:
+                # Not a repeat from the first pending row, but one could start
+                # at any of the rows after it.
+                yield pending[0]
+                to_check = pending[1:] + [candidate] + to_check
+                pending = []
+            else:
+                yield candidate
+    yield from pending
+
+
+def _merge_split_rows(column: int, rows_and_breaks: Iterable[Optional[_Row]]) -> Iterator[_Row]:
+    """Merges rows continued after a dropped header into the row before it.
+
+    :param column: Column that is empty in a continuation row.
+    :param rows_and_breaks: Rows, with None where a repeated header was dropped.
+    """
+    # Last row, held back in case the row after a dropped header continues it.
+    previous: Optional[_Row] = None
+    after_break = False
+    for row in rows_and_breaks:
+        if row is None:
+            after_break = True
+            continue
+        is_continuation = column >= len(row) or not row[column].strip()
+        if after_break and previous is not None and is_continuation:
+            width = max(len(previous), len(row))
+            previous = previous + [""] * (width - len(previous))
+            for i, text in enumerate(row):
+                previous[i] = " ".join(cell for cell in (previous[i], text) if cell)
+        else:
+            if previous is not None:
+                yield previous
+            previous = row
+        after_break = False
+    if previous is not None:
+        yield previous
+
+
 def _expand_column_on_regex(
     cfg: cfgextract.ExpandColumnOnRegex,
     rows: Iterable[_Row],
@@ -133,6 +366,160 @@ def _expand_column_on_regex(
         yield new_row
 
 
+def _extract_key_values(
+    cfg: cfgextract.ExtractKeyValues,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    if not cfg.fields:
+        raise ConfigurationError("ExtractKeyValues requires at least one field")
+    try:
+        field_rxs = [re.compile(field.pattern, re.MULTILINE) for field in cfg.fields]
+        start_rx = None if cfg.record_start is None else re.compile(cfg.record_start)
+    except re.error as exc:
+        raise ConfigurationError(f"{cfg.yaml_tag} has an invalid pattern: {exc}") from exc
+
+    records: list[list[str]] = [] if start_rx else [[]]
+    for row in rows:
+        line = " ".join(cell for cell in row if cell)
+        if start_rx and start_rx.match(line):
+            records.append([])
+        if records:
+            records[-1].append(line)
+
+    yield [field.key for field in cfg.fields]
+    for record in records:
+        text = "\n".join(record)
+        out_row: _Row = []
+        for rx in field_rxs:
+            value = ""
+            if rx_match := rx.search(text):
+                # An optional group that did not participate gives None.
+                value = rx_match.group(1 if rx.groups else 0) or ""
+            out_row.append(value.strip())
+        yield out_row
+
+
+# Maximum number of failed checks described by a TableAssertionError.
+_MAX_ASSERTION_FAILURES = 5
+
+
+def _assert_table(cfg: cfgextract.AssertTable, rows: Iterable[_Row]) -> Iterator[_Row]:
+    if cfg.header_rows < 0:
+        raise ConfigurationError(
+            f"{cfg.yaml_tag}.header_rows must not be negative, but is {cfg.header_rows}"
+        )
+    for column in [*cfg.non_empty_columns, *(cp.column for cp in cfg.cell_patterns)]:
+        if column < 0:
+            raise ConfigurationError(
+                f"{cfg.yaml_tag} columns must not be negative, but includes {column}"
+            )
+    try:
+        patterns = [(cp.column, re.compile(cp.pattern)) for cp in cfg.cell_patterns]
+    except re.error as exc:
+        raise ConfigurationError(f"{cfg.yaml_tag} has an invalid pattern: {exc}") from exc
+
+    all_rows = list(rows)
+    header, body = all_rows[: cfg.header_rows], all_rows[cfg.header_rows :]
+    failures: list[str] = []
+
+    if len(body) < cfg.min_rows:
+        failures.append(f"has {len(body)} rows after the header, fewer than {cfg.min_rows}")
+
+    header_names = {cell.strip() for row in header for cell in row}
+    for name in cfg.required_columns:
+        if name not in header_names:
+            failures.append(f"has no {name!r} column in the header")
+
+    # Row numbers are 1-based, and count the header rows.
+    for row_num, row in enumerate(body, start=len(header) + 1):
+        for column in cfg.non_empty_columns:
+            if column >= len(row) or not row[column].strip():
+                failures.append(f"row {row_num} column {column} is empty")
+        for column, rx in patterns:
+            if column < len(row) and row[column] and not rx.fullmatch(row[column]):
+                failures.append(
+                    f"row {row_num} column {column} value {row[column]!r} "
+                    f"does not match {rx.pattern!r}"
+                )
+
+    if failures:
+        message = "; ".join(failures[:_MAX_ASSERTION_FAILURES])
+        if len(failures) > _MAX_ASSERTION_FAILURES:
+            message += f"; and {len(failures) - _MAX_ASSERTION_FAILURES} more"
+        raise TableAssertionError(message)
+
+    yield from all_rows
+
+
+def _check_fill_columns(yaml_tag: str, columns: list[int]) -> None:
+    for column in columns:
+        if column < 0:
+            raise ConfigurationError(
+                f"{yaml_tag}.columns must not be negative, but includes {column}"
+            )
+
+
+def _pad_row(row: _Row, width: int) -> _Row:
+    if len(row) >= width:
+        return list(row)
+    return list(row) + [""] * (width - len(row))
+
+
+def _fill_down(
+    cfg: cfgextract.FillDown,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    _check_fill_columns(cfg.yaml_tag, cfg.columns)
+    width = max(cfg.columns, default=-1) + 1
+    rows = iter(rows)
+    yield from itertools.islice(rows, cfg.skip_rows)
+    last: dict[int, str] = {}
+    for row in rows:
+        out_row = _pad_row(row, width)
+        for column in cfg.columns:
+            if out_row[column]:
+                last[column] = out_row[column]
+            else:
+                out_row[column] = last.get(column, "")
+        yield out_row
+
+
+def _fill_right(
+    cfg: cfgextract.FillRight,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    _check_fill_columns(cfg.yaml_tag, cfg.columns)
+    columns = sorted(set(cfg.columns) - {0})
+    width = max(columns, default=-1) + 1
+    rows = iter(rows)
+    yield from itertools.islice(rows, cfg.skip_rows)
+    for row in rows:
+        out_row = _pad_row(row, width)
+        for column in columns:
+            if not out_row[column]:
+                out_row[column] = out_row[column - 1]
+        yield out_row
+
+
+def _filter_rows(
+    cfg: cfgextract.FilterRows,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    try:
+        rx = re.compile(cfg.pattern)
+    except re.error as exc:
+        raise ConfigurationError(f"{cfg.yaml_tag} has an invalid pattern: {exc}") from exc
+    rows = iter(rows)
+    yield from itertools.islice(rows, cfg.skip_rows)
+    for row in rows:
+        try:
+            cell = row[cfg.column]
+        except IndexError:
+            cell = ""
+        if (rx.fullmatch(cell) is not None) != cfg.invert:
+            yield row
+
+
 def _join_columns(
     cfg: cfgextract.JoinColumns,
     rows: Iterable[_Row],
@@ -257,6 +644,33 @@ def _split_column(
         yield new_row
 
 
+def _sort_rows(
+    cfg: cfgextract.SortRows,
+    rows: Iterable[_Row],
+) -> Iterator[_Row]:
+    rows = iter(rows)
+    yield from itertools.islice(rows, cfg.skip_rows)
+    to_sort = list(rows)
+    # Sorting by each key in reverse order of precedence relies on the sort
+    # being stable, and allows each key to have its own direction.
+    for key in reversed(cfg.keys):
+        to_sort.sort(key=functools.partial(_sort_key, key), reverse=key.descending)
+    yield from to_sort
+
+
+def _sort_key(key: cfgextract.SortKey, row: _Row) -> tuple[int, float | str]:
+    try:
+        cell = row[key.column]
+    except IndexError:
+        cell = ""
+    if not key.numeric:
+        return (1, cell)
+    if rx_match := _RX_NUMBER.search(cell):
+        return (1, float(rx_match.group(0).replace(",", "")))
+    # Cells without a number sort last, in either direction.
+    return (0 if key.descending else 2, 0.0)
+
+
 def _transpose(
     rows: Iterable[_Row],
 ) -> Iterator[_Row]:
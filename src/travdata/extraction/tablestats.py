@@ -0,0 +1,134 @@
+# -*- coding: utf-8 -*-
+"""Computes statistics about extracted tables, to help spot bad extractions."""
+
+import dataclasses
+from typing import Iterable, Iterator, Optional
+
+from travdata.extraction import tabulautil
+
+
+# Distance in PDF points within which text is considered to touch the edge of
+# its template area.
+_EDGE_TOLERANCE = 1.0
+
+
+@dataclasses.dataclass(frozen=True)
+class TableStats:
+    """Statistics about a single extracted table.
+
+    :field rows: Number of rows in the output.
+    :field columns: Number of columns in the widest row of the output.
+    :field empty_cells: Number of output cells that are empty, including cells
+    missing from rows that are shorter than the widest row.
+    :field touches_edge: True if any text read by Tabula is at the edge of its
+    template area, which suggests that the area is too small.
+    """
+
+    rows: int
+    columns: int
+    empty_cells: int
+    touches_edge: bool
+
+    @property
+    def empty_fraction(self) -> float:
+        """Fraction of cells that are empty, or zero for a table with no cells."""
+        total = self.rows * self.columns
+        return self.empty_cells / total if total else 0.0
+
+    def describe(self) -> str:
+        """Returns a human readable summary of the statistics."""
+        return (
+            f"{self.rows} rows, {self.columns} columns, "
+            f"{self.empty_fraction:.0%} empty cells"
+            f"{', touches template edge' if self.touches_edge else ''}"
+        )
+
+
+@dataclasses.dataclass(frozen=True)
+class Thresholds:
+    """Thresholds at which ``TableStats`` produce warnings.
+
+    :field min_rows: Warn if a table has fewer rows than this.
+    :field max_empty_fraction: Warn if the fraction of empty cells exceeds
+    this. None disables the warning.
+    :field warn_touches_edge: Warn if the table touches its template edge.
+    """
+
+    min_rows: int = 0
+    max_empty_fraction: Optional[float] = 0.5
+    warn_touches_edge: bool = True
+
+    def warnings(self, stats: TableStats) -> Iterator[str]:
+        """Checks the statistics against the thresholds.
+
+        :param stats: Statistics of a table.
+        :yield: Description of each threshold that was crossed.
+        """
+        if stats.rows < self.min_rows:
+            yield f"has {stats.rows} rows, fewer than {self.min_rows}"
+        if self.max_empty_fraction is not None and stats.empty_fraction > self.max_empty_fraction:
+            yield (
+                f"has {stats.empty_fraction:.0%} empty cells, "
+                f"more than {self.max_empty_fraction:.0%}"
+            )
+        if self.warn_touches_edge and stats.touches_edge:
+            yield "has text at the edge of its template area, which may be too small"
+
+
+def compute(
+    rows: list[list[str]],
+    tables: Iterable[tabulautil.TabulaTable],
+    areas: Iterable[tabulautil.TemplateArea],
+) -> TableStats:
+    """Computes statistics for an extracted table.
+
+    :param rows: Output rows of the table.
+    :param tables: Tables as read by Tabula, prior to transformation.
+    :param areas: Template areas that the tables were read from.
+    :return: Statistics.
+    """
+    columns = max((len(row) for row in rows), default=0)
+    empty_cells = sum(columns - len(row) + sum(1 for cell in row if not cell) for row in rows)
+    return TableStats(
+        rows=len(rows),
+        columns=columns,
+        empty_cells=empty_cells,
+        touches_edge=_touches_edge(tables, list(areas)),
+    )
+
+
+def _touches_edge(
+    tables: Iterable[tabulautil.TabulaTable],
+    areas: list[tabulautil.TemplateArea],
+) -> bool:
+    # Tabula does not report which area a table came from, so a cell is
+    # checked against any area that contains it.
+    for row in tabulautil.table_rows_concat(tables):
+        for cell in row:
+            if not cell["text"].strip():
+                continue
+            box = tabulautil.cell_box(cell)
+            if box is None:
+                continue
+            for area in areas:
+                if _box_at_area_edge(box, area):
+                    return True
+    return False
+
+
+def _box_at_area_edge(box: tabulautil.CellBox, area: tabulautil.TemplateArea) -> bool:
+    tol = _EDGE_TOLERANCE
+    inside = (
+        area.top - tol <= box.top
+        and box.bottom <= area.bottom + tol
+        and area.left - tol <= box.left
+        and box.right <= area.right + tol
+    )
+    if not inside:
+        return False
+    return (
+        box.top - area.top < tol
+        or area.bottom - box.bottom < tol
+        or box.left - area.left < tol
+        or area.right - box.right < tol
+    )
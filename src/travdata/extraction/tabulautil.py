@@ -1,20 +1,42 @@
 # -*- coding: utf-8 -*-
 """Utility wrapper for tabular-py."""
 
+import dataclasses
+import enum
+import itertools
 import json
 import pathlib
+import re
 import tempfile
-from typing import IO, Iterable, Iterator, TypeAlias, TypedDict, cast
+from typing import (
+    IO,
+    Any,
+    Callable,
+    Iterable,
+    Iterator,
+    NotRequired,
+    Optional,
+    TypeAlias,
+    TypedDict,
+    cast,
+)
 
 import jpype  # type: ignore[import-untyped]
 import tabula
+from travdata.config import cfgerror, cfgextract
 
 
 class TabulaCell(TypedDict):
     """Type of table cells emitted by tabula-py."""
 
-    # Ignoring irrelevant fields.
     text: str
+    # Cell geometry in PDF points, relative to the top-left of the page. Absent
+    # in tables not produced by Tabula (such as in tests), and zero for cells
+    # that Tabula synthesised to pad out a row.
+    top: NotRequired[float]
+    left: NotRequired[float]
+    width: NotRequired[float]
+    height: NotRequired[float]
 
 
 # Type of table rows emitted by tabula-py.
@@ -24,8 +46,50 @@ TabulaRow: TypeAlias = list[TabulaCell]
 class TabulaTable(TypedDict):
     """Type of tables emitted by tabula-py."""
 
-    # Ignoring irrelevant fields.
     data: list[TabulaRow]
+    # Table geometry and method, as reported by Tabula.
+    extraction_method: NotRequired[str]
+    top: NotRequired[float]
+    left: NotRequired[float]
+    width: NotRequired[float]
+    height: NotRequired[float]
+    right: NotRequired[float]
+    bottom: NotRequired[float]
+
+
+@dataclasses.dataclass(frozen=True)
+class CellBox:
+    """Bounding box of a cell within its page, in PDF points."""
+
+    top: float
+    left: float
+    width: float
+    height: float
+
+    @property
+    def bottom(self) -> float:
+        """Bottom edge of the cell."""
+        return self.top + self.height
+
+    @property
+    def right(self) -> float:
+        """Right edge of the cell."""
+        return self.left + self.width
+
+
+@dataclasses.dataclass(frozen=True)
+class TemplateArea:
+    """Area of a page that a Tabula template entry extracts from, in PDF points."""
+
+    page: int
+    top: float
+    left: float
+    bottom: float
+    right: float
+
+
+class TemplateError(cfgerror.ConfigurationError):
+    """Error in the content of a Tabula template."""
 
 
 class _TemplateEntry(TypedDict):
@@ -39,6 +103,129 @@ class _TemplateEntry(TypedDict):
     height: float
 
 
+_RX_PAGE_RANGE = re.compile(r"(\d+)(?:\s*-\s*(\d+))?")
+
+
+def parse_pages(spec: Any) -> list[int]:
+    """Parses the ``page`` of a Tabula template entry.
+
+    Tabula writes a single page number, but a template can be edited to use
+    the same area on multiple pages, with a range such as ``"34-36"``, or a
+    list such as ``[12, 17]`` (which may itself contain ranges).
+
+    :param spec: Value of the ``page`` field.
+    :raises TemplateError: If ``spec`` is not a valid page specification.
+    :return: Page numbers, in the order given.
+    """
+    if isinstance(spec, list):
+        return [page for item in spec for page in parse_pages(item)]
+    if isinstance(spec, int) and not isinstance(spec, bool):
+        pages = [spec]
+    elif isinstance(spec, str) and (match := _RX_PAGE_RANGE.fullmatch(spec.strip())):
+        first = int(match.group(1))
+        last = int(match.group(2) or first)
+        if last < first:
+            raise TemplateError(f"page range {spec!r} ends before it starts")
+        pages = list(range(first, last + 1))
+    else:
+        raise TemplateError(f"invalid page {spec!r}, expected a number, range, or list")
+    if any(page < 1 for page in pages):
+        raise TemplateError(f"invalid page {spec!r}, pages are numbered from 1")
+    return pages
+
+
+def _load_template(template_file: IO[str]) -> list[_TemplateEntry]:
+    """Loads a Tabula template, with one entry per page of any multi-page entries."""
+    template = cast(list[dict[str, Any]], json.load(template_file))
+    entries: list[_TemplateEntry] = []
+    for entry in template:
+        for page in parse_pages(entry["page"]):
+            entries.append(cast(_TemplateEntry, entry | {"page": page}))
+    return entries
+
+
+def read_template_areas(template_file: IO[str]) -> list[TemplateArea]:
+    """Reads the areas that a Tabula template refers to.
+
+    :param template_file: File-like reader for the Tabula template JSON file.
+    :return: Areas, in template order. Entries without coordinates are
+    omitted.
+    """
+    template = _load_template(template_file)
+    areas: list[TemplateArea] = []
+    for entry in template:
+        try:
+            areas.append(
+                TemplateArea(
+                    page=entry["page"],
+                    top=entry["y1"],
+                    left=entry["x1"],
+                    bottom=entry["y2"],
+                    right=entry["x2"],
+                )
+            )
+        except KeyError:
+            continue
+    return areas
+
+
+class BatchStrategy(enum.StrEnum):
+    """How Tabula template entries are batched into calls to Tabula."""
+
+    # One call per template entry.
+    NONE = "NONE"
+    # One call per run of consecutive template entries that are on the same page
+    # and use the same extraction method.
+    PAGE_METHOD = "PAGE_METHOD"
+
+
+def _batch_entries(
+    template: list[_TemplateEntry],
+    strategy: BatchStrategy,
+) -> Iterator[list[_TemplateEntry]]:
+    match strategy:
+        case BatchStrategy.NONE:
+            for entry in template:
+                yield [entry]
+        case BatchStrategy.PAGE_METHOD:
+            # Only consecutive entries are grouped, so that the order of the
+            # tables returned is the same as for unbatched calls.
+            for _, batch in itertools.groupby(
+                template,
+                key=lambda entry: (entry["page"], entry["extraction_method"]),
+            ):
+                yield list(batch)
+
+
+def read_template_pages(template_file: IO[str]) -> set[int]:
+    """Reads the page numbers that a Tabula template refers to.
+
+    :param template_file: File-like reader for the Tabula template JSON file.
+    :return: Page numbers.
+    """
+    return {entry["page"] for entry in _load_template(template_file)}
+
+
+def _options_kwargs(
+    options: Optional[cfgextract.TabulaOptions],
+    method: str,
+) -> dict[str, Any]:
+    """Returns the arguments to ``tabula.read_pdf`` for ``options``.
+
+    :param options: Options to convert.
+    :param method: Extraction method of the areas being read.
+    :return: Keyword arguments.
+    """
+    kwargs: dict[str, Any] = {}
+    if options is None:
+        return kwargs
+    if options.columns and method == "stream":
+        kwargs["columns"] = options.columns
+    if options.use_line_returns:
+        kwargs["options"] = "--use-line-returns"
+    return kwargs
+
+
 class TabulaClient:
     """Client wrapper around Tabula.
 
@@ -50,14 +237,23 @@ class TabulaClient:
     """
 
     _force_subprocess: bool
+    _batch_strategy: BatchStrategy
 
-    def __init__(self, force_subprocess: bool) -> None:
+    def __init__(
+        self,
+        force_subprocess: bool,
+        batch_strategy: BatchStrategy = BatchStrategy.NONE,
+    ) -> None:
         """Initialise the ``TabulaClient``.
 
         :param force_subprocess: Should Tabula be run as a child process, versus
         using the faster jpype.
+        :param batch_strategy: How to batch template entries into calls to
+        Tabula. Fewer calls reduces overhead, particularly when using a
+        subprocess.
         """
         self._force_subprocess = force_subprocess
+        self._batch_strategy = batch_strategy
         self._needs_shutdown = False
 
     def __enter__(self) -> "TabulaClient":
@@ -93,36 +289,49 @@ class TabulaClient:
         *,
         pdf_path: pathlib.Path,
         template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
     ) -> tuple[set[int], list[TabulaTable]]:
         """Reads table(s) from a PDF, based on the Tabula template.
 
         :param pdf_path: Path to PDF to read from.
         :param template_file: File-like reader for the Tabula template JSON
         file.
+        :param options: Tuning of how Tabula reads the template's areas.
+        :param check_deadline: If set, called before each call to Tabula, and
+        may raise an exception to stop reading.
+        :raises TemplateError: If the template is invalid.
         :return: Page numbers and tables read from the PDF.
         """
         self._needs_shutdown = not self._force_subprocess
 
         result: list[TabulaTable] = []
-        template = cast(list[_TemplateEntry], json.load(template_file))
+        template = _load_template(template_file)
 
         pages: set[int] = set()
 
-        for entry in template:
-            method = entry["extraction_method"]
-            pages.add(int(entry["page"]))
+        for batch in _batch_entries(template, self._batch_strategy):
+            # All entries in a batch share the same page and method.
+            page = batch[0]["page"]
+            method = batch[0]["extraction_method"]
+            if check_deadline:
+                check_deadline()
+            pages.add(page)
             result.extend(
                 cast(
                     list[TabulaTable],
                     self._read_pdf(
                         input_path=pdf_path,
-                        pages=[entry["page"]],
+                        pages=[page],
                         multiple_tables=True,
-                        area=[entry["y1"], entry["x1"], entry["y2"], entry["x2"]],
+                        area=[
+                            [entry["y1"], entry["x1"], entry["y2"], entry["x2"]] for entry in batch
+                        ],
                         force_subprocess=self._force_subprocess,
                         stream=method == "stream",
                         guess=method == "guess",
                         lattice=method == "lattice",
+                        **_options_kwargs(options, method),
                     ),
                 )
             )
@@ -152,6 +361,40 @@ def _table_row_text(row: TabulaRow) -> list[str]:
     return [cell["text"] for cell in row]
 
 
+def cell_box(cell: TabulaCell) -> Optional[CellBox]:
+    """Returns the bounding box of the cell, if known.
+
+    :param cell: Tabula cell to read the geometry from.
+    :return: The bounding box, or None if the cell has no geometry (or zero
+    size, as for cells that Tabula added as padding).
+    """
+    try:
+        box = CellBox(
+            top=cell["top"],
+            left=cell["left"],
+            width=cell["width"],
+            height=cell["height"],
+        )
+    except KeyError:
+        return None
+    if box.width == 0 and box.height == 0:
+        return None
+    return box
+
+
+def table_rows_boxes(rows: Iterable[TabulaRow]) -> Iterator[list[Optional[CellBox]]]:
+    """Converts Tabula row dictionaries into lists of cell bounding boxes.
+
+    This parallels ``table_rows_text``, such that the same indices address the
+    text and geometry of the same cell.
+
+    :param rows: Tabula rows to read from.
+    :yield: Lists of optional bounding boxes for each cell.
+    """
+    for row in rows:
+        yield [cell_box(cell) for cell in row]
+
+
 def table_rows_text(rows: Iterable[TabulaRow]) -> Iterator[list[str]]:
     """Converts Tabula row dictionaries into simple lists of cells.
 
@@ -160,3 +403,15 @@ def table_rows_text(rows: Iterable[TabulaRow]) -> Iterator[list[str]]:
     """
     for row in rows:
         yield _table_row_text(row)
+
+
+def tables_have_text(tables: Iterable[TabulaTable]) -> bool:
+    """Returns True if any cell in the tables contains non-whitespace text.
+
+    Tabula finds no text in regions of the PDF that are rasterised images, so
+    a False result suggests that the region needs OCR rather than Tabula.
+
+    :param tables: Tables to check.
+    :return: Whether any text was found.
+    """
+    return any(cell["text"].strip() for row in table_rows_concat(tables) for cell in row)
@@ -8,14 +8,35 @@ import enum
 import io
 import os
 import pathlib
+import re
+import secrets
 import shutil
 import tempfile
-from typing import IO, Iterator, Optional, Protocol, Self
+import threading
+from typing import IO, AnyStr, Iterator, Optional, Protocol, Self
+import unicodedata
 import zipfile
+import zlib
 
 
 _ENCODING = "utf-8"
 _NEWLINE = "\n"
+# Error handler with which MemReadWriter holds bytes that are not valid UTF-8.
+_MEM_BINARY_ERRORS = "surrogateescape"
+# Matches the names of the temporary files that DirReadWriter writes before
+# replacing the file, which end with 4 random bytes as hex.
+_TMP_FILE_NAME_RX = re.compile(r"\..+\.[0-9a-f]{8}\.tmp")
+
+# Names that Windows reserves for devices, regardless of file extension.
+_WINDOWS_RESERVED_NAMES = frozenset(
+    ["CON", "PRN", "AUX", "NUL"]
+    + [f"COM{i}" for i in range(1, 10)]
+    + [f"LPT{i}" for i in range(1, 10)]
+)
+_WINDOWS_INVALID_CHARS_RX = re.compile(r'[<>:"|?*\x00-\x1f]')
+# Paths at least this long need the "\\?\" prefix on Windows.
+_WINDOWS_MAX_PATH = 260
+_WINDOWS_LONG_PATH_PREFIX = "\\\\?\\"
 
 
 class Error(Exception):
@@ -26,6 +47,38 @@ class NotFoundError(Error):
     """Attempted to read a file that does not exist."""
 
 
+class UnsafePathError(Error):
+    """Attempted to access a path that is not safely within the collection."""
+
+
+class PermissionDeniedError(Error):
+    """The operating system denied access to a file or directory."""
+
+
+class CorruptArchiveError(Error):
+    """A ZIP file could not be read, because it is damaged or not a ZIP file."""
+
+
+class UnsupportedOperationError(Error):
+    """Attempted an operation that the reader or writer type does not support."""
+
+
+class SymlinkPolicy(enum.StrEnum):
+    """How directory-based readers and writers treat symbolic links.
+
+    Regardless of policy, paths are never allowed to resolve to a location
+    outside of the directory.
+    """
+
+    # Follow symlinks.
+    FOLLOW = "FOLLOW"
+    # Refuse to access any path that passes through a symlink.
+    REFUSE = "REFUSE"
+    # Follow symlinks for reading, but replace a symlink with a regular file
+    # when writing to it.
+    REPLACE = "REPLACE"
+
+
 class IOType(enum.StrEnum):
     """
     Enumeration of readers/writer types that can be constructed with a
@@ -37,6 +90,8 @@ class IOType(enum.StrEnum):
     # Concrete values:
     DIR = "DIR"
     ZIP = "ZIP"
+    # Discards everything written, and contains no files.
+    NULL = "NULL"
 
     @classmethod
     def from_int_id(cls, id_: int) -> "IOType":
@@ -48,6 +103,8 @@ class IOType(enum.StrEnum):
                 return IOType.DIR
             case 3:
                 return IOType.ZIP
+            case 4:
+                return IOType.NULL
             case _:
                 raise ValueError(id_)
 
@@ -60,6 +117,8 @@ class IOType(enum.StrEnum):
                 return 2
             case IOType.ZIP:
                 return 3
+            case IOType.NULL:
+                return 4
             case _:
                 raise ValueError(self)
 
@@ -79,27 +138,34 @@ class IOType(enum.StrEnum):
                 return DirReader.new_reader(path)
             case IOType.ZIP:
                 return ZipReader.new_reader(path)
+            case IOType.NULL:
+                return NullReadWriter.new_reader()
             case _:
-                raise Error(f"cannot open output type {self} with a path")
+                raise UnsupportedOperationError(f"cannot open output type {self} with a path")
 
     def new_read_writer(
         self,
         path: pathlib.Path,
+        symlink_policy: SymlinkPolicy = SymlinkPolicy.FOLLOW,
     ) -> contextlib.AbstractContextManager["ReadWriter"]:
         """Creates a context manager for a ``ReadWriter``.
 
         :param path: Path for the writer to create.
+        :param symlink_policy: Treatment of symlinks, if the ``ReadWriter`` is
+        directory-based.
         :raises Error: If ``self`` is an unknown or unresolved value like
         ``AUTO``.
         :return: Context manager for a ``ReadWriter``.
         """
         match self:
             case IOType.DIR:
-                return DirReadWriter.new_read_writer(path)
+                return DirReadWriter.new_read_writer(path, symlink_policy)
             case IOType.ZIP:
                 return ZipReadWriter.new_read_writer(path)
+            case IOType.NULL:
+                return NullReadWriter.new_read_writer()
             case _:
-                raise Error(f"cannot open output type {self} with a path")
+                raise UnsupportedOperationError(f"cannot open output type {self} with a path")
 
     def resolve_auto(self, path: pathlib.Path) -> "IOType":
         """Returns a concrete IOType for the given filesystem ``path``.
@@ -132,9 +198,60 @@ def new_reader(
 def new_read_writer(
     path: pathlib.Path,
     type_: IOType = IOType.AUTO,
+    symlink_policy: SymlinkPolicy = SymlinkPolicy.FOLLOW,
 ) -> contextlib.AbstractContextManager["ReadWriter"]:
-    """Opens a ``ReadWriter`` for the given path."""
-    return type_.resolve_auto(path).new_read_writer(path)
+    """Opens a ``ReadWriter`` for the given path.
+
+    :param symlink_policy: Treatment of symlinks, if the ``ReadWriter`` is
+    directory-based.
+    """
+    return type_.resolve_auto(path).new_read_writer(path, symlink_policy)
+
+
+def normalise_path(path: pathlib.PurePath) -> pathlib.PurePath:
+    """Returns ``path`` in the form used to look up and name files.
+
+    Backslashes are treated as path separators, as in paths created on
+    Windows, and each name is converted to Unicode normal form C, as macOS
+    can create names in normal form D. Already normalised paths are unchanged.
+
+    :param path: Path to normalise.
+    :return: Normalised path.
+    """
+    text = unicodedata.normalize("NFC", str(path))
+    return pathlib.PurePath(text.replace("\\", "/"))
+
+
+def sanitise_path(path: pathlib.PurePath) -> pathlib.PurePath:
+    """Returns a version of the relative ``path`` that is portable to Windows.
+
+    Characters that Windows does not allow in filenames are replaced, trailing
+    dots and spaces are removed, and reserved device names (such as ``CON``)
+    are suffixed with an underscore. Already sanitised paths are unchanged.
+
+    :param path: Relative path to sanitise.
+    :return: Sanitised path.
+    """
+    return pathlib.PurePath(*(_sanitise_name(part) for part in path.parts))
+
+
+def _sanitise_name(name: str) -> str:
+    name = _WINDOWS_INVALID_CHARS_RX.sub("_", name)
+    name = name.rstrip(". ") or "_"
+    stem, dot, suffix = name.partition(".")
+    if stem.upper() in _WINDOWS_RESERVED_NAMES:
+        name = f"{stem}_{dot}{suffix}"
+    return name
+
+
+def _windows_long_path(path: pathlib.Path) -> pathlib.Path:
+    """Returns ``path`` with the long path prefix if required on Windows."""
+    if os.name != "nt":
+        return path
+    abs_path = os.path.abspath(path)
+    if len(abs_path) < _WINDOWS_MAX_PATH or abs_path.startswith(_WINDOWS_LONG_PATH_PREFIX):
+        return path
+    return pathlib.Path(_WINDOWS_LONG_PATH_PREFIX + abs_path)
 
 
 class Reader(Protocol):
@@ -151,6 +268,22 @@ class Reader(Protocol):
         :param newline: Newline sequence to use.
         :return: Context-managed readable file-like object.
         :raises NotFoundError: If the ``path`` does not exist.
+        :raises PermissionDeniedError: If the file cannot be read.
+        :raises CorruptArchiveError: If the file is in a damaged ZIP file.
+        """
+        ...
+
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Open a file for reading its raw bytes.
+
+        :param path: Path of the file to read.
+        :return: Context-managed readable binary file-like object.
+        :raises NotFoundError: If the ``path`` does not exist.
+        :raises PermissionDeniedError: If the file cannot be read.
+        :raises CorruptArchiveError: If the file is in a damaged ZIP file.
         """
         ...
 
@@ -186,58 +319,196 @@ class ReadWriter(Reader, Protocol):
         :param path: Path of the file to write.
         :param newline: Newline sequence to use.
         :return: Context-managed writable file-like object.
+        :raises PermissionDeniedError: If the file cannot be written.
+        """
+        ...
+
+    def open_write_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Open a file for writing raw bytes.
+
+        :param path: Path of the file to write.
+        :return: Context-managed writable binary file-like object.
+        :raises PermissionDeniedError: If the file cannot be written.
         """
         ...
 
 
 class DirReader:
-    """Reads files in a local filesystem directory."""
+    """Reads files in a local filesystem directory.
+
+    Paths are passed through ``normalise_path`` before accessing the
+    filesystem. Existing names that differ only in Unicode normal form are
+    matched when reading. Files that exist under their own names are read as
+    they are, even if the names are not portable, while other paths are read
+    from the name that ``sanitise_path`` gives them, as written by a
+    ``DirReadWriter``.
+    """
 
     _dir_path: pathlib.Path
+    _symlink_policy: SymlinkPolicy
     _created_dirs: set[pathlib.Path]
+    _renamed_paths: dict[pathlib.PurePath, pathlib.PurePath]
 
-    def __init__(self, dir_path: pathlib.Path) -> None:
+    def __init__(
+        self,
+        dir_path: pathlib.Path,
+        symlink_policy: SymlinkPolicy = SymlinkPolicy.FOLLOW,
+    ) -> None:
         """Initialise the DirReadWriter to read/write in the given directory."""
         self._dir_path = dir_path
+        self._symlink_policy = symlink_policy
         self._created_dirs = set()
+        self._renamed_paths = {}
 
     @classmethod
     @contextlib.contextmanager
-    def new_reader(cls, dir_path: pathlib.Path) -> Iterator[Self]:
+    def new_reader(
+        cls,
+        dir_path: pathlib.Path,
+        symlink_policy: SymlinkPolicy = SymlinkPolicy.FOLLOW,
+    ) -> Iterator[Self]:
         """Create a DirReader to read in the given directory."""
-        yield cls(dir_path)
+        yield cls(dir_path, symlink_policy)
 
     @property
     def dir_path(self) -> pathlib.Path:
         """Returns the directory path."""
         return self._dir_path
 
+    def _full_path(self, path: pathlib.PurePath, writing: bool = False) -> pathlib.Path:
+        """Returns the filesystem path for ``path``, checking that it is safe.
+
+        :param path: Path of the file within the directory.
+        :param writing: True if the path is about to be written to.
+        :raises UnsafePathError: If ``path`` would access anything outside of
+        the directory, or if it violates the symlink policy.
+        :return: Filesystem path.
+        """
+        path = normalise_path(path)
+        if path.anchor or ".." in path.parts:
+            raise UnsafePathError(f"{path} is not a relative path within the directory")
+
+        if writing:
+            safe_path = sanitise_path(path)
+            if safe_path != path:
+                self._renamed_paths[path] = safe_path
+            path = safe_path
+        else:
+            path = self._read_path(path)
+
+        full_path = self._dir_path / path
+        root = self._dir_path.resolve()
+
+        match self._symlink_policy:
+            case SymlinkPolicy.REFUSE:
+                check_path = self._dir_path
+                for part in path.parts:
+                    check_path = check_path / part
+                    if check_path.is_symlink():
+                        raise UnsafePathError(f"{path} passes through symlink {check_path}")
+            case SymlinkPolicy.REPLACE if writing and full_path.is_symlink():
+                # The symlink itself is replaced once the file has been written
+                # (see ``_replace_on_success``), so its target is never accessed.
+                if not full_path.parent.resolve().is_relative_to(root):
+                    raise UnsafePathError(f"{path} resolves outside of the directory")
+                return _windows_long_path(full_path)
+
+        if not writing:
+            full_path = _match_existing_path(self._dir_path, path)
+
+        if not full_path.resolve().is_relative_to(root):
+            raise UnsafePathError(f"{path} resolves outside of the directory")
+
+        return _windows_long_path(full_path)
+
+    def _read_path(self, path: pathlib.PurePath) -> pathlib.PurePath:
+        """Returns the path to read ``path`` from.
+
+        Paths renamed when written by this instance are read from their new
+        names. Otherwise the path is read as-is if it exists, or else from its
+        sanitised name if that exists, as written by an earlier instance.
+
+        :param path: Normalised path of the file within the directory.
+        :return: Path to read from.
+        """
+        if (renamed_path := self._renamed_paths.get(path)) is not None:
+            return renamed_path
+        safe_path = sanitise_path(path)
+        if (
+            safe_path != path
+            and not _match_existing_path(self._dir_path, path).exists()
+            and (self._dir_path / safe_path).exists()
+        ):
+            return safe_path
+        return path
+
     def open_read(
         self,
         path: pathlib.PurePath,
         newline: str = _NEWLINE,
     ) -> contextlib.AbstractContextManager[IO[str]]:
         """Implements Reader.open_read."""
-        full_path = self._dir_path / path
+        full_path = self._full_path(path)
         try:
             f = full_path.open("rt", encoding=_ENCODING, newline=newline)
         except FileNotFoundError as exc:
             raise NotFoundError(path) from exc
+        except PermissionError as exc:
+            raise PermissionDeniedError(path) from exc
+        return f
+
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Implements Reader.open_read_binary."""
+        full_path = self._full_path(path)
+        try:
+            f = full_path.open("rb")
+        except FileNotFoundError as exc:
+            raise NotFoundError(path) from exc
+        except PermissionError as exc:
+            raise PermissionDeniedError(path) from exc
         return f
 
     def iter_files(self) -> Iterator[pathlib.PurePath]:
         """Implements Reader.iter_files."""
         for root, _, files in os.walk(self._dir_path):
             for filename in files:
+                if _TMP_FILE_NAME_RX.fullmatch(filename):
+                    # Still being written by a DirReadWriter.
+                    continue
                 full_path = pathlib.PurePath(root) / filename
-                yield full_path.relative_to(self._dir_path)
+                yield normalise_path(full_path.relative_to(self._dir_path))
 
     def exists(
         self,
         path: pathlib.PurePath,
     ) -> bool:
         """Implements Reader.exists."""
-        return (self._dir_path / path).exists()
+        return self._full_path(path).exists()
+
+
+def _match_existing_path(dir_path: pathlib.Path, path: pathlib.PurePath) -> pathlib.Path:
+    """Returns the existing path in ``dir_path`` that matches ``path``.
+
+    Names that do not exist are matched against the names in their directory
+    that have the same Unicode normal form C. If there is no match, then the
+    path is returned as-is.
+    """
+    full_path = dir_path
+    for part in path.parts:
+        candidate = full_path / part
+        if not candidate.exists() and full_path.is_dir():
+            for entry in full_path.iterdir():
+                if unicodedata.normalize("NFC", entry.name) == part:
+                    candidate = entry
+                    break
+        full_path = candidate
+    return full_path
 
 
 class DirReadWriter(DirReader):
@@ -245,36 +516,127 @@ class DirReadWriter(DirReader):
 
     @classmethod
     @contextlib.contextmanager
-    def new_read_writer(cls, dir_path: pathlib.Path) -> Iterator[Self]:
+    def new_read_writer(
+        cls,
+        dir_path: pathlib.Path,
+        symlink_policy: SymlinkPolicy = SymlinkPolicy.FOLLOW,
+    ) -> Iterator[Self]:
         """Create a DirReadWriter to read and write in the given directory."""
-        yield cls(dir_path)
+        yield cls(dir_path, symlink_policy)
+
+    @property
+    def renamed_paths(self) -> dict[pathlib.PurePath, pathlib.PurePath]:
+        """Returns the paths written so far that were renamed by ``sanitise_path``."""
+        return dict(self._renamed_paths)
 
     def open_write(
         self,
         path: pathlib.PurePath,
         newline: str = _NEWLINE,
     ) -> contextlib.AbstractContextManager[IO[str]]:
-        """Implements ReadWriter.open_write."""
-        full_path = self._dir_path / path
+        """Implements ReadWriter.open_write.
+
+        The content is written to a temporary file alongside the file, which
+        replaces it only once writing has completed. If writing is interrupted
+        by an exception (including ``KeyboardInterrupt``), then any existing
+        file is left as it was.
+        """
+        full_path, tmp_path = self._prepare_write(path)
+        try:
+            f = tmp_path.open("xt", encoding=_ENCODING, newline=newline)
+        except PermissionError as exc:
+            raise PermissionDeniedError(path) from exc
+        return _replace_on_success(f, tmp_path, full_path, path)
+
+    def open_write_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Implements ReadWriter.open_write_binary.
+
+        The file is replaced only once writing has completed, as for
+        ``open_write``.
+        """
+        full_path, tmp_path = self._prepare_write(path)
+        try:
+            f = tmp_path.open("xb")
+        except PermissionError as exc:
+            raise PermissionDeniedError(path) from exc
+        return _replace_on_success(f, tmp_path, full_path, path)
+
+    def _prepare_write(self, path: pathlib.PurePath) -> tuple[pathlib.Path, pathlib.Path]:
+        """Returns the full path of the file to write, and of a temporary file to write first.
+
+        :raises PermissionDeniedError: If the file's directory cannot be created.
+        """
+        full_path = self._full_path(path, writing=True)
+        if full_path.is_symlink() and self._symlink_policy != SymlinkPolicy.REPLACE:
+            # Replace the symlink's target rather than the symlink itself, as
+            # writing through the symlink would.
+            full_path = full_path.resolve()
         parent_dir = full_path.parent
-        if parent_dir not in self._created_dirs:
-            parent_dir.mkdir(parents=True, exist_ok=True)
-            self._created_dirs.add(parent_dir)
-        return full_path.open("wt", encoding=_ENCODING, newline=newline)
+        tmp_path = parent_dir / f".{full_path.name}.{secrets.token_hex(4)}.tmp"
+        try:
+            if parent_dir not in self._created_dirs:
+                parent_dir.mkdir(parents=True, exist_ok=True)
+                self._created_dirs.add(parent_dir)
+        except PermissionError as exc:
+            raise PermissionDeniedError(path) from exc
+        return full_path, tmp_path
+
+
+@contextlib.contextmanager
+def _replace_on_success(
+    f: IO[AnyStr],
+    tmp_path: pathlib.Path,
+    full_path: pathlib.Path,
+    path: pathlib.PurePath,
+) -> Iterator[IO[AnyStr]]:
+    """Yields ``f``, then replaces ``full_path`` with ``tmp_path`` if no exception was raised.
+
+    :param f: Open file of ``tmp_path``.
+    :param tmp_path: Temporary file being written.
+    :param full_path: File to replace. If this is a symlink, the symlink itself
+    is replaced, so it is left as it was if writing fails.
+    :param path: Path of the file, as given to ``open_write``.
+    :raises PermissionDeniedError: If the file cannot be replaced.
+    """
+    try:
+        with f:
+            yield f
+        if full_path.exists() and not full_path.is_symlink():
+            shutil.copymode(full_path, tmp_path)
+        os.replace(tmp_path, full_path)
+    except PermissionError as exc:
+        tmp_path.unlink(missing_ok=True)
+        raise PermissionDeniedError(path) from exc
+    except BaseException:
+        tmp_path.unlink(missing_ok=True)
+        raise
 
 
 class MemReader:
-    """Reads in-memory files."""
+    """Reads in-memory files.
+
+    Paths are passed through ``normalise_path``, as for the other backends. A
+    single instance is safe to use from multiple threads.
+    """
 
     _files: dict[pathlib.PurePath, str]
+    _lock: threading.Lock
 
-    def __init__(self, files: dict[pathlib.PurePath, str]) -> None:
-        """Initialise the MemReader to read from the given files."""
-        self._files = files
+    def __init__(self, files: Optional[dict[pathlib.PurePath, str]] = None) -> None:
+        """Initialise the MemReader to read from the given files.
+
+        :param files: Files to read, which are used (and updated, by a
+        ``MemReadWriter``) in place. Defaults to no files.
+        """
+        self._files = {} if files is None else files
+        self._lock = threading.Lock()
 
     @classmethod
     @contextlib.contextmanager
-    def new_reader(cls, files: dict[pathlib.PurePath, str]) -> Iterator[Self]:
+    def new_reader(cls, files: Optional[dict[pathlib.PurePath, str]] = None) -> Iterator[Self]:
         """Create a MemReader to read from the given files."""
         yield cls(files)
 
@@ -286,29 +648,79 @@ class MemReader:
     ) -> Iterator[IO[str]]:
         """Implements Reader.open_read."""
         try:
-            contents = self._files[path]
+            with self._lock:
+                contents = self._files[normalise_path(path)]
         except KeyError as exc:
             raise NotFoundError(path) from exc
         yield io.StringIO(contents, newline=newline)
 
+    @contextlib.contextmanager
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> Iterator[IO[bytes]]:
+        """Implements Reader.open_read_binary.
+
+        The files are held as text, so are read as their UTF-8 encoding. Bytes
+        written by ``MemReadWriter.open_write_binary`` are read back unchanged.
+        """
+        try:
+            with self._lock:
+                contents = self._files[normalise_path(path)]
+        except KeyError as exc:
+            raise NotFoundError(path) from exc
+        yield io.BytesIO(contents.encode(_ENCODING, _MEM_BINARY_ERRORS))
+
     def iter_files(self) -> Iterator[pathlib.PurePath]:
-        """Implements Reader.iter_files."""
-        return iter(self._files.keys())
+        """Implements Reader.iter_files.
+
+        The files are those that existed when this is called, so writing files
+        while iterating is safe.
+        """
+        with self._lock:
+            paths = list(self._files.keys())
+        return iter(paths)
 
     def exists(
         self,
         path: pathlib.PurePath,
     ) -> bool:
         """Implements Reader.exists."""
-        return path in self._files
+        with self._lock:
+            return normalise_path(path) in self._files
+
+    def snapshot(self) -> dict[pathlib.PurePath, str]:
+        """Returns a copy of the files, which is unaffected by later writes."""
+        with self._lock:
+            return dict(self._files)
+
+    def export_zip(self, zip_path: pathlib.Path) -> None:
+        """Writes a snapshot of the files to a ZIP file.
+
+        The ZIP file is written alongside ``zip_path`` before replacing it, so
+        an existing file is only replaced by a complete ZIP file.
+
+        :param zip_path: Path of the ZIP file to write.
+        """
+        dest_dir = zip_path.parent
+        dest_dir.mkdir(parents=True, exist_ok=True)
+        new_zip_path = _copy_reader_into_zipfile(MemReader(self.snapshot()), dest_dir)
+        shutil.move(new_zip_path, zip_path)
 
 
 class MemReadWriter(MemReader):
-    """Reads and writes in-memory files."""
+    """Reads and writes in-memory files.
+
+    This keeps outputs off the disk entirely, such as to extract tables for
+    display, or to export them as a ZIP file afterwards with ``export_zip``.
+    """
 
     @classmethod
     @contextlib.contextmanager
-    def new_read_writer(cls, files: dict[pathlib.PurePath, str]) -> Iterator[Self]:
+    def new_read_writer(
+        cls,
+        files: Optional[dict[pathlib.PurePath, str]] = None,
+    ) -> Iterator[Self]:
         """Create a MemReadWriter to read and write in the given files."""
         yield cls(files)
 
@@ -320,31 +732,147 @@ class MemReadWriter(MemReader):
     ) -> Iterator[IO[str]]:
         """Implements ReadWriter.open_write."""
         f = io.StringIO(newline=newline)
-        try:
-            yield f
-        finally:
-            f.seek(0, io.SEEK_SET)
-            self._files[path] = f.read()
+        yield f
+        # Only completed writes are kept, as with DirReadWriter.
+        f.seek(0, io.SEEK_SET)
+        contents = f.read()
+        with self._lock:
+            self._files[normalise_path(path)] = contents
+
+    @contextlib.contextmanager
+    def open_write_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> Iterator[IO[bytes]]:
+        """Implements ReadWriter.open_write_binary.
+
+        The bytes are held as text decoded from UTF-8. Any bytes that are not
+        valid UTF-8 are held as lone surrogates, so that they are read back
+        unchanged by ``open_read_binary``.
+        """
+        f = io.BytesIO()
+        yield f
+        # Only completed writes are kept, as with DirReadWriter.
+        contents = f.getvalue().decode(_ENCODING, _MEM_BINARY_ERRORS)
+        with self._lock:
+            self._files[normalise_path(path)] = contents
+
+
+class _DiscardingIO(io.TextIOBase):
+    """Text file object that discards everything written to it."""
+
+    def writable(self) -> bool:
+        return True
+
+    def write(self, s: str, /) -> int:
+        return len(s)
+
+
+class _DiscardingBinaryIO(io.BufferedIOBase):
+    """Binary file object that discards everything written to it."""
+
+    def writable(self) -> bool:
+        return True
+
+    def write(self, b: bytes, /) -> int:  # type: ignore[override]
+        return len(b)
+
+
+class NullReadWriter:
+    """Discards all files written, and never contains any files.
+
+    This is useful to measure extraction performance without the cost of
+    writing outputs.
+    """
+
+    @classmethod
+    @contextlib.contextmanager
+    def new_reader(cls) -> Iterator[Self]:
+        """Create a NullReadWriter as a Reader."""
+        yield cls()
+
+    @classmethod
+    @contextlib.contextmanager
+    def new_read_writer(cls) -> Iterator[Self]:
+        """Create a NullReadWriter."""
+        yield cls()
+
+    def open_read(
+        self,
+        path: pathlib.PurePath,
+        newline: str = _NEWLINE,
+    ) -> contextlib.AbstractContextManager[IO[str]]:
+        """Implements Reader.open_read."""
+        del newline  # unused
+        raise NotFoundError(path)
+
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Implements Reader.open_read_binary."""
+        raise NotFoundError(path)
+
+    def iter_files(self) -> Iterator[pathlib.PurePath]:
+        """Implements Reader.iter_files."""
+        yield from ()
+
+    def exists(
+        self,
+        path: pathlib.PurePath,
+    ) -> bool:
+        """Implements Reader.exists."""
+        del path  # unused
+        return False
+
+    def open_write(
+        self,
+        path: pathlib.PurePath,
+        newline: str = _NEWLINE,
+    ) -> contextlib.AbstractContextManager[IO[str]]:
+        """Implements ReadWriter.open_write."""
+        del path, newline  # unused
+        return _DiscardingIO()
+
+    def open_write_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> contextlib.AbstractContextManager[IO[bytes]]:
+        """Implements ReadWriter.open_write_binary."""
+        del path  # unused
+        return _DiscardingBinaryIO()
 
 
 def _native_to_zip(p: pathlib.PurePath) -> str:
     """Convert to a POSIX path within a ZIP file."""
-    return str(pathlib.PurePosixPath(p))
+    return str(pathlib.PurePosixPath(*normalise_path(p).parts))
 
 
 def _zip_to_native(s: str) -> pathlib.PurePath:
-    """Convert from a POSIX path within a ZIP file."""
-    return pathlib.PurePath(pathlib.PurePosixPath(s))
+    """Convert from a path within a ZIP file."""
+    return normalise_path(pathlib.PurePath(s))
 
 
 class ZipReader(Reader):
-    """Reads files from a ZIP file."""
+    """Reads files from a ZIP file.
+
+    Entry names are looked up by their ``normalise_path`` form, so that ZIP
+    files created on Windows (with backslash separators) or macOS (with
+    Unicode normal form D names) can be read.
+    """
 
     _zip_file: Optional[zipfile.ZipFile]
+    # Maps normalised paths to the names of entries in _zip_file.
+    _entries: dict[pathlib.PurePath, str]
 
     def __init__(self, zip_file: Optional[zipfile.ZipFile]) -> None:
         """Initialise the ZipReader to read from the opened ZIP file."""
         self._zip_file = zip_file
+        self._entries = {}
+        if zip_file is not None:
+            for info in zip_file.infolist():
+                if not info.is_dir():
+                    self._entries.setdefault(_zip_to_native(info.filename), info.filename)
 
     @classmethod
     @contextlib.contextmanager
@@ -361,14 +889,20 @@ class ZipReader(Reader):
         ``ZipReader`` will behave as if no files exist within it.
         :raises NotFoundError: If ``zip_path`` does not exist, and
         ``ignore_not_exist`` is False.
+        :raises PermissionDeniedError: If ``zip_path`` cannot be read.
+        :raises CorruptArchiveError: If ``zip_path`` is not a valid ZIP file.
         :returns: Context managed ZipReader.
         """
         try:
             zip_file = zipfile.ZipFile(zip_path, "r")
-        except FileNotFoundError:
+        except FileNotFoundError as exc:
             if not ignore_not_exist:
-                raise
+                raise NotFoundError(zip_path) from exc
             zip_file = None
+        except PermissionError as exc:
+            raise PermissionDeniedError(zip_path) from exc
+        except zipfile.BadZipFile as exc:
+            raise CorruptArchiveError(f"{zip_path}: {exc}") from exc
 
         try:
             yield cls(zip_file)
@@ -383,37 +917,43 @@ class ZipReader(Reader):
         newline: str = _NEWLINE,
     ) -> Iterator[IO[str]]:
         """Implements Reader.open_read."""
+        with self.open_read_binary(path) as f:
+            yield io.TextIOWrapper(f, encoding=_ENCODING, newline=newline)
+
+    @contextlib.contextmanager
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> Iterator[IO[bytes]]:
+        """Implements Reader.open_read_binary."""
         if self._zip_file is None:
             raise NotFoundError(path)
 
         try:
-            f = self._zip_file.open(_native_to_zip(path), "r")
+            name = self._entries[normalise_path(path)]
         except KeyError as exc:
             raise NotFoundError(path) from exc
+        try:
+            f = self._zip_file.open(name, "r")
+        except zipfile.BadZipFile as exc:
+            raise CorruptArchiveError(f"{path}: {exc}") from exc
         with f:
-            yield io.TextIOWrapper(f, encoding=_ENCODING, newline=newline)
+            try:
+                yield f
+            except (zipfile.BadZipFile, zlib.error) as exc:
+                # Damaged compressed data is only detected as it is read.
+                raise CorruptArchiveError(f"{path}: {exc}") from exc
 
     def iter_files(self) -> Iterator[pathlib.PurePath]:
         """Implements Reader.iter_files."""
-        if self._zip_file is None:
-            return
-
-        for info in self._zip_file.infolist():
-            yield _zip_to_native(info.filename)
+        yield from self._entries.keys()
 
     def exists(
         self,
         path: pathlib.PurePath,
     ) -> bool:
         """Implements Reader.exists."""
-        if self._zip_file is None:
-            return False
-
-        try:
-            self._zip_file.getinfo(_native_to_zip(path))
-        except KeyError:
-            return False
-        return True
+        return normalise_path(path) in self._entries
 
 
 class ZipReadWriter:
@@ -466,6 +1006,19 @@ class ZipReadWriter:
             with self._zip_reader.open_read(path, newline) as r:
                 yield r
 
+    @contextlib.contextmanager
+    def open_read_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> Iterator[IO[bytes]]:
+        """Implements Reader.open_read_binary."""
+        try:
+            with self._read_writer.open_read_binary(path) as r:
+                yield r
+        except NotFoundError:
+            with self._zip_reader.open_read_binary(path) as r:
+                yield r
+
     def iter_files(self) -> Iterator[pathlib.PurePath]:
         """Implements Reader.iter_files."""
         seen: set[pathlib.PurePath] = set()
@@ -493,6 +1046,32 @@ class ZipReadWriter:
         with self._read_writer.open_write(path, newline) as w:
             yield w
 
+    @contextlib.contextmanager
+    def open_write_binary(
+        self,
+        path: pathlib.PurePath,
+    ) -> Iterator[IO[bytes]]:
+        """Implements ReadWriter.open_write_binary."""
+        with self._read_writer.open_write_binary(path) as w:
+            yield w
+
+
+def copy_files(reader: Reader, read_writer: ReadWriter) -> list[pathlib.PurePath]:
+    """Copies all files from ``reader`` into ``read_writer``.
+
+    :param reader: Reader to copy files from.
+    :param read_writer: ReadWriter to copy files into.
+    :return: Paths of the copied files.
+    """
+    paths = sorted(reader.iter_files())
+    for path in paths:
+        with (
+            reader.open_read_binary(path) as r,
+            read_writer.open_write_binary(path) as w,
+        ):
+            shutil.copyfileobj(r, w)
+    return paths
+
 
 def _copy_reader_into_zipfile(
     reader: Reader,
@@ -508,14 +1087,9 @@ def _copy_reader_into_zipfile(
     ):
         for path in reader.iter_files():
             with (
-                reader.open_read(path, newline="") as r,
+                reader.open_read_binary(path) as r,
                 zw.open(_native_to_zip(path), mode="w") as w,
             ):
-                wb = io.TextIOWrapper(w, encoding=_ENCODING, newline="")
-                n = 0
-                while data := r.read(64 * 1024):
-                    wb.write(data)
-                    n += len(data)
-                wb.flush()
+                shutil.copyfileobj(r, w)
 
     return pathlib.Path(zf_path_str)
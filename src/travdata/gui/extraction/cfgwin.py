@@ -6,16 +6,36 @@
 
 import contextlib
 import dataclasses
+import datetime
+import functools
+import os
 import pathlib
 from typing import Callable, Optional
 
 from PySide6 import QtCore, QtWidgets, QtGui
 
-from travdata import commontext, config, filesio
+from travdata import commontext, config, csvutil, envinterp, filesio, project
 from travdata.config import cfgerror
-from travdata.extraction import bookextract, tableextract
-from travdata.gui import qtutil
-from travdata.gui.extraction import runnerwin
+from travdata.extraction import bookextract, metrics, tableextract
+from travdata.gui import palette, palettewin, qtutil
+from travdata.gui.extraction import history, historywin, presets, runnerwin
+
+
+# Key for persisting the window geometry.
+_GEOMETRY_KEY = "extraction_config"
+
+
+# Identifies a version of a file on disk, to detect changes made outside of the
+# GUI (e.g. by a git pull, or another instance of the GUI).
+_Fingerprint = tuple[int, int]
+
+
+def _file_fingerprint(path: pathlib.Path) -> Optional[_Fingerprint]:
+    try:
+        stat = path.stat()
+    except OSError:
+        return None
+    return stat.st_mtime_ns, stat.st_size
 
 
 @dataclasses.dataclass
@@ -33,11 +53,31 @@ def _open_config_reader(
     return config_type.new_reader(config_path)
 
 
+def _describe_config_io_error(exc: filesio.Error) -> str:
+    """Returns a description of a failure to read the configuration, with advice."""
+    match exc:
+        case filesio.NotFoundError():
+            return f"File not found in configuration: {exc}"
+        case filesio.PermissionDeniedError():
+            return (
+                f"Permission denied reading configuration: {exc}. "
+                "Check that the configuration is readable by your user."
+            )
+        case filesio.CorruptArchiveError():
+            return (
+                f"Configuration ZIP file is damaged: {exc}. "
+                "Try downloading or packaging it again."
+            )
+        case _:
+            return f"Could not read configuration: {exc}"
+
+
 def _open_read_writer(
+    output_type: filesio.IOType,
     path: pathlib.Path,
 ) -> contextlib.AbstractContextManager[filesio.ReadWriter]:
-    config_type = filesio.IOType.AUTO.resolve_auto(path)
-    return config_type.new_read_writer(path)
+    output_type = output_type.resolve_auto(path)
+    return output_type.new_read_writer(path)
 
 
 @dataclasses.dataclass
@@ -52,6 +92,17 @@ class _ExtractionConfigBuilder:  # pylint: disable=too-many-instance-attributes
     input_pdf: Optional[pathlib.Path] = None
     book_id: Optional[str] = None
     output_path: Optional[pathlib.Path] = None
+    # The following are not editable in the GUI, but are preserved from any
+    # loaded project or preset.
+    output_type: filesio.IOType = filesio.IOType.AUTO
+    with_tags: set[str] = dataclasses.field(default_factory=set)
+    without_tags: set[str] = dataclasses.field(default_factory=set)
+    root: Optional[pathlib.Path] = None
+    csv_encoding: csvutil.CsvEncoding = csvutil.CsvEncoding.UTF8
+    # The loaded project (if any) before and after interpolation, so that
+    # saving keeps its references to environment variables.
+    _source_project: Optional[project.Project] = dataclasses.field(default=None, init=False)
+    _expanded_project: Optional[project.Project] = dataclasses.field(default=None, init=False)
 
     @property
     def cfg(self) -> Optional[config.Config]:
@@ -101,21 +152,22 @@ class _ExtractionConfigBuilder:  # pylint: disable=too-many-instance-attributes
             self._config_type = filesio.IOType.AUTO.resolve_auto(
                 self._config_path,
             )
-            with _open_config_reader(self._config_type, self._config_path) as cfg_reader:
-                try:
+            try:
+                with _open_config_reader(self._config_type, self._config_path) as cfg_reader:
                     cfg = config.load_config(cfg_reader)
-                except filesio.NotFoundError as exc:
-                    self._cfg = None
-                    self._cfg_error = f"File not found in configuration: {exc}"
-                    self._cfg_version = None
-                except cfgerror.ConfigurationError as exc:
-                    self._cfg = None
-                    self._cfg_error = f"Configuration error: {exc}"
-                    self._cfg_version = None
-                else:
-                    self._cfg = cfg
-                    self._cfg_error = None
-                    self._cfg_version = config.load_config_version(cfg_reader)
+                    cfg_version = config.load_config_version(cfg_reader)
+            except filesio.Error as exc:
+                self._cfg = None
+                self._cfg_error = _describe_config_io_error(exc)
+                self._cfg_version = None
+            except cfgerror.ConfigurationError as exc:
+                self._cfg = None
+                self._cfg_error = f"Configuration error: {exc}"
+                self._cfg_version = None
+            else:
+                self._cfg = cfg
+                self._cfg_error = None
+                self._cfg_version = cfg_version
 
         if self._cfg is None or self.book_id not in self._cfg.books:
             self.book_id = None
@@ -152,6 +204,67 @@ class _ExtractionConfigBuilder:  # pylint: disable=too-many-instance-attributes
 
         return errors
 
+    def set_from_project(
+        self,
+        proj: project.Project,
+        source: Optional[project.Project] = None,
+    ) -> bool:
+        """Sets all fields from the given project.
+
+        :param proj: Project to set the fields from.
+        :param source: Project as loaded, if ``proj`` was interpolated from it.
+        :return: False if the project's book is not in the configuration, in
+        which case no book is selected.
+        """
+        self.set_config_path(proj.resolved_config_path())
+        self.input_pdf = proj.input_pdf
+        self.output_path = proj.output_path
+        self.output_type = proj.output_type
+        self.with_tags = set(proj.with_tags)
+        self.without_tags = set(proj.without_tags)
+        self.root = proj.root
+        self.csv_encoding = proj.csv_encoding
+        self._source_project = source
+        self._expanded_project = None if source is None else proj
+        self.book_id = None
+        if self._cfg is not None and proj.book_id in self._cfg.books:
+            self.book_id = proj.book_id
+        return self.book_id is not None
+
+    def build_project(self) -> Optional[project.Project]:
+        """Builds a project from the current fields, if complete."""
+        if self._config_path is None:
+            return None
+        if self.input_pdf is None:
+            return None
+        if self.book_id is None:
+            return None
+        if self.output_path is None:
+            return None
+
+        return project.Project(
+            book_id=self.book_id,
+            input_pdf=self.input_pdf,
+            output_path=self.output_path,
+            config_path=self._config_path,
+            output_type=self.output_type,
+            with_tags=set(self.with_tags),
+            without_tags=set(self.without_tags),
+            root=self.root,
+            csv_encoding=self.csv_encoding,
+        )
+
+    def build_project_to_save(self) -> Optional[project.Project]:
+        """Builds a project to save from the current fields, if complete.
+
+        Unlike ``build_project``, values that were interpolated from environment
+        variables are saved as the original references, unless changed since.
+        """
+        proj = self.build_project()
+        if proj is None or self._source_project is None or self._expanded_project is None:
+            return proj
+        return proj.uninterpolated(self._source_project, self._expanded_project)
+
     def build(self) -> Optional[bookextract.ExtractionConfig]:
         """Builds the extraction configuration, if complete."""
         if self._cfg is None:
@@ -167,14 +280,39 @@ class _ExtractionConfigBuilder:  # pylint: disable=too-many-instance-attributes
 
         return bookextract.ExtractionConfig(
             cfg_reader_ctx=_open_config_reader(self._config_type, self._config_path),
-            out_writer_ctx=_open_read_writer(self.output_path),
+            out_writer_ctx=_open_read_writer(self.output_type, self.output_path),
             input_pdf=self.input_pdf,
             book_id=self.book_id,
             overwrite_existing=False,
-            with_tags=frozenset(),
-            without_tags=frozenset(),
+            with_tags=frozenset(self.with_tags),
+            without_tags=frozenset(self.without_tags),
+            csv_encoding=self.csv_encoding,
         )
 
+    def plan_outputs(self) -> Optional[bookextract.PlannedOutputs]:
+        """Returns the files that extraction would write into an existing output.
+
+        :raises cfgerror.ConfigurationError: If the configuration is invalid.
+        :raises OSError: If the output cannot be read.
+        :return: Planned outputs, or None if the extraction configuration is
+        incomplete, or the output does not yet exist.
+        """
+        ext_cfg = self.build()
+        if ext_cfg is None or self._config_path is None or self.output_path is None:
+            return None
+        if not self.output_path.exists():
+            return None
+        output_type = self.output_type.resolve_auto(self.output_path)
+        with (
+            _open_config_reader(self._config_type, self._config_path) as cfg_reader,
+            output_type.new_reader(self.output_path) as out_reader,
+        ):
+            return bookextract.plan_outputs(
+                ext_cfg=ext_cfg,
+                cfg_reader=cfg_reader,
+                out_reader=out_reader,
+            )
+
 
 class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many-instance-attributes
     """QT window to configure and start PDF extraction."""
@@ -187,27 +325,42 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
     _extract: Optional[bookextract.ExtractionConfig]
 
     _runner: Optional[runnerwin.ExtractionRunnerWindow]
+    _history_window: Optional[historywin.ExtractionHistoryWindow]
+
+    # Project file last opened or saved, and its fingerprint at that time.
+    _project_path: Optional[pathlib.Path]
+    _project_fingerprint: Optional[_Fingerprint]
+
+    # True if the compact layout (for small screens) is in use.
+    _compact: bool
 
     def __init__(
         self,
         thread_pool: QtCore.QThreadPool,
         table_reader: tableextract.TableReader,
         default_config_path: Optional[pathlib.Path],
+        font_scaler: qtutil.FontScaler,
     ) -> None:
         super().__init__()
         self.setWindowTitle("Travdata Extraction Setup")
+        self._font_scaler = font_scaler
 
         icon_provider = QtWidgets.QFileIconProvider()
         self._file_icon = icon_provider.icon(icon_provider.IconType.File)
         self._folder_icon = icon_provider.icon(icon_provider.IconType.Folder)
 
-        data_usage_text = QtWidgets.QLabel(commontext.DATA_USAGE)
+        self._data_usage_text = QtWidgets.QLabel(commontext.DATA_USAGE)
+        self._data_usage_text.setWordWrap(True)
 
         self._thread_pool = thread_pool
         self._table_reader = table_reader
         self._default_config_path = default_config_path
 
         self._runner = None
+        self._history_window = None
+        self._project_path = None
+        self._project_fingerprint = None
+        self._compact = False
 
         self._book_combo_dirty = True
 
@@ -217,10 +370,20 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
 
         self._extract_button = QtWidgets.QPushButton("Extract")
         self._extract_button.clicked.connect(self._run_extraction)
+        qtutil.set_accessible(
+            self._extract_button,
+            "Extract",
+            "Start extracting tables from the selected PDF into the output.",
+        )
 
+        self._init_project_menu()
+        self._presets_box = self._init_presets()
+        self._project_box = self._init_project()
         outer_box = qtutil.make_group_vbox(
             "Extract tables from PDF",
-            data_usage_text,
+            self._data_usage_text,
+            self._presets_box,
+            self._project_box,
             self._init_select_config(),
             self._init_select_input_pdf(),
             self._init_select_output(),
@@ -230,10 +393,277 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
                 QtWidgets.QSizePolicy.Policy.MinimumExpanding,
                 QtWidgets.QSizePolicy.Policy.MinimumExpanding,
             ),
+        )
+
+        # The settings scroll on small screens, while the extract button stays
+        # in view below them.
+        central = QtWidgets.QWidget()
+        central_layout = QtWidgets.QVBoxLayout(central)
+        central_layout.addWidget(qtutil.make_scroll_area(outer_box))
+        central_layout.addWidget(self._extract_button)
+
+        self.setCentralWidget(central)
+        self._init_status_bar()
+        self._init_view_menu()
+        self._init_history_menu()
+        self._set_compact_layout(qtutil.compact_layout())
+
+        qtutil.set_tab_order(
+            self._save_preset_button,
+            self._open_project_button,
+            self._save_project_button,
+            self._config_path_button_dir,
+            self._config_path_button_zip,
+            self._default_config_path_button,
+            self._config_path_label,
+            self._config_version_label,
+            self._input_pdf_button,
+            self._input_pdf_label,
+            self._book_combo,
+            self._output_path_button_dir,
+            self._output_path_button_zip,
+            self._output_path_label,
             self._extract_button,
         )
 
-        self.setCentralWidget(outer_box)
+        qtutil.restore_geometry(self, _GEOMETRY_KEY)
+
+    def _init_status_bar(self) -> None:
+        self._status_config_label = QtWidgets.QLabel("")
+        self._status_pdf_label = QtWidgets.QLabel("")
+        self._status_activity_label = QtWidgets.QLabel("")
+
+        status_bar = self.statusBar()
+        status_bar.addWidget(self._status_config_label)
+        status_bar.addWidget(self._status_pdf_label)
+        status_bar.addPermanentWidget(self._status_activity_label)
+
+    def _init_view_menu(self) -> None:
+        view_menu = self.menuBar().addMenu("&View")
+
+        self._palette_action = view_menu.addAction("Command &palette...")
+        self._palette_action.setShortcut(QtGui.QKeySequence("Ctrl+P"))
+        self._palette_action.triggered.connect(self._show_palette)
+        view_menu.addSeparator()
+
+        larger_action = view_menu.addAction("&Larger text")
+        larger_action.setShortcut(QtGui.QKeySequence.StandardKey.ZoomIn)
+        larger_action.triggered.connect(self._font_scaler.larger)
+        smaller_action = view_menu.addAction("&Smaller text")
+        smaller_action.setShortcut(QtGui.QKeySequence.StandardKey.ZoomOut)
+        smaller_action.triggered.connect(self._font_scaler.smaller)
+        reset_action = view_menu.addAction("&Reset text size")
+        reset_action.setShortcut(QtGui.QKeySequence("Ctrl+0"))
+        reset_action.triggered.connect(self._font_scaler.reset)
+
+        view_menu.addSeparator()
+        scale_menu = view_menu.addMenu("&Interface scale")
+        scale_group = QtGui.QActionGroup(scale_menu)
+        current_scale = qtutil.ui_scale()
+        for scale in qtutil.UI_SCALES:
+            action = scale_menu.addAction(f"{scale:.0%}")
+            action.setCheckable(True)
+            action.setChecked(scale == current_scale)
+            action.triggered.connect(functools.partial(self._set_ui_scale, scale))
+            scale_group.addAction(action)
+
+        view_menu.addSeparator()
+        compact_action = view_menu.addAction("&Compact layout")
+        compact_action.setCheckable(True)
+        compact_action.setChecked(qtutil.compact_layout())
+        compact_action.toggled.connect(self._toggle_compact_layout)
+        data_usage_action = view_menu.addAction("&Data usage notice")
+        data_usage_action.triggered.connect(self._show_data_usage)
+
+    def _init_project_menu(self) -> None:
+        # Duplicates the project and preset buttons, which are hidden by the
+        # compact layout.
+        project_menu = self.menuBar().addMenu("&Project")
+        open_action = project_menu.addAction("&Open project...")
+        open_action.setShortcut(QtGui.QKeySequence.StandardKey.Open)
+        open_action.triggered.connect(self._open_project)
+        save_action = project_menu.addAction("&Save project...")
+        save_action.setShortcut(QtGui.QKeySequence.StandardKey.Save)
+        save_action.triggered.connect(self._save_project)
+        project_menu.addSeparator()
+        self._presets_menu = project_menu.addMenu("Run &preset")
+        save_preset_action = project_menu.addAction("Save as p&reset...")
+        save_preset_action.triggered.connect(self._save_preset)
+
+    def _set_compact_layout(self, compact: bool) -> None:
+        """Shows or hides the parts of the window that the compact layout omits.
+
+        :param compact: True to use the compact layout.
+        """
+        self._compact = compact
+        self._data_usage_text.setVisible(not compact)
+        self._presets_box.setVisible(not compact)
+        self._project_box.setVisible(not compact)
+
+    @QtCore.Slot(bool)
+    def _toggle_compact_layout(self, compact: bool) -> None:
+        qtutil.save_compact_layout(compact)
+        self._set_compact_layout(compact)
+
+    @QtCore.Slot()
+    def _show_palette(self) -> None:
+        commands = palettewin.menu_commands(self.menuBar(), exclude=[self._palette_action])
+        if self._book_combo.isEnabled():
+            for i in range(self._book_combo.count()):
+                commands.append(
+                    palette.Command(
+                        f"Select book > {self._book_combo.itemText(i)}",
+                        functools.partial(self._book_combo.setCurrentIndex, i),
+                    )
+                )
+        palettewin.CommandPalette(commands, self).exec()
+
+    @QtCore.Slot()
+    def _show_data_usage(self) -> None:
+        QtWidgets.QMessageBox.information(self, "Data usage", commontext.DATA_USAGE)
+
+    def _init_history_menu(self) -> None:
+        history_menu = self.menuBar().addMenu("&History")
+        show_action = history_menu.addAction("Show past &runs")
+        show_action.triggered.connect(self._show_history)
+
+    @QtCore.Slot()
+    def _show_history(self) -> None:
+        if self._history_window is None:
+            self._history_window = historywin.ExtractionHistoryWindow(replay=self._replay)
+        self._history_window.refresh()
+        self._history_window.show()
+        self._history_window.raise_()
+
+    def _replay(self, proj: project.Project) -> None:
+        if self._runner is not None:
+            self.statusBar().showMessage("An extraction is already running.")
+            return
+        found_book = self._extract_builder.set_from_project(proj)
+        self._book_combo_dirty = True
+        self._refresh_from_state()
+        if not found_book:
+            self.statusBar().showMessage(
+                f"Not replaying: book {proj.book_id} is not in the configuration."
+            )
+            return
+        self._run_extraction()
+
+    def _record_run(
+        self,
+        proj: project.Project,
+        started: str,
+        outcome: str,
+        duration: float,
+        run_metrics: metrics.Metrics,
+    ) -> None:
+        record = history.RunRecord(
+            started=started,
+            project=proj,
+            outcome=outcome,
+            duration=duration,
+            tables_succeeded=run_metrics.tables_succeeded,
+            tables_failed=run_metrics.tables_failed,
+        )
+        try:
+            history.record_run(record)
+        except OSError as exc:
+            self.statusBar().showMessage(f"Could not record run in history: {exc}")
+            return
+        if self._history_window is not None:
+            self._history_window.refresh()
+
+    def _set_ui_scale(self, scale: float) -> None:
+        qtutil.save_ui_scale(scale)
+        self.statusBar().showMessage(
+            f"Interface scale will be {scale:.0%} when the program is next started."
+        )
+
+    def _refresh_status_bar(self) -> None:
+        builder = self._extract_builder
+        if builder.cfg is None:
+            self._status_config_label.setText("No configuration")
+        else:
+            version = builder.config_version or "<unknown>"
+            self._status_config_label.setText(f"Configuration version {version}")
+
+        if builder.input_pdf is None:
+            self._status_pdf_label.setText("No PDF")
+        else:
+            self._status_pdf_label.setText(f"PDF: {builder.input_pdf.name}")
+
+        if self._runner is None:
+            self._status_activity_label.setText("Idle")
+        else:
+            self._status_activity_label.setText("Extracting...")
+
+    def _init_presets(self) -> QtWidgets.QWidget:
+        self._save_preset_button = QtWidgets.QPushButton("Save as preset")
+        self._save_preset_button.clicked.connect(self._save_preset)
+        qtutil.set_accessible(
+            self._save_preset_button,
+            "Save as preset",
+            "Save the current settings as a named preset.",
+        )
+        self._preset_buttons_layout = QtWidgets.QHBoxLayout()
+        self._preset_buttons_layout.setContentsMargins(0, 0, 0, 0)
+        preset_buttons = QtWidgets.QWidget()
+        preset_buttons.setLayout(self._preset_buttons_layout)
+        self._preset_error = QtWidgets.QLabel("")
+        qtutil.set_error_style(self._preset_error)
+        qtutil.set_accessible(self._preset_error, "Preset error")
+        self._repopulate_presets()
+
+        return qtutil.make_group_hbox(
+            "Presets",
+            preset_buttons,
+            self._save_preset_button,
+            self._preset_error,
+            QtWidgets.QSpacerItem(
+                0,
+                0,
+                QtWidgets.QSizePolicy.Policy.MinimumExpanding,
+                QtWidgets.QSizePolicy.Policy.Minimum,
+            ),
+        )
+
+    def _repopulate_presets(self) -> None:
+        while (item := self._preset_buttons_layout.takeAt(0)) is not None:
+            if (widget := item.widget()) is not None:
+                widget.deleteLater()
+        for name, path in presets.list_presets().items():
+            button = QtWidgets.QPushButton(name)
+            button.setToolTip(f"Load and extract using preset {name}")
+            qtutil.set_accessible(button, f"Preset {name}", button.toolTip())
+            button.clicked.connect(functools.partial(self._run_preset, path))
+            self._preset_buttons_layout.addWidget(button)
+        self._presets_menu.clear()
+        for name, path in presets.list_presets().items():
+            action = self._presets_menu.addAction(name)
+            action.triggered.connect(functools.partial(self._run_preset, path))
+        self._presets_menu.setEnabled(not self._presets_menu.isEmpty())
+
+    def _init_project(self) -> QtWidgets.QWidget:
+        self._open_project_button = QtWidgets.QPushButton(self._file_icon, "Open project")
+        self._open_project_button.clicked.connect(self._open_project)
+        self._save_project_button = QtWidgets.QPushButton(self._file_icon, "Save project")
+        self._save_project_button.clicked.connect(self._save_project)
+        self._project_error = QtWidgets.QLabel("")
+        qtutil.set_error_style(self._project_error)
+        qtutil.set_accessible(self._project_error, "Project error")
+
+        return qtutil.make_group_hbox(
+            "Project",
+            self._open_project_button,
+            self._save_project_button,
+            self._project_error,
+            QtWidgets.QSpacerItem(
+                0,
+                0,
+                QtWidgets.QSizePolicy.Policy.MinimumExpanding,
+                QtWidgets.QSizePolicy.Policy.Minimum,
+            ),
+        )
 
     def _init_select_config(self) -> QtWidgets.QWidget:
         self._config_path_button_dir = QtWidgets.QPushButton(self._folder_icon, "Select directory")
@@ -242,6 +672,9 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
         self._config_path_button_zip.clicked.connect(self._select_config_path_zip)
         self._default_config_path_button = QtWidgets.QPushButton("Default")
         self._default_config_path_button.clicked.connect(self._select_default_config_path)
+        qtutil.set_accessible(self._config_path_button_dir, "Select configuration directory")
+        qtutil.set_accessible(self._config_path_button_zip, "Select configuration ZIP file")
+        qtutil.set_accessible(self._default_config_path_button, "Use default configuration")
 
         select_config_box = QtWidgets.QWidget()
         layout = QtWidgets.QHBoxLayout(select_config_box)
@@ -258,10 +691,11 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
             )
         )
 
-        self._config_path_label = QtWidgets.QLabel("")
-        self._config_version_label = QtWidgets.QLabel("")
+        self._config_path_label = qtutil.make_value_label("Configuration path")
+        self._config_version_label = qtutil.make_value_label("Configuration version")
         self._config_path_error = QtWidgets.QLabel("")
         qtutil.set_error_style(self._config_path_error)
+        qtutil.set_accessible(self._config_path_error, "Configuration error")
 
         config_box = QtWidgets.QGroupBox("Extraction configuration")
         layout = QtWidgets.QFormLayout(config_box)
@@ -273,13 +707,20 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
         return config_box
 
     def _init_select_input_pdf(self) -> QtWidgets.QWidget:
-        self._input_pdf_label = QtWidgets.QLabel("")
+        self._input_pdf_label = qtutil.make_value_label("Input PDF path")
         self._input_pdf_error = QtWidgets.QLabel("")
+        qtutil.set_accessible(self._input_pdf_error, "Input PDF error")
         self._input_pdf_button = QtWidgets.QPushButton(self._file_icon, "Select PDF")
         self._input_pdf_button.clicked.connect(self._select_input_pdf)
+        qtutil.set_accessible(self._input_pdf_button, "Select input PDF")
 
         self._book_combo = QtWidgets.QComboBox()
         self._book_combo.currentIndexChanged.connect(self._select_book)
+        qtutil.set_accessible(
+            self._book_combo,
+            "Book",
+            "The book that the input PDF contains.",
+        )
 
         input_pdf_box = QtWidgets.QGroupBox("Input PDF")
         layout = QtWidgets.QFormLayout(input_pdf_box)
@@ -292,14 +733,17 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
         return input_pdf_box
 
     def _init_select_output(self) -> QtWidgets.QWidget:
-        self._output_path_label = QtWidgets.QLabel("")
+        self._output_path_label = qtutil.make_value_label("Output path")
         self._output_path_error = QtWidgets.QLabel("")
         qtutil.set_error_style(self._output_path_error)
+        qtutil.set_accessible(self._output_path_error, "Output error")
 
         self._output_path_button_dir = QtWidgets.QPushButton(self._folder_icon, "Select directory")
         self._output_path_button_dir.clicked.connect(self._select_output_dir)
         self._output_path_button_zip = QtWidgets.QPushButton(self._file_icon, "Select ZIP")
         self._output_path_button_zip.clicked.connect(self._select_output_zip)
+        qtutil.set_accessible(self._output_path_button_dir, "Select output directory")
+        qtutil.set_accessible(self._output_path_button_zip, "Select output ZIP file")
         self._output_path_button = QtWidgets.QPushButton(
             self._folder_icon,
             "Select output path",
@@ -333,6 +777,14 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
         self._refresh_from_state()
         return super().showEvent(event)
 
+    def closeEvent(self, event: QtGui.QCloseEvent) -> None:  # pylint: disable=invalid-name
+        """Intercepts the window being closed."""
+        qtutil.save_geometry(self, _GEOMETRY_KEY)
+        if self._runner is not None:
+            # Stops any extraction in progress.
+            self._runner.close()
+        return super().closeEvent(event)
+
     def _refresh_from_state(self) -> None:
         """Update widgets from current self.state."""
         _bulk_enable(
@@ -361,6 +813,134 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
 
         self._extract = self._extract_builder.build()
         self._extract_button.setEnabled(self._extract is not None and self._runner is None)
+        self._save_project_button.setEnabled(self._extract_builder.build_project() is not None)
+        self._save_preset_button.setEnabled(self._extract_builder.build_project() is not None)
+        self._refresh_status_bar()
+
+    def _load_project_file(self, path: pathlib.Path) -> None:
+        fingerprint = _file_fingerprint(path)
+        try:
+            source = project.load_project(path)
+            proj = source.interpolated(envinterp.Interpolator(os.environ)).resolved(path.parent)
+            found_book = self._extract_builder.set_from_project(proj, source)
+        except (OSError, cfgerror.ConfigurationError) as exc:
+            self._update_box_error(self._project_error, f"Could not open project: {exc}")
+            return
+        self._update_box_error(
+            self._project_error,
+            None if found_book else f"Book {proj.book_id} is not in the configuration.",
+        )
+        self._project_path = path
+        self._project_fingerprint = fingerprint
+        self._book_combo_dirty = True
+        self._refresh_from_state()
+
+    def _confirm_save_over_changes(self, path: pathlib.Path) -> bool:
+        """Checks with the user before overwriting a project changed on disk.
+
+        :param path: Project file about to be saved.
+        :return: True if the save should go ahead.
+        """
+        if path != self._project_path or _file_fingerprint(path) == self._project_fingerprint:
+            return True
+
+        box = QtWidgets.QMessageBox(self)
+        box.setIcon(QtWidgets.QMessageBox.Icon.Warning)
+        box.setWindowTitle("Project changed on disk")
+        box.setText(f"{path} has been changed outside of this window since it was opened.")
+        box.setInformativeText(
+            "Reload it to discard the changes made here, or overwrite it to discard "
+            "the changes made on disk."
+        )
+        reload_button = box.addButton("Reload", QtWidgets.QMessageBox.ButtonRole.ResetRole)
+        overwrite_button = box.addButton("Overwrite", QtWidgets.QMessageBox.ButtonRole.AcceptRole)
+        box.addButton(QtWidgets.QMessageBox.StandardButton.Cancel)
+        box.exec()
+
+        clicked = box.clickedButton()
+        if clicked is reload_button:
+            self._load_project_file(path)
+        return clicked is overwrite_button
+
+    def _update_box_error(self, label: QtWidgets.QLabel, error: Optional[str]) -> None:
+        """Updates an error label, which the compact layout might have hidden.
+
+        In the compact layout, the error is also shown in the status bar.
+        """
+        _update_error_label(label, error)
+        if error is not None and self._compact:
+            self.statusBar().showMessage(error)
+
+    @QtCore.Slot()
+    def _open_project(self) -> None:
+        _do_file_selection(
+            parent=self,
+            accept_mode=QtWidgets.QFileDialog.AcceptMode.AcceptOpen,
+            file_mode=QtWidgets.QFileDialog.FileMode.ExistingFile,
+            selected_callback=self._load_project_file,
+            filter_=f"*{project.PROJECT_SUFFIX}",
+        )
+
+    @QtCore.Slot()
+    def _save_project(self) -> None:
+        proj = self._extract_builder.build_project_to_save()
+        if proj is None:
+            return
+
+        def selected(path: pathlib.Path) -> None:
+            if not self._confirm_save_over_changes(path):
+                return
+            try:
+                project.save_project(path, proj.relativised(path.parent))
+            except OSError as exc:
+                self._update_box_error(self._project_error, f"Could not save project: {exc}")
+                return
+            self._update_box_error(self._project_error, None)
+            self._project_path = path
+            self._project_fingerprint = _file_fingerprint(path)
+
+        _do_file_selection(
+            parent=self,
+            accept_mode=QtWidgets.QFileDialog.AcceptMode.AcceptSave,
+            file_mode=QtWidgets.QFileDialog.FileMode.AnyFile,
+            selected_callback=selected,
+            filter_=f"*{project.PROJECT_SUFFIX}",
+        )
+
+    @QtCore.Slot()
+    def _save_preset(self) -> None:
+        proj = self._extract_builder.build_project_to_save()
+        if proj is None:
+            return
+        name, ok = QtWidgets.QInputDialog.getText(self, "Save preset", "Preset name:")
+        if not ok:
+            return
+        try:
+            presets.save_preset(name, proj)
+        except (OSError, ValueError) as exc:
+            self._update_box_error(self._preset_error, f"Could not save preset: {exc}")
+            return
+        self._update_box_error(self._preset_error, None)
+        self._repopulate_presets()
+
+    def _run_preset(self, path: pathlib.Path) -> None:
+        try:
+            source = project.load_project(path)
+            proj = source.interpolated(envinterp.Interpolator(os.environ)).resolved(path.parent)
+            found_book = self._extract_builder.set_from_project(proj, source)
+        except (OSError, cfgerror.ConfigurationError) as exc:
+            self._update_box_error(self._preset_error, f"Could not load preset: {exc}")
+            return
+        self._book_combo_dirty = True
+        self._refresh_from_state()
+        if not found_book:
+            self._update_box_error(
+                self._preset_error,
+                f"Not extracting: book {proj.book_id} is not in the configuration.",
+            )
+            return
+        self._update_box_error(self._preset_error, None)
+        self._run_extraction()
 
     def _selected_config(self, config_path: pathlib.Path) -> None:
         self._book_combo_dirty = self._extract_builder.set_config_path(config_path)
@@ -459,6 +1039,53 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
             filter_="*.zip",
         )
 
+    def _confirm_outputs(self) -> bool:
+        """Shows the user what extraction would write into an existing output.
+
+        :return: True if the extraction should go ahead.
+        """
+        output_path = self._extract_builder.output_path
+        if output_path is None or not qtutil.wants_overwrite_confirmation(output_path):
+            return True
+        if output_path.is_dir() and not any(output_path.iterdir()):
+            return True
+        try:
+            planned = self._extract_builder.plan_outputs()
+        except (OSError, KeyError, cfgerror.ConfigurationError, filesio.Error):
+            # Extraction itself reports these problems in more detail.
+            return True
+        if planned is None:
+            return True
+
+        box = QtWidgets.QMessageBox(self)
+        box.setIcon(QtWidgets.QMessageBox.Icon.Question)
+        box.setWindowTitle("Extract into existing output")
+        box.setText(f"{output_path} already contains files.")
+        box.setInformativeText(
+            f"{len(planned.created)} files will be created, and "
+            f"{len(planned.overwritten)} existing files will be overwritten. "
+            f"{len(planned.kept)} tables that already exist will be kept. "
+            "No files will be deleted."
+        )
+        details = [
+            *(f"Create: {path}" for path in planned.created),
+            *(f"Overwrite: {path}" for path in planned.overwritten),
+            *(f"Keep: {path}" for path in planned.kept),
+        ]
+        box.setDetailedText("\n".join(details))
+        dont_ask = QtWidgets.QCheckBox("Don't ask again for this output")
+        box.setCheckBox(dont_ask)
+        box.setStandardButtons(
+            QtWidgets.QMessageBox.StandardButton.Ok | QtWidgets.QMessageBox.StandardButton.Cancel
+        )
+        box.setDefaultButton(QtWidgets.QMessageBox.StandardButton.Cancel)
+
+        if box.exec() != QtWidgets.QMessageBox.StandardButton.Ok:
+            return False
+        if dont_ask.isChecked():
+            qtutil.skip_overwrite_confirmation(output_path)
+        return True
+
     @QtCore.Slot()
     def _run_extraction(self) -> None:
         if self._extract is None:
@@ -466,13 +1093,23 @@ class ExtractionConfigWindow(QtWidgets.QMainWindow):  # pylint: disable=too-many
         if self._runner is not None:
             # Extraction already running.
             return
+        if not self._confirm_outputs():
+            return
+        output_path = self._extract_builder.output_path
+        if output_path is None:
+            return
         self._runner = runnerwin.ExtractionRunnerWindow(
             self._extract,
             self._thread_pool,
             self._table_reader,
+            output_path,
+            self._extract_builder.output_type,
         )
         self._refresh_from_state()
         self._runner.closing.connect(self._runner_closing)
+        if (proj := self._extract_builder.build_project()) is not None:
+            started = datetime.datetime.now().isoformat()
+            self._runner.ended.connect(functools.partial(self._record_run, proj, started))
         self._runner.show()
         self._runner.start_extraction()
 
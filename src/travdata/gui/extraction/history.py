@@ -0,0 +1,99 @@
+# -*- coding: utf-8 -*-
+"""History of extraction runs, saved in the user's data directory."""
+
+import dataclasses
+import datetime
+import os
+import pathlib
+from typing import Any, ClassVar, Self
+
+from ruamel import yaml
+from travdata import project, yamlutil
+from travdata.config import cfgerror, yamlreg
+
+
+# Maximum number of runs kept in the history. Older runs are removed.
+MAX_RUNS = 50
+
+_TIMESTAMP_FORMAT = "%Y%m%dT%H%M%S%f"
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class RunRecord(yamlutil.YamlMappingMixin):
+    """Record of a past extraction run.
+
+    :field started: Local time that the run started, in ISO 8601 format.
+    :field project: Extraction setup of the run, which can be used to replay it.
+    :field outcome: "complete" if the run finished, or "stopped" if it was
+    cancelled or failed.
+    :field duration: Time taken by the run, in seconds.
+    :field tables_succeeded: Number of tables extracted and written.
+    :field tables_failed: Number of tables that failed extraction.
+    """
+
+    yaml_tag: ClassVar = "!RunRecord"
+    yaml_format_version: ClassVar = 1
+    started: str
+    project: project.Project
+    outcome: str
+    duration: float = dataclasses.field(default=0.0, metadata={yamlutil.FROM_YAML: float})
+    tables_succeeded: int = dataclasses.field(default=0, metadata=yamlutil.INT_METADATA)
+    tables_failed: int = dataclasses.field(default=0, metadata=yamlutil.INT_METADATA)
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(started="", project=project.Project.yaml_create_empty(), outcome="")
+
+    def summary(self) -> str:
+        """Returns a one line human readable summary of the run."""
+        started = self.started.replace("T", " ").partition(".")[0]
+        return (
+            f"{started} {self.project.book_id}: {self.outcome}, "
+            f"{self.tables_succeeded} tables ({self.tables_failed} failed) "
+            f"in {self.duration:.0f}s"
+        )
+
+
+def history_dir() -> pathlib.Path:
+    """Returns the directory that run history is saved in."""
+    data_home = os.environ.get("XDG_DATA_HOME") or pathlib.Path.home() / ".local" / "share"
+    return pathlib.Path(data_home) / "travdata" / "history"
+
+
+def record_run(record: RunRecord) -> pathlib.Path:
+    """Saves a run to the history, removing the oldest runs beyond ``MAX_RUNS``.
+
+    :param record: Run to save.
+    :return: Path to the saved record.
+    """
+    directory = history_dir()
+    directory.mkdir(parents=True, exist_ok=True)
+    timestamp = datetime.datetime.fromisoformat(record.started).strftime(_TIMESTAMP_FORMAT)
+    path = directory / f"{timestamp}.yaml"
+    with path.open("wt", encoding="utf-8") as f:
+        yamlreg.YAML.dump(record, f)
+
+    for old_path in sorted(directory.glob("*.yaml"), reverse=True)[MAX_RUNS:]:
+        old_path.unlink()
+    return path
+
+
+def list_runs() -> list[RunRecord]:
+    """Returns the runs in the history, most recent first.
+
+    Records that cannot be read are skipped.
+    """
+    directory = history_dir()
+    if not directory.is_dir():
+        return []
+    records: list[RunRecord] = []
+    for path in sorted(directory.glob("*.yaml"), reverse=True):
+        try:
+            with path.open("rt", encoding="utf-8") as f:
+                record: Any = yamlreg.YAML.load(f)
+        except (OSError, yaml.YAMLError, cfgerror.ConfigurationError):
+            continue
+        if isinstance(record, RunRecord):
+            records.append(record)
+    return records
@@ -0,0 +1,91 @@
+# -*- coding: utf-8 -*-
+"""Defines a window that lists past extraction runs, and can replay them."""
+
+# Pylint doesn't like QT much.
+# pylint: disable=I1101
+
+from typing import Callable
+
+from PySide6 import QtCore, QtWidgets
+
+from travdata import project
+from travdata.gui import qtutil
+from travdata.gui.extraction import history
+
+
+# Key for persisting the window geometry.
+_GEOMETRY_KEY = "extraction_history"
+
+
+class ExtractionHistoryWindow(QtWidgets.QWidget):
+    """Window listing past extraction runs."""
+
+    def __init__(
+        self,
+        replay: Callable[[project.Project], None],
+        *args,
+        **kwargs,
+    ) -> None:
+        """Initialise the ExtractionHistoryWindow.
+
+        :param replay: Called with the project of a run that the user chose to
+        replay.
+        """
+        super().__init__(*args, **kwargs)
+        self.setWindowTitle("Travdata Extraction History")
+
+        self._replay = replay
+        self._records: list[history.RunRecord] = []
+
+        self._runs_list = QtWidgets.QListWidget()
+        self._runs_list.currentRowChanged.connect(self._selection_changed)
+        self._runs_list.itemActivated.connect(self._replay_selected)
+        qtutil.set_accessible(self._runs_list, "Past extraction runs")
+
+        self._replay_button = QtWidgets.QPushButton("Replay")
+        self._replay_button.clicked.connect(self._replay_selected)
+        qtutil.set_accessible(
+            self._replay_button,
+            "Replay",
+            "Run the selected extraction again with the same settings.",
+        )
+
+        contents = qtutil.make_group_vbox(
+            "Past extraction runs",
+            self._runs_list,
+            self._replay_button,
+        )
+
+        layout = QtWidgets.QStackedLayout()
+        layout.addWidget(contents)
+        self.setLayout(layout)
+
+        self.refresh()
+        qtutil.restore_geometry(self, _GEOMETRY_KEY)
+
+    def refresh(self) -> None:
+        """Reloads the list of runs from the history."""
+        self._records = history.list_runs()
+        self._runs_list.clear()
+        for record in self._records:
+            item = QtWidgets.QListWidgetItem(record.summary())
+            item.setToolTip(
+                f"Input: {record.project.input_pdf}\nOutput: {record.project.output_path}"
+            )
+            self._runs_list.addItem(item)
+        self._selection_changed(self._runs_list.currentRow())
+
+    def closeEvent(self, event) -> None:  # pylint: disable=invalid-name
+        """Captures event of window closing."""
+        qtutil.save_geometry(self, _GEOMETRY_KEY)
+        super().closeEvent(event)
+
+    @QtCore.Slot()
+    def _selection_changed(self, row: int) -> None:
+        self._replay_button.setEnabled(0 <= row < len(self._records))
+
+    @QtCore.Slot()
+    def _replay_selected(self) -> None:
+        row = self._runs_list.currentRow()
+        if 0 <= row < len(self._records):
+            self._replay(self._records[row].project)
@@ -0,0 +1,41 @@
+# -*- coding: utf-8 -*-
+"""Named extraction presets, saved as project files in the user's configuration."""
+
+import os
+import pathlib
+
+from travdata import project
+
+
+def presets_dir() -> pathlib.Path:
+    """Returns the directory that presets are saved in."""
+    config_home = os.environ.get("XDG_CONFIG_HOME") or pathlib.Path.home() / ".config"
+    return pathlib.Path(config_home) / "travdata" / "presets"
+
+
+def list_presets() -> dict[str, pathlib.Path]:
+    """Returns the saved presets.
+
+    :return: Mapping from preset name to the project file of the preset.
+    """
+    directory = presets_dir()
+    if not directory.is_dir():
+        return {}
+    return {path.stem: path for path in sorted(directory.glob(f"*{project.PROJECT_SUFFIX}"))}
+
+
+def save_preset(name: str, proj: project.Project) -> pathlib.Path:
+    """Saves a preset, replacing any existing preset with the same name.
+
+    :param name: Name of the preset. This must be usable as a filename.
+    :param proj: Project to save as the preset.
+    :raises ValueError: If ``name`` is not usable as a filename.
+    :return: Path to the saved project file.
+    """
+    if not name or name != pathlib.PurePath(name).name or name.startswith("."):
+        raise ValueError(f"{name!r} is not a valid preset name")
+    directory = presets_dir()
+    directory.mkdir(parents=True, exist_ok=True)
+    path = directory / f"{name}{project.PROJECT_SUFFIX}"
+    project.save_project(path, proj)
+    return path
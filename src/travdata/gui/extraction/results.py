@@ -0,0 +1,129 @@
+# -*- coding: utf-8 -*-
+"""Prepares extraction outputs and their outcomes for display in the GUI."""
+
+import csv
+import dataclasses
+import json
+import pathlib
+from typing import Any
+
+from travdata import csvutil, filesio
+from travdata.extraction import bookextract
+
+
+# Suffixes of output files that ``load_result`` can load.
+VIEWABLE_SUFFIXES = frozenset([".csv", ".json"])
+
+
+class ResultError(Exception):
+    """An output file could not be loaded as a table."""
+
+
+@dataclasses.dataclass
+class ResultTable:
+    """Contents of an output file, as a table of text.
+
+    :field header: Column names.
+    :field rows: Rows of cells. Rows may be shorter or longer than ``header``.
+    """
+
+    header: list[str]
+    rows: list[list[str]]
+
+
+def is_viewable(path: pathlib.PurePath) -> bool:
+    """Returns True if ``load_result`` can load the output file."""
+    return path.suffix in VIEWABLE_SUFFIXES
+
+
+def load_result(reader: filesio.Reader, path: pathlib.PurePath) -> ResultTable:
+    """Loads an output file as a table.
+
+    CSV files are shown as-is, with the first row as the header. Aggregate JSON
+    documents are flattened into one row per table row, with the path of the
+    table in the first column.
+
+    :param reader: Reader of the output.
+    :param path: Path of the output file.
+    :raises ResultError: If the file cannot be loaded.
+    :raises filesio.Error: If the file cannot be read.
+    :return: Loaded table.
+    """
+    match path.suffix:
+        case ".csv":
+            return _load_csv(reader, path)
+        case ".json":
+            return _load_json(reader, path)
+        case _:
+            raise ResultError(f"{path} is not a CSV or JSON file")
+
+
+def _load_csv(reader: filesio.Reader, path: pathlib.PurePath) -> ResultTable:
+    with csvutil.open_by_reader(reader, path) as f:
+        rows = list(csv.reader(f))
+    if not rows:
+        return ResultTable(header=[], rows=[])
+    return ResultTable(header=rows[0], rows=rows[1:])
+
+
+def _load_json(reader: filesio.Reader, path: pathlib.PurePath) -> ResultTable:
+    with reader.open_read(path) as f:
+        try:
+            document = json.load(f)
+        except json.JSONDecodeError as exc:
+            raise ResultError(f"{path} is not valid JSON: {exc}") from exc
+    if not isinstance(document, dict):
+        raise ResultError(f"{path} is not an aggregate JSON document")
+
+    keys: list[str] = []
+    width = 0
+    rows: list[list[str]] = []
+    for table_key, table_rows in document.items():
+        if table_key == bookextract.ATTRIBUTION_JSON_KEY:
+            continue
+        if not isinstance(table_rows, list):
+            raise ResultError(f"{path} has non-list rows for {table_key!r}")
+        for row in table_rows:
+            if isinstance(row, dict):
+                for key in row:
+                    if key not in keys:
+                        keys.append(key)
+                rows.append([table_key] + [_cell_text(row.get(key)) for key in keys])
+            elif isinstance(row, list):
+                width = max(width, len(row))
+                rows.append([table_key] + [_cell_text(cell) for cell in row])
+            else:
+                raise ResultError(f"{path} has a row that is not a list or object in {table_key!r}")
+
+    header = ["table"] + keys + [str(i) for i in range(len(keys) + 1, width + 1)]
+    return ResultTable(header=header, rows=rows)
+
+
+def _cell_text(value: Any) -> str:
+    if value is None:
+        return ""
+    if isinstance(value, str):
+        return value
+    return json.dumps(value)
+
+
+def describe_table_outcome(event: bookextract.TableFinished, errors: list[str]) -> str:
+    """Returns a description of the extraction of a table, for a tooltip.
+
+    :param event: Outcome of the table's extraction.
+    :param errors: Errors reported while extracting the table.
+    :return: Multi-line description.
+    """
+    lines = [str(event.output_path)]
+    if event.title is not None:
+        lines.append(event.title)
+    outcome = "Extracted" if event.success else "Failed"
+    if event.stats is not None:
+        lines.append(f"{outcome} in {event.duration:.1f}s: {event.stats.describe()}")
+    else:
+        lines.append(f"{outcome} in {event.duration:.1f}s")
+    if errors:
+        lines.append(f"Last error: {errors[-1]}")
+    if event.doc is not None:
+        lines.extend(["", event.doc.strip()])
+    return "\n".join(lines)
@@ -0,0 +1,200 @@
+# -*- coding: utf-8 -*-
+"""Defines a widget that browses the files produced by an extraction."""
+
+# Pylint doesn't like QT much.
+# pylint: disable=I1101
+
+import pathlib
+from typing import Optional
+
+from PySide6 import QtCore, QtGui, QtWidgets
+
+from travdata import filesio
+from travdata.extraction import bookextract
+from travdata.gui import qtutil
+from travdata.gui.extraction import results
+
+
+# Item data role holding the output path of a file item in the tree.
+_PATH_ROLE = QtCore.Qt.ItemDataRole.UserRole
+
+
+class ExtractionResultsView(QtWidgets.QWidget):
+    """Lists the files produced by an extraction, and shows their contents."""
+
+    def __init__(
+        self,
+        output_path: pathlib.Path,
+        output_type: filesio.IOType,
+        *args,
+        **kwargs,
+    ) -> None:
+        """Initialise the ExtractionResultsView.
+
+        :param output_path: Path to the directory or ZIP file written to.
+        :param output_type: How the output was written.
+        """
+        super().__init__(*args, **kwargs)
+
+        self._output_path = output_path
+        self._output_type = output_type.resolve_auto(output_path)
+        self._group_items: dict[pathlib.PurePath, QtWidgets.QTreeWidgetItem] = {}
+        self._file_items: dict[pathlib.PurePath, QtWidgets.QTreeWidgetItem] = {}
+
+        self._files_tree = QtWidgets.QTreeWidget()
+        self._files_tree.setHeaderHidden(True)
+        self._files_tree.currentItemChanged.connect(self._selection_changed)
+        qtutil.set_accessible(self._files_tree, "Extracted files")
+
+        self._table_model = QtGui.QStandardItemModel()
+        self._table_view = QtWidgets.QTableView()
+        self._table_view.setModel(self._table_model)
+        self._table_view.setSortingEnabled(True)
+        self._table_view.setEditTriggers(QtWidgets.QAbstractItemView.EditTrigger.NoEditTriggers)
+        qtutil.set_accessible(self._table_view, "Contents of the selected file")
+
+        self._error_label = QtWidgets.QLabel()
+        self._error_label.setWordWrap(True)
+        qtutil.set_error_style(self._error_label)
+
+        self._open_folder_button = QtWidgets.QPushButton("Open containing folder")
+        self._open_folder_button.clicked.connect(self._open_folder)
+        qtutil.set_accessible(
+            self._open_folder_button,
+            "Open containing folder",
+            "Open the folder containing the selected file in the file manager.",
+        )
+
+        self._copy_path_button = QtWidgets.QPushButton("Copy path")
+        self._copy_path_button.clicked.connect(self._copy_path)
+        qtutil.set_accessible(
+            self._copy_path_button,
+            "Copy path",
+            "Copy the path of the selected file to the clipboard.",
+        )
+
+        buttons = QtWidgets.QHBoxLayout()
+        buttons.addWidget(self._open_folder_button)
+        buttons.addWidget(self._copy_path_button)
+
+        viewer = QtWidgets.QWidget()
+        viewer_layout = QtWidgets.QVBoxLayout()
+        viewer_layout.setContentsMargins(0, 0, 0, 0)
+        viewer_layout.addWidget(self._table_view)
+        viewer_layout.addWidget(self._error_label)
+        viewer_layout.addLayout(buttons)
+        viewer.setLayout(viewer_layout)
+
+        splitter = QtWidgets.QSplitter()
+        splitter.addWidget(self._files_tree)
+        splitter.addWidget(viewer)
+        splitter.setStretchFactor(1, 1)
+
+        layout = QtWidgets.QVBoxLayout()
+        layout.addWidget(splitter)
+        self.setLayout(layout)
+
+        self._selection_changed(None)
+
+    def add_output(self, path: pathlib.PurePath) -> None:
+        """Adds a file produced by the extraction to the list.
+
+        Files are grouped by their directories, which follow the groups of the
+        book's configuration.
+        """
+        self._file_item(path)
+
+    def set_table_outcome(self, event: bookextract.TableFinished, errors: list[str]) -> None:
+        """Shows the outcome of extracting a table on its item in the list.
+
+        The table's title and documentation, statistics, the time taken and the
+        last error are shown as a tooltip, and tables that failed are marked as
+        such.
+
+        :param event: Outcome of the table's extraction.
+        :param errors: Errors reported while extracting the table.
+        """
+        item = self._file_item(event.output_path)
+        item.setToolTip(0, results.describe_table_outcome(event, errors))
+        if not event.success:
+            item.setText(0, f"{event.output_path.name} (failed)")
+        if not event.success or errors:
+            icon = self.style().standardIcon(QtWidgets.QStyle.StandardPixmap.SP_MessageBoxWarning)
+            item.setIcon(0, icon)
+
+    def _file_item(self, path: pathlib.PurePath) -> QtWidgets.QTreeWidgetItem:
+        if (item := self._file_items.get(path)) is None:
+            item = QtWidgets.QTreeWidgetItem([path.name])
+            item.setData(0, _PATH_ROLE, path)
+            item.setToolTip(0, str(path))
+            self._group_item(path.parent).addChild(item)
+            self._file_items[path] = item
+        return item
+
+    def _group_item(self, group: pathlib.PurePath) -> QtWidgets.QTreeWidgetItem:
+        if group == pathlib.PurePath():
+            return self._files_tree.invisibleRootItem()
+        if (item := self._group_items.get(group)) is None:
+            item = QtWidgets.QTreeWidgetItem([group.name])
+            self._group_item(group.parent).addChild(item)
+            item.setExpanded(True)
+            self._group_items[group] = item
+        return item
+
+    def _selected_path(self) -> Optional[pathlib.PurePath]:
+        item = self._files_tree.currentItem()
+        if item is None:
+            return None
+        path = item.data(0, _PATH_ROLE)
+        return path if isinstance(path, pathlib.PurePath) else None
+
+    def _full_path(self, path: pathlib.PurePath) -> pathlib.Path:
+        """Returns the filesystem path of an output file.
+
+        Files within a ZIP output are given as if the ZIP file were a
+        directory.
+        """
+        return self._output_path / path
+
+    @QtCore.Slot()
+    def _selection_changed(
+        self,
+        current: Optional[QtWidgets.QTreeWidgetItem],
+        previous: Optional[QtWidgets.QTreeWidgetItem] = None,
+    ) -> None:
+        del current, previous  # unused
+        path = self._selected_path()
+        self._open_folder_button.setEnabled(path is not None)
+        self._copy_path_button.setEnabled(path is not None)
+        self._table_model.clear()
+        self._error_label.setText("")
+        if path is None or not results.is_viewable(path):
+            return
+
+        try:
+            with self._output_type.new_reader(self._output_path) as reader:
+                table = results.load_result(reader, path)
+        except (OSError, filesio.Error, results.ResultError, UnicodeDecodeError) as exc:
+            self._error_label.setText(f"Could not show {path}: {exc}")
+            return
+
+        self._table_model.setHorizontalHeaderLabels(table.header)
+        for row in table.rows:
+            self._table_model.appendRow([QtGui.QStandardItem(cell) for cell in row])
+        self._table_view.resizeColumnsToContents()
+
+    @QtCore.Slot()
+    def _open_folder(self) -> None:
+        if (path := self._selected_path()) is None:
+            return
+        if self._output_type == filesio.IOType.ZIP:
+            folder = self._output_path.parent
+        else:
+            folder = self._full_path(path).parent
+        QtGui.QDesktopServices.openUrl(QtCore.QUrl.fromLocalFile(str(folder)))
+
+    @QtCore.Slot()
+    def _copy_path(self) -> None:
+        if (path := self._selected_path()) is None:
+            return
+        QtWidgets.QApplication.clipboard().setText(str(self._full_path(path)))
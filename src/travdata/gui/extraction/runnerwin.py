@@ -5,17 +5,28 @@
 # pylint: disable=I1101
 
 import pathlib
+import time
 import traceback
 from typing import Optional
 
 from PySide6 import QtCore, QtWidgets
 
-from travdata.extraction import bookextract, tableextract
+from travdata import filesio
+from travdata.extraction import bookextract, metrics, tableextract
 from travdata.gui import qtutil
+from travdata.gui.extraction import resultsview
+
+
+# Minimum interval between progress updates sent to the UI thread, in seconds.
+_PROGRESS_INTERVAL = 0.1
+# Key for persisting the window geometry.
+_GEOMETRY_KEY = "extraction_runner"
 
 
 class _WorkerSignals(QtCore.QObject):
     progress = QtCore.Signal(bookextract.Progress)
+    table_started = QtCore.Signal(bookextract.TableStarted)
+    table_finished = QtCore.Signal(bookextract.TableFinished)
     output = QtCore.Signal(pathlib.PurePath)
     error = QtCore.Signal(str)
     stopped = QtCore.Signal()
@@ -34,11 +45,26 @@ class _Worker(QtCore.QRunnable):
         self._ext_cfg = ext_cfg
         self._table_reader = table_reader
         self._continue = True
+        self.metrics = metrics.Metrics()
 
     def stop(self) -> None:
         """Stops extraction as soon as possible."""
         self._continue = False
 
+    @property
+    def stop_requested(self) -> bool:
+        """True if ``stop`` has been called."""
+        return not self._continue
+
+    def _on_event(self, event: bookextract.Event) -> None:
+        match event:
+            case bookextract.Progress():
+                self.signals.progress.emit(event)
+            case bookextract.TableStarted():
+                self.signals.table_started.emit(event)
+            case bookextract.TableFinished():
+                self.signals.table_finished.emit(event)
+
     @QtCore.Slot()
     def run(self) -> None:
         """Runs the extraction."""
@@ -49,8 +75,14 @@ class _Worker(QtCore.QRunnable):
                 events=bookextract.ExtractEvents(
                     on_error=self.signals.error.emit,
                     on_output=self.signals.output.emit,
-                    on_progress=self.signals.progress.emit,
                     do_continue=lambda: self._continue,
+                    observers=[
+                        bookextract.RateLimited(
+                            self._on_event,
+                            min_interval=_PROGRESS_INTERVAL,
+                        ),
+                        self.metrics,
+                    ],
                 ),
             )
         except Exception:  # pylint: disable=broad-exception-caught
@@ -64,6 +96,9 @@ class ExtractionRunnerWindow(QtWidgets.QWidget):
     """Window to manage extraction from PDF."""
 
     closing = QtCore.Signal()
+    # Emitted when the extraction ends, with an outcome of "complete" or
+    # "stopped", the duration in seconds, and the run's metrics.
+    ended = QtCore.Signal(str, float, metrics.Metrics)
 
     _worker: Optional[_Worker]
 
@@ -72,13 +107,26 @@ class ExtractionRunnerWindow(QtWidgets.QWidget):
         cfg: bookextract.ExtractionConfig,
         thread_pool: QtCore.QThreadPool,
         table_reader: tableextract.TableReader,
+        output_path: pathlib.Path,
+        output_type: filesio.IOType,
         *args,
         **kwargs,
     ) -> None:
+        """Initialise the ExtractionRunnerWindow.
+
+        :param cfg: Extraction to run.
+        :param thread_pool: Pool to run the extraction in.
+        :param table_reader: Reads tables from the PDF.
+        :param output_path: Path that ``cfg`` writes to, for browsing results.
+        :param output_type: How ``cfg`` writes to ``output_path``.
+        """
         super().__init__(*args, **kwargs)
         self.setWindowTitle("Travdata Extraction")
 
         self._worker = None
+        self._start_time = 0.0
+        # Errors reported since the current table started.
+        self._table_errors: list[str] = []
 
         self._cfg = cfg
         self._thread_pool = thread_pool
@@ -86,28 +134,42 @@ class ExtractionRunnerWindow(QtWidgets.QWidget):
 
         self._output_text_area = QtWidgets.QPlainTextEdit()
         self._output_text_area.setReadOnly(True)
+        qtutil.set_accessible(self._output_text_area, "Extraction log")
 
         self._progress_bar = QtWidgets.QProgressBar()
         self._progress_bar.setMinimum(0)
+        qtutil.set_accessible(self._progress_bar, "Extraction progress")
 
         self._cancel_button = QtWidgets.QPushButton("Cancel")
         self._cancel_button.clicked.connect(self._cancel)
+        qtutil.set_accessible(self._cancel_button, "Cancel extraction")
 
-        contents = qtutil.make_group_vbox(
+        progress = qtutil.make_group_vbox(
             "Extraction progress",
             self._output_text_area,
             self._progress_bar,
             self._cancel_button,
         )
 
+        self._results_view = resultsview.ExtractionResultsView(output_path, output_type)
+
+        self._tabs = QtWidgets.QTabWidget()
+        self._tabs.addTab(progress, "Progress")
+        self._tabs.addTab(self._results_view, "Results")
+
         layout = QtWidgets.QStackedLayout()
-        layout.addWidget(contents)
+        layout.addWidget(self._tabs)
         self.setLayout(layout)
 
+        qtutil.restore_geometry(self, _GEOMETRY_KEY)
+
     def start_extraction(self) -> None:
         """Starts the extraction."""
         self._worker = _Worker(self._cfg, self._table_reader)
+        self._start_time = time.monotonic()
         self._worker.signals.progress.connect(self._progress)
+        self._worker.signals.table_started.connect(self._table_started)
+        self._worker.signals.table_finished.connect(self._table_finished)
         self._worker.signals.output.connect(self._on_output)
         self._worker.signals.error.connect(self._error)
         self._worker.signals.finished.connect(self._finished)
@@ -119,11 +181,11 @@ class ExtractionRunnerWindow(QtWidgets.QWidget):
         if self._worker is None:
             return
         self._worker.stop()
-        self._worker = None
         self._cancel_button.setEnabled(False)
 
     def closeEvent(self, event) -> None:  # pylint: disable=invalid-name
         """Captures event of window closing."""
+        qtutil.save_geometry(self, _GEOMETRY_KEY)
         self.stop_extraction()
         self.closing.emit()
         super().closeEvent(event)
@@ -139,20 +201,42 @@ class ExtractionRunnerWindow(QtWidgets.QWidget):
             self._progress_bar.setMaximum(progress.total)
         self._progress_bar.setValue(progress.completed)
 
+    @QtCore.Slot()
+    def _table_started(self, event: bookextract.TableStarted) -> None:
+        del event  # unused
+        self._table_errors = []
+
+    @QtCore.Slot()
+    def _table_finished(self, event: bookextract.TableFinished) -> None:
+        self._results_view.set_table_outcome(event, self._table_errors)
+        self._table_errors = []
+
     @QtCore.Slot()
     def _on_output(self, path: pathlib.PurePath) -> None:
         self._output_text_area.appendPlainText(f"Output {path}")
+        self._results_view.add_output(path)
 
     @QtCore.Slot()
     def _error(self, error: str) -> None:
         self._output_text_area.appendPlainText(error)
+        self._table_errors.append(error)
 
     @QtCore.Slot()
     def _finished(self) -> None:
         self._output_text_area.appendPlainText("Complete.")
         self._cancel_button.setEnabled(False)
+        self._ended("complete")
 
     @QtCore.Slot()
     def _stopped(self) -> None:
         self._output_text_area.appendPlainText("Stopped.")
         self._cancel_button.setEnabled(False)
+        self._ended("stopped")
+
+    def _ended(self, outcome: str) -> None:
+        if self._worker is None:
+            return
+        if self._worker.stop_requested:
+            outcome = "stopped"
+        self.ended.emit(outcome, time.monotonic() - self._start_time, self._worker.metrics)
+        self._worker = None
@@ -4,31 +4,56 @@
 # Pylint doesn't like QT much.
 # pylint: disable=I1101
 
+import signal
 import sys
 
 from PySide6 import QtCore, QtWidgets
 
 from travdata import config
 from travdata.extraction import tabulautil
+from travdata.gui import qtutil
 from travdata.gui.extraction import cfgwin
 
 
+# Interval at which to check for signals while the Qt event loop runs.
+_SIGNAL_POLL_MS = 250
+
+
 def main() -> None:
     """Entry point for GUI program to extract data from a PDF."""
+    qtutil.apply_ui_scale()
     app = QtWidgets.QApplication(sys.argv)
     app.setQuitOnLastWindowClosed(True)
-
+    font_scaler = qtutil.FontScaler(app)
+
+    # Closing the windows on SIGINT or SIGTERM stops any running extraction
+    # between tables, rather than killing it part way through writing.
+    for signum in (signal.SIGINT, signal.SIGTERM):
+        signal.signal(signum, lambda signum, frame: app.closeAllWindows())
+    # Python signal handlers only run when the interpreter has control, so
+    # wake up periodically from the Qt event loop to let them.
+    signal_timer = QtCore.QTimer()
+    signal_timer.timeout.connect(lambda: None)
+    signal_timer.start(_SIGNAL_POLL_MS)
+
+    thread_pool = QtCore.QThreadPool()
     with tabulautil.TabulaClient(
         force_subprocess=False,
     ) as tabula_client:
         window = cfgwin.ExtractionConfigWindow(
-            thread_pool=QtCore.QThreadPool(),
+            thread_pool=thread_pool,
             table_reader=tabula_client,
             default_config_path=config.get_default_config_path(),
+            font_scaler=font_scaler,
         )
 
         window.show()
-        sys.exit(app.exec())
+        exit_code = app.exec()
+        # Let a stopping extraction finish its current table and close its
+        # output before shutting down Tabula.
+        thread_pool.waitForDone()
+
+    sys.exit(exit_code)
 
 
 if __name__ == "__main__":
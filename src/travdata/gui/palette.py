@@ -0,0 +1,84 @@
+# -*- coding: utf-8 -*-
+"""Commands for the command palette, and fuzzy matching of them."""
+
+import dataclasses
+from typing import Callable, Iterable, Optional
+
+
+# Score adjustments for each character of the query that is matched.
+_MATCH_SCORE = 1
+_CONSECUTIVE_BONUS = 5
+_WORD_START_BONUS = 3
+
+
+@dataclasses.dataclass(frozen=True)
+class Command:
+    """A command that can be run from the command palette.
+
+    :field label: Text shown in the palette, and matched against what the user
+    types, such as "Project > Open project".
+    :field run: Called when the user chooses the command.
+    """
+
+    label: str
+    run: Callable[[], None]
+
+
+def strip_mnemonic(text: str) -> str:
+    """Returns the text of a menu item, without its ``&`` mnemonic markers.
+
+    :param text: Text of the menu item, where ``&&`` is a literal ``&``.
+    :return: Text as displayed.
+    """
+    return "&".join(part.replace("&", "") for part in text.split("&&"))
+
+
+def match_score(query: str, text: str) -> Optional[int]:
+    """Scores how well ``query`` matches ``text``.
+
+    The characters of ``query`` must appear in ``text`` in the same order, but
+    not necessarily next to each other. Matching ignores case, and whitespace in
+    ``query``. Runs of consecutive characters, and characters at the start of
+    words, score more, while characters skipped over in ``text`` score less.
+
+    :param query: Text typed by the user.
+    :param text: Text to match against.
+    :return: Score, where higher is a better match, or None if ``query`` does
+    not match.
+    """
+    folded = text.casefold()
+    score = 0
+    pos = 0
+    prev = -2
+    for char in query.casefold():
+        if char.isspace():
+            continue
+        index = folded.find(char, pos)
+        if index < 0:
+            return None
+        score += _MATCH_SCORE - (index - pos)
+        if index == prev + 1:
+            score += _CONSECUTIVE_BONUS
+        if index == 0 or not folded[index - 1].isalnum():
+            score += _WORD_START_BONUS
+        prev = index
+        pos = index + 1
+    return score
+
+
+def rank(query: str, commands: Iterable[Command]) -> list[Command]:
+    """Returns the commands that match ``query``, best first.
+
+    Commands that match equally well keep their given order.
+
+    :param query: Text typed by the user.
+    :param commands: Commands to choose from.
+    :return: Matching commands.
+    """
+    scored: list[tuple[int, int, Command]] = []
+    for i, command in enumerate(commands):
+        score = match_score(query, command.label)
+        if score is not None:
+            scored.append((-score, i, command))
+    scored.sort(key=lambda item: item[:2])
+    return [command for _, _, command in scored]
@@ -0,0 +1,129 @@
+# -*- coding: utf-8 -*-
+"""Defines a dialog that runs a command chosen by typing part of its name."""
+
+# Pylint doesn't like QT much.
+# pylint: disable=I1101
+
+from typing import Iterable, Optional
+
+from PySide6 import QtCore, QtGui, QtWidgets
+
+from travdata.gui import palette, qtutil
+
+
+# Keys in the query box that move the selection in the list of commands.
+_NAVIGATION_KEYS = frozenset(
+    [
+        QtCore.Qt.Key.Key_Up,
+        QtCore.Qt.Key.Key_Down,
+        QtCore.Qt.Key.Key_PageUp,
+        QtCore.Qt.Key.Key_PageDown,
+    ]
+)
+
+
+def menu_commands(
+    menu_bar: QtWidgets.QMenuBar,
+    exclude: Iterable[QtGui.QAction] = (),
+) -> list[palette.Command]:
+    """Returns commands for the enabled actions in the menus of ``menu_bar``.
+
+    :param menu_bar: Menu bar to collect actions from, including submenus.
+    :param exclude: Actions to leave out, such as the one that opens the
+    palette.
+    :return: Commands, labelled with the path of menus to each action.
+    """
+    excluded = set(exclude)
+    commands: list[palette.Command] = []
+
+    def collect(actions: Iterable[QtGui.QAction], path: list[str]) -> None:
+        for action in actions:
+            if action.isSeparator() or not action.isEnabled() or action in excluded:
+                continue
+            text = palette.strip_mnemonic(action.text()).removesuffix("...")
+            if (menu := action.menu()) is not None:
+                collect(menu.actions(), path + [text])
+                continue
+            commands.append(palette.Command(" > ".join(path + [text]), action.trigger))
+
+    collect(menu_bar.actions(), [])
+    return commands
+
+
+class CommandPalette(QtWidgets.QDialog):
+    """Dialog listing commands that match what the user types.
+
+    Enter runs the selected command, and the arrow keys move the selection
+    while typing.
+    """
+
+    def __init__(
+        self,
+        commands: list[palette.Command],
+        parent: Optional[QtWidgets.QWidget] = None,
+    ) -> None:
+        """Initialise the CommandPalette.
+
+        :param commands: Commands to choose from, in the order to list them
+        before anything is typed.
+        :param parent: Parent window.
+        """
+        super().__init__(parent)
+        self.setWindowTitle("Command palette")
+
+        self._commands = commands
+        self._matches: list[palette.Command] = []
+
+        self._query_edit = QtWidgets.QLineEdit()
+        self._query_edit.setPlaceholderText("Type to search commands...")
+        self._query_edit.textChanged.connect(self._refresh)
+        self._query_edit.returnPressed.connect(self._run_selected)
+        self._query_edit.installEventFilter(self)
+        qtutil.set_accessible(self._query_edit, "Search commands")
+
+        self._commands_list = QtWidgets.QListWidget()
+        self._commands_list.itemActivated.connect(self._run_selected)
+        qtutil.set_accessible(self._commands_list, "Matching commands")
+
+        layout = QtWidgets.QVBoxLayout(self)
+        layout.addWidget(self._query_edit)
+        layout.addWidget(self._commands_list)
+
+        self._refresh("")
+        self.resize(500, 300)
+
+    def eventFilter(  # pylint: disable=invalid-name
+        self,
+        watched: QtCore.QObject,
+        event: QtCore.QEvent,
+    ) -> bool:
+        """Forwards navigation keys from the query box to the list of commands."""
+        if (
+            watched is self._query_edit
+            and isinstance(event, QtGui.QKeyEvent)
+            and event.type() == QtCore.QEvent.Type.KeyPress
+            and event.key() in _NAVIGATION_KEYS
+        ):
+            QtWidgets.QApplication.sendEvent(self._commands_list, event)
+            return True
+        return super().eventFilter(watched, event)
+
+    @QtCore.Slot(str)
+    def _refresh(self, query: str) -> None:
+        self._matches = palette.rank(query, self._commands)
+        self._commands_list.clear()
+        for command in self._matches:
+            self._commands_list.addItem(command.label)
+        if self._matches:
+            self._commands_list.setCurrentRow(0)
+
+    @QtCore.Slot()
+    def _run_selected(self) -> None:
+        row = self._commands_list.currentRow()
+        if not 0 <= row < len(self._matches):
+            return
+        command = self._matches[row]
+        # Closes first, so that any window opened by the command is not
+        # behind the palette.
+        self.accept()
+        command.run()
@@ -4,9 +4,11 @@
 # Pylint doesn't like QT much.
 # pylint: disable=I1101
 
+import os
+import pathlib
 from typing import Iterable, Optional, TypeAlias
 
-from PySide6 import QtWidgets
+from PySide6 import QtCore, QtGui, QtWidgets
 
 
 LayoutItem: TypeAlias = QtWidgets.QWidget | QtWidgets.QSpacerItem
@@ -17,6 +19,263 @@ QLabel { color: red; }
 """
 
 
+def state_settings() -> QtCore.QSettings:
+    """Returns settings used to persist GUI state between sessions.
+
+    These are stored in the XDG state directory, rather than alongside user
+    configuration, as they are not something that the user chose.
+    """
+    state_home = os.environ.get("XDG_STATE_HOME") or pathlib.Path.home() / ".local" / "state"
+    path = pathlib.Path(state_home) / "travdata" / "gui-state.ini"
+    return QtCore.QSettings(str(path), QtCore.QSettings.Format.IniFormat)
+
+
+def restore_geometry(widget: QtWidgets.QWidget, key: str) -> None:
+    """Restores the size and position of the window, if previously saved.
+
+    :param widget: Window to restore.
+    :param key: Key that ``save_geometry`` was called with for the window.
+    """
+    geometry = state_settings().value(f"{key}/geometry")
+    if isinstance(geometry, QtCore.QByteArray):
+        widget.restoreGeometry(geometry)
+    fit_to_screen(widget)
+
+
+def fit_to_screen(widget: QtWidgets.QWidget) -> None:
+    """Shrinks and moves the window to fit within the available screen area.
+
+    Saved geometry might come from a larger screen, or the window's preferred
+    size might be larger than a small laptop screen.
+
+    :param widget: Window to fit.
+    """
+    screen = widget.screen() or QtGui.QGuiApplication.primaryScreen()
+    if screen is None:
+        return
+    available = screen.availableGeometry()
+    frame = widget.frameGeometry()
+    # Allows for the window frame, which is not part of the window's size.
+    frame_width = frame.width() - widget.width()
+    frame_height = frame.height() - widget.height()
+    width = min(widget.width(), available.width() - frame_width)
+    height = min(widget.height(), available.height() - frame_height)
+    if (width, height) != (widget.width(), widget.height()):
+        widget.resize(width, height)
+    x = min(max(frame.x(), available.left()), available.right() + 1 - width - frame_width)
+    y = min(max(frame.y(), available.top()), available.bottom() + 1 - height - frame_height)
+    if (x, y) != (frame.x(), frame.y()):
+        widget.move(x, y)
+
+
+def is_small_screen() -> bool:
+    """Returns True if the primary screen is too small for the default layouts."""
+    screen = QtGui.QGuiApplication.primaryScreen()
+    if screen is None:
+        return False
+    available = screen.availableSize()
+    return available.width() < _SMALL_SCREEN_WIDTH or available.height() < _SMALL_SCREEN_HEIGHT
+
+
+def save_geometry(widget: QtWidgets.QWidget, key: str) -> None:
+    """Saves the size and position of the window.
+
+    :param widget: Window to save.
+    :param key: Unique key for the window.
+    """
+    state_settings().setValue(f"{key}/geometry", widget.saveGeometry())
+
+
+def preference_settings() -> QtCore.QSettings:
+    """Returns settings for preferences that the user chose, such as text size.
+
+    These are stored in the XDG config directory.
+    """
+    config_home = os.environ.get("XDG_CONFIG_HOME") or pathlib.Path.home() / ".config"
+    path = pathlib.Path(config_home) / "travdata" / "gui.ini"
+    return QtCore.QSettings(str(path), QtCore.QSettings.Format.IniFormat)
+
+
+_UI_SCALE_KEY = "appearance/ui_scale"
+_FONT_SCALE_KEY = "appearance/font_scale"
+_COMPACT_LAYOUT_KEY = "appearance/compact_layout"
+
+# Screens with less available space than this, in device independent pixels,
+# default to the compact layout. This is below 1366x768 once taskbars and
+# window frames are allowed for.
+_SMALL_SCREEN_WIDTH = 1366
+_SMALL_SCREEN_HEIGHT = 800
+
+# Choices of UI scale, as multiples of the scale chosen by the platform.
+UI_SCALES = (1.0, 1.25, 1.5, 1.75, 2.0)
+
+
+def _float_preference(key: str, default: float) -> float:
+    value = preference_settings().value(key)
+    if value is None:
+        return default
+    try:
+        return float(value)
+    except (TypeError, ValueError):
+        return default
+
+
+def ui_scale() -> float:
+    """Returns the saved UI scale."""
+    return _float_preference(_UI_SCALE_KEY, 1.0)
+
+
+def save_ui_scale(scale: float) -> None:
+    """Saves the UI scale, which is applied by ``apply_ui_scale`` on next start."""
+    preference_settings().setValue(_UI_SCALE_KEY, scale)
+
+
+def compact_layout() -> bool:
+    """Returns True if windows should use their compact layout.
+
+    This defaults to True on small screens, unless the user chose otherwise.
+    """
+    value = preference_settings().value(_COMPACT_LAYOUT_KEY)
+    if value is None:
+        return is_small_screen()
+    return str(value).lower() in ("true", "1")
+
+
+def save_compact_layout(compact: bool) -> None:
+    """Saves whether windows should use their compact layout."""
+    preference_settings().setValue(_COMPACT_LAYOUT_KEY, compact)
+
+
+_NO_OVERWRITE_CONFIRM_KEY = "extraction/no_overwrite_confirm"
+
+
+def _no_overwrite_confirm_paths() -> list[str]:
+    value = preference_settings().value(_NO_OVERWRITE_CONFIRM_KEY, [], type=list)
+    return [str(item) for item in value]
+
+
+def wants_overwrite_confirmation(output_path: pathlib.Path) -> bool:
+    """Returns True unless the user chose not to confirm overwrites in the output."""
+    return str(output_path.absolute()) not in _no_overwrite_confirm_paths()
+
+
+def skip_overwrite_confirmation(output_path: pathlib.Path) -> None:
+    """Saves that the user does not want to confirm overwrites in the output."""
+    paths = _no_overwrite_confirm_paths()
+    path = str(output_path.absolute())
+    if path not in paths:
+        preference_settings().setValue(_NO_OVERWRITE_CONFIRM_KEY, paths + [path])
+
+
+def apply_ui_scale() -> None:
+    """Applies the saved UI scale.
+
+    Qt only reads the scale at startup, so this must be called before the
+    ``QApplication`` is created. A ``QT_SCALE_FACTOR`` already set in the
+    environment takes precedence.
+    """
+    scale = ui_scale()
+    if scale != 1.0:
+        os.environ.setdefault("QT_SCALE_FACTOR", str(scale))
+
+
+class FontScaler:
+    """Scales the application's text relative to its default size.
+
+    The chosen scale is saved, and restored on creation.
+    """
+
+    _MIN_SCALE = 0.5
+    _MAX_SCALE = 3.0
+    _STEP = 0.1
+
+    def __init__(self, app: QtWidgets.QApplication) -> None:
+        self._app = app
+        self._default_font = QtGui.QFont(app.font())
+        self._scale = 1.0
+        self._apply(_float_preference(_FONT_SCALE_KEY, 1.0))
+
+    @property
+    def scale(self) -> float:
+        """Current scale of text."""
+        return self._scale
+
+    def set_scale(self, scale: float) -> None:
+        """Sets and saves the scale of text."""
+        self._apply(scale)
+        preference_settings().setValue(_FONT_SCALE_KEY, self._scale)
+
+    def larger(self) -> None:
+        """Increases the size of text by one step."""
+        self.set_scale(self._scale + self._STEP)
+
+    def smaller(self) -> None:
+        """Decreases the size of text by one step."""
+        self.set_scale(self._scale - self._STEP)
+
+    def reset(self) -> None:
+        """Returns text to its default size."""
+        self.set_scale(1.0)
+
+    def _apply(self, scale: float) -> None:
+        self._scale = round(min(max(scale, self._MIN_SCALE), self._MAX_SCALE), 2)
+        font = QtGui.QFont(self._default_font)
+        if self._default_font.pointSizeF() > 0:
+            font.setPointSizeF(self._default_font.pointSizeF() * self._scale)
+        else:
+            font.setPixelSize(round(self._default_font.pixelSize() * self._scale))
+        self._app.setFont(font)
+
+
+def make_scroll_area(widget: QtWidgets.QWidget) -> QtWidgets.QScrollArea:
+    """Wraps a widget in a frameless area that scrolls when the window is too small.
+
+    :param widget: Widget to scroll.
+    :return: Scroll area containing the widget.
+    """
+    scroll_area = QtWidgets.QScrollArea()
+    scroll_area.setWidgetResizable(True)
+    scroll_area.setFrameShape(QtWidgets.QFrame.Shape.NoFrame)
+    scroll_area.setWidget(widget)
+    return scroll_area
+
+
+def set_accessible(
+    widget: QtWidgets.QWidget,
+    name: str,
+    description: Optional[str] = None,
+) -> None:
+    """Sets the name (and optionally description) announced by screen readers.
+
+    This is needed where a widget's visible text is ambiguous out of context,
+    such as several buttons labelled "Select ZIP".
+    """
+    widget.setAccessibleName(name)
+    if description is not None:
+        widget.setAccessibleDescription(description)
+
+
+def make_value_label(name: str) -> QtWidgets.QLabel:
+    """Creates a label to display a value, reachable by keyboard focus.
+
+    :param name: Accessible name of the value.
+    :return: Label.
+    """
+    label = QtWidgets.QLabel("")
+    label.setTextInteractionFlags(
+        QtCore.Qt.TextInteractionFlag.TextSelectableByMouse
+        | QtCore.Qt.TextInteractionFlag.TextSelectableByKeyboard
+    )
+    set_accessible(label, name)
+    return label
+
+
+def set_tab_order(*widgets: QtWidgets.QWidget) -> None:
+    """Sets the keyboard focus order of the widgets to the order given."""
+    for first, second in zip(widgets, widgets[1:]):
+        QtWidgets.QWidget.setTabOrder(first, second)
+
+
 def set_error_style(*items: QtWidgets.QWidget) -> None:
     """Sets an error indicator style to the given widgets."""
     for item in items:
@@ -0,0 +1,301 @@
+# -*- coding: utf-8 -*-
+"""Project files, which record a complete extraction setup.
+
+A project file (conventionally with a ``.travdata`` suffix) is a YAML document
+containing a single ``!Project`` that records everything needed to repeat an
+extraction: the configuration to use, the input PDF and the book within the
+configuration that it corresponds to, where to write the output, and which
+tables to extract.
+
+The book ID and paths may refer to environment variables, as described in
+``envinterp``.
+
+Relative paths are relative to the project's root directory, which defaults to
+the directory containing the project file. Projects saved with ``relativised``
+store paths within the root as relative paths, so that the root can be moved or
+synced between machines.
+"""
+
+import dataclasses
+import hashlib
+import os
+import pathlib
+from typing import Any, ClassVar, Optional, Self, TypeVar
+
+from travdata import config, csvutil, envinterp, filesio, yamlutil
+from travdata.config import cfgerror, yamlreg
+from travdata.extraction import bookextract
+
+
+PROJECT_SUFFIX = ".travdata"
+
+_T = TypeVar("_T", str, pathlib.Path, Optional[pathlib.Path])
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class Project(yamlutil.YamlMappingMixin):
+    """Complete extraction setup.
+
+    :field book_id: ID of the book within the configuration.
+    :field input_pdf: Path to the PDF file to extract from.
+    :field output_path: Path to the directory or ZIP file to write to.
+    :field config_path: Path to the configuration. If unset, the default
+    configuration is used.
+    :field output_type: How to write to ``output_path``.
+    :field overwrite_existing: If true, overwrite existing CSV files.
+    :field with_tags: Only extracts tables that have any of these these tags.
+    :field without_tags: Only extracts tables that do not include any of these
+    tags (takes precedence over with_tags).
+    :field root: Directory that relative paths are relative to. If this is
+    itself relative, then it is relative to the directory containing the
+    project file, which is also the default.
+    :field input_pdf_sha256: Hex SHA-256 checksum of the input PDF. If set, and
+    the input PDF is not found at its path, then a PDF with the same checksum
+    is searched for within the root.
+    :field csv_encoding: Encoding of the CSV files written.
+    """
+
+    # pylint: disable=too-many-instance-attributes
+
+    yaml_tag: ClassVar = "!Project"
+    yaml_format_version: ClassVar = 1
+    book_id: str
+    input_pdf: pathlib.Path = dataclasses.field(metadata=yamlutil.PATH_METADATA)
+    output_path: pathlib.Path = dataclasses.field(metadata=yamlutil.PATH_METADATA)
+    config_path: Optional[pathlib.Path] = dataclasses.field(
+        default=None, metadata=yamlutil.PATH_METADATA
+    )
+    output_type: filesio.IOType = dataclasses.field(
+        default=filesio.IOType.AUTO,
+        metadata={yamlutil.TO_YAML: str, yamlutil.FROM_YAML: filesio.IOType},
+    )
+    overwrite_existing: bool = dataclasses.field(
+        default=False, metadata=yamlutil.BOOL_METADATA
+    )
+    with_tags: set[str] = dataclasses.field(default_factory=set, metadata=yamlutil.SET_METADATA)
+    without_tags: set[str] = dataclasses.field(
+        default_factory=set, metadata=yamlutil.SET_METADATA
+    )
+    root: Optional[pathlib.Path] = dataclasses.field(
+        default=None, metadata=yamlutil.PATH_METADATA
+    )
+    input_pdf_sha256: Optional[str] = None
+    csv_encoding: csvutil.CsvEncoding = dataclasses.field(
+        default=csvutil.CsvEncoding.UTF8,
+        metadata={yamlutil.TO_YAML: str, yamlutil.FROM_YAML: csvutil.CsvEncoding},
+    )
+
+    @classmethod
+    def yaml_create_empty(cls) -> Self:
+        return cls(
+            book_id="",
+            input_pdf=pathlib.Path(),
+            output_path=pathlib.Path(),
+        )
+
+    def interpolated(self, interpolator: envinterp.Interpolator) -> "Project":
+        """Returns a copy of the project with environment variables interpolated.
+
+        Variables are interpolated into ``book_id`` and the paths.
+
+        :param interpolator: Interpolator to use, which records the
+        substitutions made.
+        :raises cfgerror.ConfigurationError: If a referenced variable is unset
+        and has no default.
+        :return: Interpolated project.
+        """
+
+        def interp_path(path: pathlib.Path) -> pathlib.Path:
+            return pathlib.Path(interpolator(str(path)))
+
+        return dataclasses.replace(
+            self,
+            book_id=interpolator(self.book_id),
+            input_pdf=interp_path(self.input_pdf),
+            output_path=interp_path(self.output_path),
+            config_path=None if self.config_path is None else interp_path(self.config_path),
+        )
+
+    def uninterpolated(self, source: "Project", expanded: "Project") -> "Project":
+        """Returns a copy of the project with environment variable references restored.
+
+        Each of ``book_id`` and the paths that still has its value from
+        ``expanded``, and that refers to environment variables in ``source``,
+        is restored to its value in ``source``. This avoids saving this
+        machine's values in place of the references.
+
+        This should be called before ``relativised``, if used.
+
+        :param source: Project as loaded, before ``interpolated``.
+        :param expanded: ``source`` after ``interpolated`` and ``resolved``.
+        :return: Project with unchanged references restored.
+        """
+
+        def restore(value: _T, source_value: _T, expanded_value: _T) -> _T:
+            if (
+                source_value is not None
+                and value == expanded_value
+                and envinterp.has_references(str(source_value))
+            ):
+                return source_value
+            return value
+
+        input_pdf = restore(self.input_pdf, source.input_pdf, expanded.input_pdf)
+        input_pdf_sha256 = self.input_pdf_sha256
+        if input_pdf != self.input_pdf:
+            # relativised cannot find the PDF from the reference.
+            input_pdf_sha256 = (
+                _file_sha256(self.input_pdf)
+                if self.input_pdf.is_file()
+                else source.input_pdf_sha256
+            )
+        return dataclasses.replace(
+            self,
+            book_id=restore(self.book_id, source.book_id, expanded.book_id),
+            input_pdf=input_pdf,
+            output_path=restore(self.output_path, source.output_path, expanded.output_path),
+            config_path=restore(self.config_path, source.config_path, expanded.config_path),
+            input_pdf_sha256=input_pdf_sha256,
+        )
+
+    def root_dir(self, project_dir: pathlib.Path) -> pathlib.Path:
+        """Returns the directory that relative paths are relative to.
+
+        :param project_dir: Directory containing the project file.
+        :return: Root directory.
+        """
+        if self.root is None:
+            return project_dir
+        # Normalised so that paths within the root can be recognised.
+        return pathlib.Path(os.path.normpath(project_dir / self.root))
+
+    def resolved(self, project_dir: pathlib.Path) -> "Project":
+        """Returns a copy of the project with relative paths resolved.
+
+        This should be called after ``interpolated``, if used.
+
+        :param project_dir: Directory containing the project file.
+        :return: Project with paths relative to the root made absolute, and the
+        input PDF located by its checksum if it has moved.
+        """
+        root = self.root_dir(project_dir)
+        input_pdf = root / self.input_pdf
+        if not input_pdf.exists() and self.input_pdf_sha256:
+            input_pdf = _find_file_by_checksum(root, self.input_pdf_sha256) or input_pdf
+        return dataclasses.replace(
+            self,
+            input_pdf=input_pdf,
+            output_path=root / self.output_path,
+            config_path=None if self.config_path is None else root / self.config_path,
+        )
+
+    def relativised(self, project_dir: pathlib.Path) -> "Project":
+        """Returns a copy of the project suitable for saving in ``project_dir``.
+
+        :param project_dir: Directory that the project file will be saved in.
+        :return: Project with paths within the root made relative to it, and
+        the checksum of the input PDF recorded (if the PDF exists).
+        """
+        root = self.root_dir(project_dir)
+
+        def relative(path: pathlib.Path) -> pathlib.Path:
+            if path.is_absolute() and path.is_relative_to(root):
+                return path.relative_to(root)
+            return path
+
+        input_pdf_sha256 = self.input_pdf_sha256
+        if self.input_pdf.is_file():
+            input_pdf_sha256 = _file_sha256(self.input_pdf)
+        return dataclasses.replace(
+            self,
+            input_pdf=relative(self.input_pdf),
+            output_path=relative(self.output_path),
+            config_path=None if self.config_path is None else relative(self.config_path),
+            input_pdf_sha256=input_pdf_sha256,
+        )
+
+    def resolved_config_path(self) -> pathlib.Path:
+        """Returns the path to the configuration to use.
+
+        :raises cfgerror.ConfigurationError: If the project does not specify a
+        configuration, and there is no default configuration.
+        :return: Path to the configuration.
+        """
+        if self.config_path is not None:
+            return self.config_path
+        if (default_path := config.get_default_config_path()) is not None:
+            return default_path
+        raise cfgerror.ConfigurationError(
+            "Project does not specify a configuration, and no default configuration was found."
+        )
+
+    def extraction_config(
+        self,
+        symlink_policy: filesio.SymlinkPolicy = filesio.SymlinkPolicy.FOLLOW,
+    ) -> bookextract.ExtractionConfig:
+        """Creates the extraction configuration described by the project.
+
+        :param symlink_policy: How to treat symlinks when the output is a
+        directory.
+        :return: Extraction configuration.
+        """
+        config_path = self.resolved_config_path()
+        output_type = self.output_type.resolve_auto(self.output_path)
+        return bookextract.ExtractionConfig(
+            cfg_reader_ctx=filesio.new_reader(config_path),
+            out_writer_ctx=output_type.new_read_writer(self.output_path, symlink_policy),
+            input_pdf=self.input_pdf,
+            book_id=self.book_id,
+            overwrite_existing=self.overwrite_existing,
+            with_tags=frozenset(self.with_tags),
+            without_tags=frozenset(self.without_tags),
+            csv_encoding=self.csv_encoding,
+        )
+
+
+def _file_sha256(path: pathlib.Path) -> str:
+    digest = hashlib.sha256()
+    with path.open("rb") as f:
+        while data := f.read(1024 * 1024):
+            digest.update(data)
+    return digest.hexdigest()
+
+
+def _find_file_by_checksum(root: pathlib.Path, sha256: str) -> Optional[pathlib.Path]:
+    """Returns the first PDF within ``root`` with the given checksum, if any."""
+    if not root.is_dir():
+        return None
+    for path in sorted(root.rglob("*")):
+        if path.suffix.lower() == ".pdf" and path.is_file() and _file_sha256(path) == sha256:
+            return path
+    return None
+
+
+def _check_project(project: Any | Project) -> Project:
+    if not isinstance(project, Project):
+        raise cfgerror.ConfigurationError(
+            f"Expected a {Project.yaml_tag} in project file, got {type(project).__name__}."
+        )
+    return project
+
+
+def load_project(path: pathlib.Path) -> Project:
+    """Loads a project file.
+
+    :param path: Path to the project file.
+    :raises cfgerror.ConfigurationError: If the file does not contain a project.
+    :return: Loaded project.
+    """
+    with path.open("rt", encoding="utf-8") as f:
+        return _check_project(yamlreg.YAML.load(f))
+
+
+def save_project(path: pathlib.Path, project: Project) -> None:
+    """Saves a project file.
+
+    :param path: Path to the project file to write.
+    :param project: Project to save.
+    """
+    with path.open("wt", encoding="utf-8") as f:
+        yamlreg.YAML.dump(project, f)
@@ -4,10 +4,12 @@
 from __future__ import annotations
 
 import dataclasses
-from typing import Any, ClassVar, Iterator, Self, TypeVar, cast, TYPE_CHECKING
+import pathlib
+import warnings
+from typing import Any, ClassVar, Iterator, Optional, Self, TypeVar, cast, TYPE_CHECKING
 
 from ruamel import yaml
-from travdata import dataclassutil
+from travdata import dataclassutil, travdatarelease
 from travdata.config import cfgerror
 
 _T = TypeVar("_T")
@@ -17,6 +19,22 @@ _T = TypeVar("_T")
 YAML_NAME = "yaml"  # Override the name of the field in a YAML mapping.
 TO_YAML = "to_yaml"  # Callable to convert to YAML value.
 FROM_YAML = "from_yaml"  # Callable to convert from YAML value.
+# Former names of the field in a YAML mapping, which are accepted with a
+# ``DeprecatedFieldWarning``.
+RENAMED_FROM = "renamed_from"
+
+
+# Keys written into the YAML mappings of types that set ``yaml_format_version``.
+FORMAT_VERSION_KEY = "format_version"
+WRITTEN_BY_KEY = "written_by"
+
+
+class DeprecatedFieldWarning(UserWarning):
+    """Warns that YAML uses a deprecated field name, which ``travdata_cli fix`` can update."""
+
+
+class NewerFormatError(cfgerror.ConfigurationError):
+    """YAML was written by a newer version of travdata, in a format this version cannot read."""
 
 
 def parse_bool(v: bool | str) -> bool:
@@ -33,6 +51,7 @@ def parse_bool(v: bool | str) -> bool:
 
 BOOL_METADATA = {FROM_YAML: parse_bool}
 INT_METADATA = {TO_YAML: int, FROM_YAML: int}
+PATH_METADATA = {TO_YAML: str, FROM_YAML: pathlib.Path}
 SET_METADATA = {TO_YAML: sorted, FROM_YAML: set}
 
 
@@ -47,12 +66,30 @@ def _check_node_type(
 ) -> _T:
     if not isinstance(node, want_type):
         raise cfgerror.ConfigurationError(
-            f"{node.start_mark.name}:{node.start_mark.line+1}:{node.start_mark.column+1}: "
+            f"{_format_mark(node)}: "
             f"{yaml_tag} should be a {want_type.__name__}, but is {type(node).__name__}"
         )
     return node
 
 
+def _format_mark(node: Any) -> str:
+    mark = node.start_mark
+    return f"{mark.name}:{mark.line+1}:{mark.column+1}"
+
+
+def renamed_fields(cls: type) -> dict[str, str]:
+    """Returns a mapping from the former YAML names of fields in ``cls`` to their current names.
+
+    :param cls: Dataclass that uses ``YamlMappingMixin``.
+    """
+    renames: dict[str, str] = {}
+    for field in dataclasses.fields(cast(type["DataclassInstance"], cls)):
+        key = field.metadata.get(YAML_NAME, field.name)
+        for old_key in field.metadata.get(RENAMED_FROM, ()):
+            renames[old_key] = key
+    return renames
+
+
 def _yaml_field(cls: type) -> dataclasses.Field:
     fields = dataclasses.fields(cast(type["DataclassInstance"], cls))
     if len(fields) != 1:
@@ -64,6 +101,12 @@ class YamlMappingMixin:
     """Mixin for classes instantiated by parsing YAML mappings."""
 
     yaml_tag: ClassVar
+    # If set, mappings are written with this format version and the version of
+    # travdata that wrote them. Mappings with a greater format version are
+    # rejected with a ``NewerFormatError``, rather than failing on whatever
+    # changed. This should be incremented when a change would prevent older
+    # versions from reading the mapping correctly.
+    yaml_format_version: ClassVar[Optional[int]] = None
 
     @classmethod
     def yaml_create_empty(cls) -> Self:
@@ -78,7 +121,10 @@ class YamlMappingMixin:
     @classmethod
     def to_yaml(cls, representer, node):
         """Implements serialising the node as basic YAML types."""
-        mapping = {}
+        mapping: dict[str, Any] = {}
+        if cls.yaml_format_version is not None:
+            mapping[FORMAT_VERSION_KEY] = cls.yaml_format_version
+            mapping[WRITTEN_BY_KEY] = f"travdata {travdatarelease.EXECUTABLE_VERSION}"
         for field in dataclasses.fields(cast(type["DataclassInstance"], cls)):
             value = getattr(node, field.name)
             if not value and dataclassutil.has_default(field):
@@ -94,12 +140,33 @@ class YamlMappingMixin:
         """Implements deserialising the node from basic YAML types."""
         node = _check_node_type(cls.yaml_tag, node, yaml.MappingNode)
 
+        if cls.yaml_format_version is not None:
+            # Checked before construction, which could fail on content from a
+            # newer version.
+            cls._check_format_version(node)
+
         obj = cls.yaml_create_empty()
         yield obj
         data = yaml.CommentedMap()
         constructor.construct_mapping(node, maptyp=data, deep=True)
         if not isinstance(data, dict):
             raise TypeError(data)
+        key_nodes = {key_node.value: key_node for key_node, _ in node.value}
+        if cls.yaml_format_version is not None:
+            data.pop(FORMAT_VERSION_KEY, None)
+            data.pop(WRITTEN_BY_KEY, None)
+        for old_key, key in renamed_fields(cls).items():
+            if old_key not in data or key in data:
+                # If both are present, the old key is reported as unexpected.
+                continue
+            warnings.warn(
+                DeprecatedFieldWarning(
+                    f"{_format_mark(key_nodes[old_key])}: field {old_key} in {cls.yaml_tag} "
+                    f"has been renamed to {key}; run `travdata_cli fix` to update it"
+                ),
+                stacklevel=2,
+            )
+            data[key] = data.pop(old_key)
         for field in dataclasses.fields(cast(type["DataclassInstance"], cls)):
             key = field.metadata.get("yaml", field.name)
             try:
@@ -117,6 +184,33 @@ class YamlMappingMixin:
             names = ", ".join(sorted(data))
             raise TypeError(f"unexpected fields {names} in {cls.yaml_tag}")
 
+    @classmethod
+    def _check_format_version(cls, node: yaml.MappingNode) -> None:
+        """Checks that the format version of ``node`` is one that can be read.
+
+        Mappings without a format version are treated as version 1.
+        """
+        values = {
+            key_node.value: value_node.value
+            for key_node, value_node in node.value
+            if isinstance(value_node, yaml.ScalarNode)
+        }
+        written_by = values.get(WRITTEN_BY_KEY, "an unknown version of travdata")
+        try:
+            format_version = int(values.get(FORMAT_VERSION_KEY, 1))
+        except ValueError as exc:
+            raise cfgerror.ConfigurationError(
+                f"{_format_mark(node)}: {FORMAT_VERSION_KEY} in {cls.yaml_tag} should be an "
+                f"integer, but is {values[FORMAT_VERSION_KEY]!r}"
+            ) from exc
+        if cls.yaml_format_version is not None and format_version > cls.yaml_format_version:
+            raise NewerFormatError(
+                f"{_format_mark(node)}: {cls.yaml_tag} was produced by a newer travdata "
+                f"({written_by}) using format version {format_version}, but this is travdata "
+                f"{travdatarelease.EXECUTABLE_VERSION}, which supports up to format version "
+                f"{cls.yaml_format_version}; upgrade travdata to read it"
+            )
+
 
 class YamlScalarMixin:
     """Mixin for classes instantiated by parsing YAML scalars.
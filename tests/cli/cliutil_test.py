@@ -0,0 +1,30 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import signal
+
+import pytest
+
+from travdata.cli import cliutil
+
+
+@pytest.mark.parametrize(
+    "name,signum",
+    [
+        ("SIGINT", signal.SIGINT),
+        ("SIGTERM", signal.SIGTERM),
+    ],
+)
+def test_graceful_stop(name: str, signum: signal.Signals) -> None:
+    print(name)
+    previous = signal.getsignal(signum)
+    with cliutil.GracefulStop() as graceful_stop:
+        assert graceful_stop.do_continue()
+        signal.raise_signal(signum)
+        assert graceful_stop.stop_requested
+        assert not graceful_stop.do_continue()
+
+        with pytest.raises(KeyboardInterrupt):
+            signal.raise_signal(signum)
+
+    assert signal.getsignal(signum) == previous
@@ -0,0 +1,71 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import io
+import json
+import pathlib
+
+import testfixtures  # type: ignore[import-untyped]
+from travdata.cli.cmds import extractcsvtables
+from travdata.extraction import bookextract, tablestats
+
+
+def test_progress_log() -> None:
+    times = iter([0.0, 5.0, 40.0, 50.0, 60.0])
+    out = io.StringIO()
+    progress_log = extractcsvtables.ProgressLog(out, interval=30.0, clock=lambda: next(times))
+
+    progress_log(bookextract.Progress(0, 4))
+    progress_log(bookextract.Progress(1, 4))  # Within interval, so not written.
+    progress_log(bookextract.Progress(2, 4))
+    progress_log(bookextract.Progress(3, 4))  # Within interval, so not written.
+    progress_log(bookextract.Progress(4, 4))  # Final progress is always written.
+
+    testfixtures.compare(
+        expected=[
+            "Extracted 0/4 tables.",
+            "Extracted 2/4 tables, about 0:00:40 remaining.",
+            "Extracted 4/4 tables.",
+        ],
+        actual=out.getvalue().splitlines(),
+    )
+
+
+def test_json_lines_progress() -> None:
+    out = io.StringIO()
+    json_progress = extractcsvtables.JsonLinesProgress(out)
+    path = pathlib.PurePath("group/table.csv")
+    stats = tablestats.TableStats(rows=2, columns=3, empty_cells=1, touches_edge=False)
+
+    json_progress(bookextract.Progress(0, 2))
+    json_progress(bookextract.TableStarted(path))
+    json_progress.error("group/table: something odd")
+    json_progress(bookextract.TableFinished(path, success=True, duration=1.23456, stats=stats))
+    json_progress(bookextract.TableFinished(path, success=False, duration=0.5))
+    json_progress(bookextract.Progress(1, 3))
+    json_progress(bookextract.RunFinished(stopped=False))
+
+    testfixtures.compare(
+        expected=[
+            {"event": "progress", "completed": 0, "total": 2, "percent": 0.0},
+            {"event": "table_started", "path": "group/table.csv"},
+            {"event": "error", "message": "group/table: something odd"},
+            {
+                "event": "table_finished",
+                "path": "group/table.csv",
+                "success": True,
+                "duration": 1.235,
+                "stats": {"rows": 2, "columns": 3, "empty_cells": 1, "touches_edge": False},
+            },
+            {
+                "event": "table_finished",
+                "path": "group/table.csv",
+                "success": False,
+                "duration": 0.5,
+                "stats": None,
+            },
+            {"event": "progress", "completed": 1, "total": 3, "percent": 33.3},
+            {"event": "run_finished", "stopped": False},
+        ],
+        actual=[json.loads(line) for line in out.getvalue().splitlines()],
+    )
@@ -0,0 +1,104 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import dataclasses
+import pathlib
+import tempfile
+import warnings
+from typing import ClassVar
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import yamlutil
+from travdata.config import cfgfix, yamlreg
+
+
+@dataclasses.dataclass
+@yamlreg.YAML.register_class
+class _Renamed(yamlutil.YamlMappingMixin):
+    yaml_tag: ClassVar = "!TestRenamed"
+    new_name: str = dataclasses.field(
+        default="", metadata={yamlutil.RENAMED_FROM: ("old_name", "older_name")}
+    )
+    other: str = ""
+
+
+def test_load_renamed_field_warns() -> None:
+    with pytest.warns(yamlutil.DeprecatedFieldWarning, match="old_name .* renamed to new_name"):
+        actual = yamlreg.YAML.load("!TestRenamed\nother: x\nold_name: value\n")
+
+    testfixtures.compare(expected=_Renamed(new_name="value", other="x"), actual=actual)
+
+
+def test_load_current_field_does_not_warn() -> None:
+    with warnings.catch_warnings():
+        warnings.simplefilter("error")
+        actual = yamlreg.YAML.load("!TestRenamed\nnew_name: value\n")
+
+    testfixtures.compare(expected=_Renamed(new_name="value"), actual=actual)
+
+
+@pytest.mark.parametrize(
+    "name,text,expected_text,expected_renames",
+    [
+        (
+            "No renames.",
+            "!TestRenamed\nnew_name: a\n",
+            "!TestRenamed\nnew_name: a\n",
+            [],
+        ),
+        (
+            "Nested, quoted, and commented.",
+            "# Comment.\n- !TestRenamed\n  older_name: a  # Keep.\n- !TestRenamed {'old_name': b}",
+            "# Comment.\n- !TestRenamed\n  new_name: a  # Keep.\n- !TestRenamed {new_name: b}",
+            [
+                cfgfix.Rename(3, "!TestRenamed", "older_name", "new_name"),
+                cfgfix.Rename(4, "!TestRenamed", "old_name", "new_name"),
+            ],
+        ),
+        (
+            "Aliased node is renamed once.",
+            "a: &x !TestRenamed\n  old_name: a\nb: *x\n",
+            "a: &x !TestRenamed\n  new_name: a\nb: *x\n",
+            [cfgfix.Rename(2, "!TestRenamed", "old_name", "new_name")],
+        ),
+        (
+            "Other types are unchanged.",
+            "!Other\nold_name: a\n",
+            "!Other\nold_name: a\n",
+            [],
+        ),
+        (
+            "Conflicting current name is left for the user.",
+            "!TestRenamed\nold_name: a\nnew_name: b\n",
+            "!TestRenamed\nold_name: a\nnew_name: b\n",
+            [],
+        ),
+    ],
+)
+def test_fix_text(
+    name: str,
+    text: str,
+    expected_text: str,
+    expected_renames: list[cfgfix.Rename],
+) -> None:
+    print(name)
+    actual_text, actual_renames = cfgfix.fix_text(text)
+    testfixtures.compare(expected=expected_text, actual=actual_text)
+    testfixtures.compare(expected=expected_renames, actual=actual_renames)
+
+
+def test_fix_file() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = pathlib.Path(tmpdir) / "sub" / "book.yaml"
+        path.parent.mkdir()
+        path.write_text("!TestRenamed\nold_name: a\n", encoding="utf-8")
+
+        actual_paths = list(cfgfix.yaml_files([pathlib.Path(tmpdir)]))
+        testfixtures.compare(expected=[path], actual=actual_paths)
+        checked = cfgfix.fix_file(path, write=False)
+        testfixtures.compare(expected="!TestRenamed\nold_name: a\n", actual=path.read_text())
+        fixed = cfgfix.fix_file(path, write=True)
+        testfixtures.compare(expected="!TestRenamed\nnew_name: a\n", actual=path.read_text())
+
+    testfixtures.compare(expected=checked, actual=fixed)
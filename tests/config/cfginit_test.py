@@ -0,0 +1,41 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import pathlib
+
+import pytest
+from travdata import config, filesio
+from travdata.config import cfgerror, cfginit, cfgpackage
+
+
+def test_init_config() -> None:
+    files: dict[pathlib.PurePath, str] = {}
+    with filesio.MemReadWriter.new_read_writer(files) as cfg_writer:
+        paths = cfginit.init_config(
+            cfg_writer,
+            book_id="new_book",
+            name='The "New" Book',
+            pdf_filename="new book.pdf",
+        )
+
+    assert paths == sorted(files)
+    with filesio.MemReader.new_reader(files) as cfg_reader:
+        assert not list(cfgpackage.validate_config(cfg_reader))
+        assert config.load_config_version(cfg_reader) == cfginit.INITIAL_VERSION
+        cfg = config.load_config(cfg_reader)
+        book = cfg.books["new_book"]
+        assert book.name == 'The "New" Book'
+        assert book.default_filename == "new book.pdf"
+        tables = list(book.load_group(cfg_reader).all_tables())
+
+    assert [table.file_stem for table in tables] == [
+        pathlib.PurePath("new_book/example-group/example-table")
+    ]
+    assert tables[0].extraction is not None
+
+
+@pytest.mark.parametrize("book_id", ["", "Book", "-book", "a/b", "a b"])
+def test_init_config_rejects_book_id(book_id: str) -> None:
+    with filesio.MemReadWriter.new_read_writer({}) as cfg_writer:
+        with pytest.raises(cfgerror.ConfigurationError):
+            cfginit.init_config(cfg_writer, book_id=book_id, name="Book", pdf_filename="b.pdf")
@@ -0,0 +1,335 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import hashlib
+import json
+import pathlib
+import tempfile
+import textwrap
+import zipfile
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import filesio, travdatarelease
+from travdata.config import cfgerror, cfgpackage
+
+
+_CONFIG_YAML = """\
+!Config
+books:
+    book: !Book
+        name: Book
+        default_filename: book.pdf
+"""
+
+_BOOK_YAML = """\
+!Group
+table_defaults: !TableDefaults
+    extraction: !TableExtraction []
+tables:
+    present: !Table {}
+    missing: !Table {}
+    not-extracted: !Table
+        extraction: null
+"""
+
+
+def _config_files() -> dict[pathlib.PurePath, str]:
+    return {
+        pathlib.PurePath("config.yaml"): _CONFIG_YAML,
+        pathlib.PurePath("book/book.yaml"): _BOOK_YAML,
+        pathlib.PurePath("book/present.tabula-template.json"): "[]",
+    }
+
+
+@pytest.mark.parametrize(
+    "version,expected",
+    [
+        ("1.2.3", True),
+        ("0.0.1-alpha.1+build.5", True),
+        ("1.2", False),
+        ("01.2.3", False),
+        ("v1.2.3", False),
+    ],
+)
+def test_is_semantic_version(version: str, expected: bool) -> None:
+    assert cfgpackage.is_semantic_version(version) == expected
+
+
+def test_validate_config() -> None:
+    with filesio.MemReader.new_reader(_config_files()) as cfg_reader:
+        errors = list(cfgpackage.validate_config(cfg_reader))
+
+    testfixtures.compare(
+        expected=["Table book/missing is missing book/missing.tabula-template.json."],
+        actual=errors,
+    )
+
+
+def test_package_config() -> None:
+    files = _config_files()
+    files[pathlib.PurePath("book/missing.tabula-template.json")] = "[]"
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp_path = pathlib.Path(tmpdir)
+        config_dir = tmp_path / "config"
+        with filesio.DirReadWriter.new_read_writer(config_dir) as cfg_writer:
+            with filesio.MemReader.new_reader(files) as reader:
+                filesio.copy_files(reader, cfg_writer)
+        licence_path = tmp_path / "LICENCE.txt"
+        licence_path.write_text("Licence text.")
+        package_zip = tmp_path / "package.zip"
+
+        cfgpackage.package_config(
+            version="1.0.0",
+            config_dir=config_dir,
+            licence_paths=[licence_path],
+            package_zip=package_zip,
+        )
+
+        with zipfile.ZipFile(package_zip) as zf:
+            contents = {name: zf.read(name) for name in zf.namelist()}
+
+    testfixtures.compare(
+        expected=[
+            "CHANGELOG.md",
+            "LICENCE.txt",
+            "MANIFEST.json",
+            "SHA256SUMS",
+            "book/book.yaml",
+            "book/missing.tabula-template.json",
+            "book/present.tabula-template.json",
+            "config.yaml",
+            "version.txt",
+        ],
+        actual=sorted(contents),
+    )
+    assert contents["version.txt"] == b"1.0.0"
+    assert json.loads(contents["MANIFEST.json"])["version"] == "1.0.0"
+    expected_sums = "".join(
+        f"{hashlib.sha256(contents[name]).hexdigest()}  {name}\n"
+        for name in sorted(contents)
+        if name != "SHA256SUMS"
+    )
+    testfixtures.compare(expected=expected_sums, actual=contents["SHA256SUMS"].decode())
+
+
+def test_build_manifest() -> None:
+    files = _config_files()
+    files[pathlib.PurePath("config.yaml")] = textwrap.dedent(
+        """\
+        !Config
+        books:
+            book: !Book
+                name: Book
+                default_filename: book.pdf
+                tags: [book]
+                post_process:
+                    - !PostProcessCommand
+                      command: [gzip, "{path}"]
+        """
+    )
+    files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """\
+        !Group
+        aggregate_json: true
+        tables:
+            present: !Table
+                title: Present table
+                tags: [present]
+                extraction: !TableExtraction []
+            not-extracted: !Table
+                extraction: null
+        """
+    )
+
+    with filesio.MemReader.new_reader(files) as cfg_reader:
+        actual = cfgpackage.build_manifest(cfg_reader, "1.0.0")
+
+    testfixtures.compare(
+        expected=cfgpackage.Manifest(
+            format_version=cfgpackage.MANIFEST_FORMAT_VERSION,
+            version="1.0.0",
+            travdata_version=travdatarelease.EXECUTABLE_VERSION,
+            books=[
+                cfgpackage.BookManifest(
+                    book_id="book",
+                    title="Book",
+                    default_filename="book.pdf",
+                    output_formats=["csv", "json"],
+                    requires_allow_exec=True,
+                    tags=["book", "book/book", "present"],
+                    tables=[
+                        cfgpackage.TableManifest(
+                            path="book/present",
+                            output="book/present.csv",
+                            title="Present table",
+                            tags=["book", "book/book", "present"],
+                        ),
+                    ],
+                ),
+            ],
+        ),
+        actual=actual,
+    )
+
+
+def test_validate_config_names_table_title() -> None:
+    files = _config_files()
+    files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """\
+        !Group
+        tables:
+            missing: !Table
+                title: Missing Table
+                extraction: !TableExtraction []
+        """
+    )
+    with filesio.MemReader.new_reader(files) as cfg_reader:
+        errors = list(cfgpackage.validate_config(cfg_reader))
+
+    testfixtures.compare(
+        expected=[
+            "Table book/missing (Missing Table) is missing book/missing.tabula-template.json."
+        ],
+        actual=errors,
+    )
+
+
+def test_validate_config_rejects_non_utf8() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        config_dir = pathlib.Path(tmpdir)
+        with filesio.DirReadWriter.new_read_writer(config_dir) as cfg_writer:
+            with filesio.MemReader.new_reader(_config_files()) as reader:
+                filesio.copy_files(reader, cfg_writer)
+        (config_dir / "book" / "missing.tabula-template.json").write_bytes(b"[]")
+        (config_dir / "notes.txt").write_bytes("caf\xe9".encode("latin-1"))
+
+        with filesio.DirReader.new_reader(config_dir) as cfg_reader:
+            errors = list(cfgpackage.validate_config(cfg_reader))
+
+    testfixtures.compare(expected=["notes.txt is not UTF-8 text."], actual=errors)
+
+
+def test_package_config_rejects_invalid_config() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp_path = pathlib.Path(tmpdir)
+        config_dir = tmp_path / "config"
+        with filesio.DirReadWriter.new_read_writer(config_dir) as cfg_writer:
+            with filesio.MemReader.new_reader(_config_files()) as reader:
+                filesio.copy_files(reader, cfg_writer)
+        package_zip = tmp_path / "package.zip"
+
+        with pytest.raises(cfgerror.ConfigurationError):
+            cfgpackage.package_config(
+                version="1.0.0",
+                config_dir=config_dir,
+                licence_paths=[],
+                package_zip=package_zip,
+            )
+
+        assert not package_zip.exists()
+
+
+@pytest.mark.parametrize(
+    "name,licence_names",
+    [
+        ("Configuration file.", ["config.yaml"]),
+        ("Generated file.", ["MANIFEST.json"]),
+        ("Generated changelog.", ["CHANGELOG.md"]),
+        ("Two licences.", ["LICENCE.txt", "LICENCE.txt"]),
+    ],
+)
+def test_package_config_rejects_clashing_licence(name: str, licence_names: list[str]) -> None:
+    print(name)
+    files = _config_files()
+    files[pathlib.PurePath("book/missing.tabula-template.json")] = "[]"
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp_path = pathlib.Path(tmpdir)
+        config_dir = tmp_path / "config"
+        with filesio.DirReadWriter.new_read_writer(config_dir) as cfg_writer:
+            with filesio.MemReader.new_reader(files) as reader:
+                filesio.copy_files(reader, cfg_writer)
+        licence_paths = []
+        for i, licence_name in enumerate(licence_names):
+            licence_path = tmp_path / f"licence{i}" / licence_name
+            licence_path.parent.mkdir()
+            licence_path.write_text("Licence text.")
+            licence_paths.append(licence_path)
+        package_zip = tmp_path / "package.zip"
+
+        with pytest.raises(cfgerror.ConfigurationError, match="would replace"):
+            cfgpackage.package_config(
+                version="1.0.0",
+                config_dir=config_dir,
+                licence_paths=licence_paths,
+                package_zip=package_zip,
+            )
+
+        assert not package_zip.exists()
+
+
+def _checksum_line(content: str, path: str) -> str:
+    return f"{hashlib.sha256(content.encode()).hexdigest()}  {path}\n"
+
+
+@pytest.mark.parametrize(
+    "name,files,expected",
+    [
+        ("no manifest", {"config.yaml": "foo"}, []),
+        (
+            "valid",
+            {"config.yaml": "foo", "SHA256SUMS": _checksum_line("foo", "config.yaml")},
+            [],
+        ),
+        (
+            "modified",
+            {"config.yaml": "bar", "SHA256SUMS": _checksum_line("foo", "config.yaml")},
+            ["config.yaml does not match its checksum in SHA256SUMS."],
+        ),
+        (
+            "missing",
+            {"SHA256SUMS": _checksum_line("foo", "config.yaml")},
+            ["config.yaml is listed in SHA256SUMS but is missing."],
+        ),
+        (
+            "unlisted",
+            {
+                "config.yaml": "foo",
+                "extra.yaml": "",
+                "SHA256SUMS": _checksum_line("foo", "config.yaml"),
+            },
+            ["extra.yaml is not listed in SHA256SUMS."],
+        ),
+        (
+            "malformed",
+            {"SHA256SUMS": "not a checksum line\n"},
+            ["SHA256SUMS line 1 is malformed."],
+        ),
+    ],
+)
+def test_verify_checksums(name: str, files: dict[str, str], expected: list[str]) -> None:
+    print(name)
+    mem_files = {pathlib.PurePath(path): content for path, content in files.items()}
+    with filesio.MemReader.new_reader(mem_files) as reader:
+        actual = list(cfgpackage.verify_checksums(reader))
+
+    testfixtures.compare(expected=expected, actual=actual)
+
+
+def test_verify_checksums_hashes_bytes() -> None:
+    # Checksums must match ``sha256sum``, including for non-UTF-8 files and
+    # line endings that text mode would translate.
+    contents = "caf\xe9\r\n".encode("latin-1")
+    with tempfile.TemporaryDirectory() as tmpdir:
+        config_dir = pathlib.Path(tmpdir)
+        (config_dir / "data.bin").write_bytes(contents)
+        (config_dir / "SHA256SUMS").write_text(
+            f"{hashlib.sha256(contents).hexdigest()}  data.bin\n"
+        )
+
+        with filesio.DirReader.new_reader(config_dir) as reader:
+            actual = list(cfgpackage.verify_checksums(reader))
+
+    testfixtures.compare(expected=[], actual=actual)
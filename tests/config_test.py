@@ -81,6 +81,230 @@ groups:
     )
 
 
+def test_load_group_table_defaults() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+table_defaults: !TableDefaults
+    extraction: !TableExtraction
+        - !Transpose {}
+tables:
+    inherits: !Table {}
+    opts-out: !Table
+        extraction: !!null
+groups:
+    grp-a: !Group
+        table_defaults: !TableDefaults
+            extraction: !TableExtraction
+                - !WrapRowEveryN 2
+        tables:
+            overridden-by-group: !Table {}
+            overridden-by-table: !Table
+                extraction: !TableExtraction []
+    grp-b: !Group
+        table_defaults: !TableDefaults {}
+        tables:
+            inherits-from-grandparent: !Table {}
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    transpose = cfgextract.TableExtraction(transforms=[cfgextract.Transpose()])
+    wrap = cfgextract.TableExtraction(transforms=[cfgextract.WrapRowEveryN(2)])
+    actual_extractions = {str(table.file_stem): table.extraction for table in actual.all_tables()}
+    testfixtures.compare(
+        expected={
+            f"{book_name}/inherits": transpose,
+            f"{book_name}/opts-out": None,
+            f"{book_name}/grp-a/overridden-by-group": wrap,
+            f"{book_name}/grp-a/overridden-by-table": cfgextract.TableExtraction(),
+            f"{book_name}/grp-b/inherits-from-grandparent": transpose,
+        },
+        actual=actual_extractions,
+    )
+
+
+def test_load_group_output_pattern() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+table_defaults: !TableDefaults
+    output_pattern: "{book_id}/all/{group_name}-{table_name}.{ext}"
+tables:
+    inherits: !Table {}
+groups:
+    grp-a: !Group
+        tables:
+            overridden: !Table
+                output_pattern: "{group_path}/renamed.{ext}"
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    actual_paths = {
+        str(table.file_stem): table.output_path.as_posix() for table in actual.all_tables()
+    }
+    testfixtures.compare(
+        expected={
+            f"{book_name}/inherits": f"{book_name}/all/{book_name}-inherits.csv",
+            f"{book_name}/grp-a/overridden": f"{book_name}/grp-a/renamed.csv",
+        },
+        actual=actual_paths,
+    )
+
+
+def test_load_group_time_budget() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+table_defaults: !TableDefaults
+    time_budget: 30
+tables:
+    inherits: !Table {}
+    overridden: !Table
+        time_budget: 2.5
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    testfixtures.compare(
+        expected={f"{book_name}/inherits": 30.0, f"{book_name}/overridden": 2.5},
+        actual={str(table.file_stem): table.time_budget for table in actual.all_tables()},
+    )
+
+
+def test_load_group_cache() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+table_defaults: !TableDefaults
+    cache: always
+tables:
+    inherits: !Table {}
+    overridden: !Table
+        cache: never
+groups:
+    unset: !Group
+        table_defaults: !TableDefaults {}
+        tables:
+            inherits: !Table {}
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    testfixtures.compare(
+        expected={
+            f"{book_name}/inherits": config.CachePolicy.ALWAYS,
+            f"{book_name}/overridden": config.CachePolicy.NEVER,
+            f"{book_name}/unset/inherits": config.CachePolicy.ALWAYS,
+        },
+        actual={str(table.file_stem): table.cache for table in actual.all_tables()},
+    )
+
+
+def test_load_group_invalid_cache() -> None:
+    book_name = "book-name"
+    book_yaml = """
+!Group
+tables:
+    t: !Table
+        cache: sometimes
+"""
+    files = {pathlib.PurePath(book_name) / "book.yaml": book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        with pytest.raises(cfgerror.ConfigurationError, match="cache must be one of"):
+            config.load_book(cfg_reader, book_name, set())
+
+
+def test_load_group_tabula_options() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+table_defaults: !TableDefaults
+    tabula_options: !TabulaOptions
+        use_line_returns: true
+tables:
+    inherits: !Table {}
+    overridden: !Table
+        tabula_options: !TabulaOptions
+            columns: [100, 250.5]
+groups:
+    unset: !Group
+        table_defaults: !TableDefaults {}
+        tables:
+            inherits: !Table {}
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    testfixtures.compare(
+        expected={
+            f"{book_name}/inherits": cfgextract.TabulaOptions(use_line_returns=True),
+            f"{book_name}/overridden": cfgextract.TabulaOptions(columns=[100.0, 250.5]),
+            f"{book_name}/unset/inherits": cfgextract.TabulaOptions(use_line_returns=True),
+        },
+        actual={str(table.file_stem): table.tabula_options for table in actual.all_tables()},
+    )
+
+
+def test_load_group_title_and_doc() -> None:
+    book_name = "book-name"
+    book_yaml_path = pathlib.PurePath(book_name) / "book.yaml"
+    book_yaml = """
+!Group
+title: Book Title
+groups:
+    grp: !Group
+        doc: Notes about the group.
+        tables:
+            table: !Table
+                title: Table Caption
+                doc: |
+                    Multiple lines
+                    of notes.
+"""
+    files = {book_yaml_path: book_yaml}
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_book(cfg_reader, book_name, set())
+
+    grp = actual.groups["grp"]
+    table = grp.tables["table"]
+    testfixtures.compare(
+        expected=[
+            ("Book Title", None),
+            (None, "Notes about the group."),
+            ("Table Caption", "Multiple lines\nof notes.\n"),
+        ],
+        actual=[(actual.title, actual.doc), (grp.title, grp.doc), (table.title, table.doc)],
+    )
+
+
+@pytest.mark.parametrize(
+    "name,pattern",
+    [
+        ("Unknown variable.", "{unknown}.csv"),
+        ("Unbalanced brace.", "{table_name.csv"),
+        ("Absolute path.", "/{table_name}.{ext}"),
+        ("Parent directory.", "../{table_name}.{ext}"),
+        ("Empty.", ""),
+    ],
+)
+def test_format_output_path_errors(name: str, pattern: str) -> None:
+    print(name)
+    with pytest.raises(cfgerror.ConfigurationError):
+        config.format_output_path(pattern, pathlib.PurePath("book/grp/table"))
+
+
 @pytest.mark.parametrize(
     "name,yaml",
     [
@@ -146,6 +370,47 @@ def test_config_parse_errors(
                 default=[r"", r"\g<0>"],
             ),
         ),
+        (
+            "ExtractKeyValues",
+            """
+            !ExtractKeyValues
+              fields:
+                - !KeyValueField {key: Hits, pattern: 'HITS (\\d+)'}
+              record_start: '[A-Z][a-z]'
+            """,
+            cfgextract.ExtractKeyValues(
+                fields=[cfgextract.KeyValueField(key="Hits", pattern=r"HITS (\d+)")],
+                record_start="[A-Z][a-z]",
+            ),
+        ),
+        (
+            "FilterRows",
+            """
+            !FilterRows
+              column: 2
+              pattern: '\\d+'
+              invert: true
+              skip_rows: 1
+            """,
+            cfgextract.FilterRows(column=2, pattern=r"\d+", invert=True, skip_rows=1),
+        ),
+        (
+            "SortRows",
+            """
+            !SortRows
+              keys:
+                - !SortKey {column: 1, numeric: true}
+                - !SortKey {column: 0, descending: true}
+              skip_rows: 1
+            """,
+            cfgextract.SortRows(
+                keys=[
+                    cfgextract.SortKey(column=1, numeric=True),
+                    cfgextract.SortKey(column=0, descending=True),
+                ],
+                skip_rows=1,
+            ),
+        ),
         (
             "WrapRowEveryN",
             """
@@ -176,6 +441,14 @@ def test_config_parse_errors(
             """,
             cfgextract.PrependRow(["foo", "bar"]),
         ),
+        (
+            "DropRepeatedHeader",
+            """
+            !DropRepeatedHeader
+              header_rows: 2
+            """,
+            cfgextract.DropRepeatedHeader(header_rows=2),
+        ),
         (
             "Transpose",
             """
@@ -189,3 +462,47 @@ def test_parse(name: str, yaml: str, expected: Any) -> None:
     print(name)
     actual = config.parse_yaml_for_testing(yaml)
     testfixtures.compare(expected=expected, actual=actual)
+
+
+@pytest.mark.parametrize(
+    "isbn,valid",
+    [
+        ("978-0-306-40615-7", True),
+        ("978-0-306-40615-8", False),
+        ("0-306-40615-2", True),
+        ("0-8044-2957-X", True),
+        ("0-306-40615-3", False),
+        ("not an isbn", False),
+    ],
+)
+def test_book_metadata_isbn(isbn: str, valid: bool) -> None:
+    metadata = config.BookMetadata(isbn=isbn)
+    if valid:
+        metadata.validate("book")
+    else:
+        with pytest.raises(cfgerror.ConfigurationError):
+            metadata.validate("book")
+
+
+def test_load_config_systems() -> None:
+    files = {
+        pathlib.PurePath("config.yaml"): textwrap.dedent(
+            """
+            !Config
+            books: {}
+            systems: !Systems
+              table_warnings: !TableWarnings
+                min_rows: 2
+                warn_touches_edge: false
+            """
+        ),
+    }
+    with filesio.MemReadWriter.new_reader(files) as cfg_reader:
+        actual = config.load_config(cfg_reader)
+
+    testfixtures.compare(
+        expected=config.Systems(
+            table_warnings=config.TableWarnings(min_rows=2, warn_touches_edge=False),
+        ),
+        actual=actual.systems,
+    )
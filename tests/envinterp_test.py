@@ -0,0 +1,51 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import envinterp
+from travdata.config import cfgerror
+
+
+_ENVIRON = {"SET": "value", "EMPTY": ""}
+
+
+@pytest.mark.parametrize(
+    "value,expected,expected_substitutions",
+    [
+        ("no variables", "no variables", []),
+        ("a/${SET}/b", "a/value/b", [envinterp.Substitution("SET", "value", False)]),
+        ("${SET:-other}", "value", [envinterp.Substitution("SET", "value", False)]),
+        ("${UNSET:-other}", "other", [envinterp.Substitution("UNSET", "other", True)]),
+        ("${EMPTY:-other}", "other", [envinterp.Substitution("EMPTY", "other", True)]),
+        ("${UNSET:-}", "", [envinterp.Substitution("UNSET", "", True)]),
+        ("$${SET} $SET", "${SET} $SET", []),
+    ],
+)
+def test_interpolator(
+    value: str,
+    expected: str,
+    expected_substitutions: list[envinterp.Substitution],
+) -> None:
+    interpolator = envinterp.Interpolator(_ENVIRON)
+    testfixtures.compare(expected=expected, actual=interpolator(value))
+    testfixtures.compare(expected=expected_substitutions, actual=interpolator.substitutions)
+
+
+def test_interpolator_unset_without_default() -> None:
+    interpolator = envinterp.Interpolator(_ENVIRON)
+    with pytest.raises(cfgerror.ConfigurationError):
+        interpolator("${UNSET}")
+
+
+@pytest.mark.parametrize(
+    "value,expected",
+    [
+        ("no variables", False),
+        ("a/${SET}/b", True),
+        ("$$", True),
+        ("^price$", False),
+    ],
+)
+def test_has_references(value: str, expected: bool) -> None:
+    testfixtures.compare(expected=expected, actual=envinterp.has_references(value))
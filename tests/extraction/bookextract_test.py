@@ -0,0 +1,777 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import json
+import pathlib
+import tempfile
+import textwrap
+from typing import IO, Any, Callable, Optional
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import config, csvutil, filesio, travdatarelease
+from travdata.config import cfgextract
+from travdata.extraction import bookextract, tablestats, tabulautil
+
+
+def test_rate_limited() -> None:
+    now = 0.0
+    received: list[bookextract.Event] = []
+    rate_limited = bookextract.RateLimited(received.append, min_interval=1.0, clock=lambda: now)
+
+    started = bookextract.TableStarted(pathlib.PurePath("a.csv"))
+    sent: list[tuple[float, bookextract.Event]] = [
+        (0.0, bookextract.Progress(0, 4)),
+        (0.1, started),
+        (0.2, bookextract.Progress(1, 4)),
+        (1.5, bookextract.Progress(2, 4)),
+        (1.6, bookextract.Progress(3, 4)),
+        (1.7, bookextract.Progress(4, 4)),
+    ]
+    for now, event in sent:
+        rate_limited(event)
+
+    testfixtures.compare(
+        expected=[
+            bookextract.Progress(0, 4),
+            started,
+            bookextract.Progress(2, 4),
+            bookextract.Progress(4, 4),
+        ],
+        actual=received,
+    )
+
+
+class FakeTableReader:
+    def __init__(self, tables: list[tabulautil.TabulaTable]) -> None:
+        self.tables = tables
+
+    def read_pdf_with_template(
+        self,
+        *,
+        pdf_path: pathlib.Path,
+        template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
+    ) -> tuple[set[int], list[tabulautil.TabulaTable]]:
+        del pdf_path  # unused
+        del template_file  # unused
+        del options  # unused
+        del check_deadline  # unused
+        return {1}, self.tables
+
+
+_CONFIG_FILES = {
+    pathlib.PurePath("config.yaml"): textwrap.dedent(
+        """
+        !Config
+        books:
+          book: !Book
+            name: Book
+            default_filename: book.pdf
+            metadata: !BookMetadata
+              publisher: Publisher
+              isbn: 978-0-306-40615-7
+              template_authors: [Author]
+        """
+    ),
+    pathlib.PurePath("book/book.yaml"): textwrap.dedent(
+        """
+        !Group
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    ),
+    pathlib.PurePath("book/table.tabula-template.json"): '[{"page": 1}]',
+}
+
+
+def _extract_book(
+    output_files: dict[pathlib.PurePath, str],
+    tables: list[tabulautil.TabulaTable],
+    observer: Optional[Callable[[bookextract.Event], None]] = None,
+    config_files: Optional[dict[pathlib.PurePath, str]] = None,
+    **kwargs: Any,
+) -> list[str]:
+    errors: list[str] = []
+    bookextract.extract_book(
+        table_reader=FakeTableReader(tables),
+        ext_cfg=bookextract.ExtractionConfig(
+            cfg_reader_ctx=filesio.MemReader.new_reader(dict(config_files or _CONFIG_FILES)),
+            out_writer_ctx=filesio.MemReadWriter.new_read_writer(output_files),
+            input_pdf=pathlib.Path("book.pdf"),
+            book_id="book",
+            overwrite_existing=True,
+            with_tags=frozenset(),
+            without_tags=frozenset(),
+            **kwargs,
+        ),
+        events=bookextract.ExtractEvents(
+            on_error=errors.append,
+            observers=[] if observer is None else [observer],
+        ),
+    )
+    return errors
+
+
+def test_extract_book_debug_extraction() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "a", "top": 1.0, "left": 2.0, "width": 3.0, "height": 4.0}]]},
+    ]
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(output_files, tables, debug_extraction=True)
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(expected="a\r\n", actual=output_files[pathlib.PurePath("book/table.csv")])
+    testfixtures.compare(
+        expected={"template": [{"page": 1}], "tables": tables},
+        actual=json.loads(output_files[pathlib.PurePath("book/table.tabula.json")]),
+    )
+
+
+@pytest.mark.parametrize(
+    "name,intermediates_tags,expect_written",
+    [
+        ("All tables.", frozenset(), True),
+        ("Table without selected tag.", frozenset({"other"}), False),
+    ],
+)
+def test_extract_book_dump_intermediates(
+    name: str,
+    intermediates_tags: frozenset[str],
+    expect_written: bool,
+) -> None:
+    print(name)
+    tables: list[tabulautil.TabulaTable] = [
+        {
+            "data": [
+                [
+                    {"text": "a", "top": 10.0, "left": 20.0, "width": 30.0, "height": 5.0},
+                    {"text": "", "top": 0.0, "left": 0.0, "width": 0.0, "height": 0.0},
+                ]
+            ]
+        }
+    ]
+    with tempfile.TemporaryDirectory() as tmpdir:
+        intermediates_dir = pathlib.Path(tmpdir)
+
+        errors = _extract_book(
+            {},
+            tables,
+            intermediates_dir=intermediates_dir,
+            intermediates_tags=intermediates_tags,
+        )
+
+        testfixtures.compare(expected=[], actual=errors)
+        step_path = intermediates_dir / "book/table/00-tabula.json"
+        if expect_written:
+            testfixtures.compare(
+                expected={
+                    "table": "book/table",
+                    "step": "00-tabula",
+                    "rows": [["a", ""]],
+                    "boxes": [[{"top": 10.0, "left": 20.0, "width": 30.0, "height": 5.0}, None]],
+                },
+                actual=json.loads(step_path.read_text(encoding="utf-8")),
+            )
+        else:
+            assert not step_path.exists()
+
+
+def test_extract_book_writes_metadata() -> None:
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(output_files, [{"data": [[{"text": "a"}]]}])
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(
+        expected={
+            "id": "book",
+            "title": "Book",
+            "publisher": "Publisher",
+            "edition": None,
+            "isbn": "978-0-306-40615-7",
+            "template_authors": ["Author"],
+            "config_version": None,
+            "travdata_version": travdatarelease.EXECUTABLE_VERSION,
+            "attribution": None,
+        },
+        actual=json.loads(output_files[pathlib.PurePath("book/metadata.json")]),
+    )
+    assert pathlib.PurePath("book/ATTRIBUTION.txt") not in output_files
+
+
+def test_extract_book_writes_attribution() -> None:
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("config.yaml")] += textwrap.dedent(
+        """
+        systems: !Systems
+          attribution: |
+            Fan content, not approved or endorsed by the publisher.
+        """
+    )
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+          _attribution: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    config_files[pathlib.PurePath("book/_attribution.tabula-template.json")] = '[{"page": 1}]'
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(output_files, [{"data": [[{"text": "a"}]]}], config_files=config_files)
+
+    testfixtures.compare(
+        expected=[
+            "Table book/_attribution is left out of book.json, "
+            "as its name is reserved for the attribution notice."
+        ],
+        actual=errors,
+    )
+    notice = "Fan content, not approved or endorsed by the publisher.\n"
+    testfixtures.compare(
+        expected=notice,
+        actual=output_files[pathlib.PurePath("book/ATTRIBUTION.txt")],
+    )
+    metadata = json.loads(output_files[pathlib.PurePath("book/metadata.json")])
+    testfixtures.compare(expected=notice, actual=metadata["attribution"])
+    testfixtures.compare(
+        expected={"_attribution": notice, "table": [["a"]]},
+        actual=json.loads(output_files[pathlib.PurePath("book.json")]),
+    )
+
+
+@pytest.mark.parametrize(
+    "tables",
+    [
+        [],
+        [{"data": []}],
+        [{"data": [[{"text": ""}, {"text": " "}]]}],
+    ],
+)
+def test_extract_book_warns_on_no_text(tables: list[tabulautil.TabulaTable]) -> None:
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        stats_thresholds=tablestats.Thresholds(max_empty_fraction=None),
+    )
+
+    testfixtures.compare(
+        expected=[
+            "Table book/table: no text found in the configured region, which appears "
+            "to be an image; consider OCR extraction."
+        ],
+        actual=errors,
+    )
+
+
+def test_extract_book_warns_on_stats_thresholds() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "a"}, {"text": ""}, {"text": ""}]]},
+    ]
+    output_files: dict[pathlib.PurePath, str] = {}
+    events: list[bookextract.Event] = []
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        stats_thresholds=tablestats.Thresholds(min_rows=2, max_empty_fraction=0.5),
+        observer=events.append,
+    )
+
+    testfixtures.compare(
+        expected=[
+            "Table book/table has 1 rows, fewer than 2.",
+            "Table book/table has 67% empty cells, more than 50%.",
+        ],
+        actual=errors,
+    )
+    finished = [e for e in events if isinstance(e, bookextract.TableFinished)]
+    testfixtures.compare(
+        expected=[tablestats.TableStats(rows=1, columns=3, empty_cells=2, touches_edge=False)],
+        actual=[e.stats for e in finished],
+    )
+
+
+@pytest.mark.parametrize(
+    "name,budget_action,expect_written,expect_aborted,expect_error_prefix",
+    [
+        (
+            "Warn keeps the table.",
+            bookextract.BudgetAction.WARN,
+            True,
+            False,
+            "Table book/table took ",
+        ),
+        (
+            "Fail stops and discards the table.",
+            bookextract.BudgetAction.FAIL,
+            False,
+            False,
+            "Table book/table was stopped after ",
+        ),
+        (
+            "Abort stops and discards the table, and stops the run.",
+            bookextract.BudgetAction.ABORT,
+            False,
+            True,
+            "Table book/table was stopped after ",
+        ),
+    ],
+)
+def test_extract_book_time_budget(
+    name: str,
+    budget_action: bookextract.BudgetAction,
+    expect_written: bool,
+    expect_aborted: bool,
+    expect_error_prefix: str,
+) -> None:
+    print(name)
+    tables: list[tabulautil.TabulaTable] = [{"data": [[{"text": "a"}]]}]
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    # No extraction completes within a zero budget.
+    errors = _extract_book(output_files, tables, time_budget=0.0, budget_action=budget_action)
+
+    testfixtures.compare(
+        expected=expect_written, actual=pathlib.PurePath("book/table.csv") in output_files
+    )
+    assert errors[0].startswith(expect_error_prefix)
+    assert errors[0].endswith("exceeding its time budget of 0.0s.")
+    testfixtures.compare(
+        expected=expect_aborted,
+        actual="Stopping extraction, as a table exceeded its time budget." in errors,
+    )
+
+
+def test_extract_book_table_warnings_from_config() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "a"}, {"text": ""}, {"text": ""}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_yaml = pathlib.PurePath("config.yaml")
+    config_files[config_yaml] += textwrap.dedent(
+        """
+        systems: !Systems
+          table_warnings: !TableWarnings
+            min_rows: 2
+            max_empty_fraction: 0.9
+        """
+    )
+
+    errors = _extract_book(
+        {},
+        tables,
+        config_files=config_files,
+        stats_thresholds=tablestats.Thresholds(max_empty_fraction=0.1),
+        stats_overrides=config.TableWarnings(min_rows=3),
+    )
+
+    # min_rows is overridden, and max_empty_fraction is set by the configuration.
+    testfixtures.compare(
+        expected=["Table book/table has 1 rows, fewer than 3."],
+        actual=errors,
+    )
+
+
+def test_extract_book_assert_table() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "Name"}, {"text": "TL"}], [{"text": "a"}, {"text": "x"}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        tables:
+          table: !Table
+            extraction: !TableExtraction
+              - !AssertTable
+                min_rows: 2
+                required_columns: [Name, Cost]
+                cell_patterns:
+                  - !CellPattern
+                    column: 1
+                    pattern: '\\d+'
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(output_files, tables, config_files=config_files)
+
+    testfixtures.compare(
+        expected=[
+            "Table book/table failed its assertions: "
+            "has 1 rows after the header, fewer than 2; "
+            "has no 'Cost' column in the header; "
+            "row 2 column 1 value 'x' does not match '\\\\d+'",
+        ],
+        actual=errors,
+    )
+    assert pathlib.PurePath("book/table.csv") not in output_files
+
+
+def test_extract_book_cell_types() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "Name"}, {"text": "TL"}], [{"text": "a"}, {"text": "12"}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        tables:
+          table: !Table
+            extraction: !TableExtraction
+              - !AnnotateCellTypes {}
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(output_files, tables, config_files=config_files)
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(
+        expected="Name,TL\r\na,12\r\n",
+        actual=output_files[pathlib.PurePath("book/table.csv")],
+    )
+    testfixtures.compare(
+        expected=",\r\ntext,integer\r\n",
+        actual=output_files[pathlib.PurePath(f"book/table{bookextract.CELL_TYPES_SUFFIX}")],
+    )
+
+
+def test_extract_book_aggregate_json() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "a"}, {"text": "b"}], [{"text": "c"}, {"text": "d"}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        config_files=config_files,
+        stats_thresholds=tablestats.Thresholds(warn_touches_edge=False),
+    )
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(
+        expected={"table": [["a", "b"], ["c", "d"]]},
+        actual=json.loads(output_files[pathlib.PurePath("book.json")]),
+    )
+
+
+def test_extract_book_aggregate_json_objects() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "Item"}, {"text": "TL"}], [{"text": "Knife"}, {"text": "1"}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        json_objects: true
+        json_coerce_types: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        config_files=config_files,
+        stats_thresholds=tablestats.Thresholds(warn_touches_edge=False),
+    )
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(
+        expected={"table": [{"Item": "Knife", "TL": 1}]},
+        actual=json.loads(output_files[pathlib.PurePath("book.json")]),
+    )
+
+
+@pytest.mark.parametrize(
+    "name,overwrite_existing,expected",
+    [
+        (
+            "Existing table is kept.",
+            False,
+            bookextract.PlannedOutputs(
+                created=[pathlib.PurePath("book/other.csv"), pathlib.PurePath("book.json")],
+                overwritten=[pathlib.PurePath("book/metadata.json")],
+                kept=[pathlib.PurePath("book/table.csv")],
+            ),
+        ),
+        (
+            "Existing table is overwritten.",
+            True,
+            bookextract.PlannedOutputs(
+                created=[pathlib.PurePath("book/other.csv"), pathlib.PurePath("book.json")],
+                overwritten=[
+                    pathlib.PurePath("book/metadata.json"),
+                    pathlib.PurePath("book/table.csv"),
+                ],
+                kept=[],
+            ),
+        ),
+    ],
+)
+def test_plan_outputs(
+    name: str,
+    overwrite_existing: bool,
+    expected: bookextract.PlannedOutputs,
+) -> None:
+    print(name)
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+          other: !Table
+            extraction: !TableExtraction []
+          not-extracted: !Table
+            extraction: null
+        """
+    )
+    output_files = {
+        pathlib.PurePath("book/metadata.json"): "{}",
+        pathlib.PurePath("book/table.csv"): "a\r\n",
+    }
+    ext_cfg = bookextract.ExtractionConfig(
+        cfg_reader_ctx=filesio.MemReader.new_reader({}),
+        out_writer_ctx=filesio.MemReadWriter.new_read_writer({}),
+        input_pdf=pathlib.Path("book.pdf"),
+        book_id="book",
+        overwrite_existing=overwrite_existing,
+        with_tags=frozenset(),
+        without_tags=frozenset(),
+    )
+
+    with (
+        filesio.MemReader.new_reader(config_files) as cfg_reader,
+        filesio.MemReader.new_reader(output_files) as out_reader,
+    ):
+        actual = bookextract.plan_outputs(
+            ext_cfg=ext_cfg, cfg_reader=cfg_reader, out_reader=out_reader
+        )
+
+    testfixtures.compare(expected=expected, actual=actual)
+
+
+@pytest.mark.parametrize(
+    "cache,overwrite_existing,expected",
+    [
+        ("auto", False, "old\r\n"),
+        ("auto", True, "a\r\n"),
+        ("always", True, "old\r\n"),
+        ("never", False, "a\r\n"),
+    ],
+)
+def test_extract_book_cache_policy(cache: str, overwrite_existing: bool, expected: str) -> None:
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        f"""
+        !Group
+        table_defaults: !TableDefaults
+          cache: {cache}
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    output_files = {pathlib.PurePath("book/table.csv"): "old\r\n"}
+    errors: list[str] = []
+
+    bookextract.extract_book(
+        table_reader=FakeTableReader([{"data": [[{"text": "a"}]]}]),
+        ext_cfg=bookextract.ExtractionConfig(
+            cfg_reader_ctx=filesio.MemReader.new_reader(config_files),
+            out_writer_ctx=filesio.MemReadWriter.new_read_writer(output_files),
+            input_pdf=pathlib.Path("book.pdf"),
+            book_id="book",
+            overwrite_existing=overwrite_existing,
+            with_tags=frozenset(),
+            without_tags=frozenset(),
+        ),
+        events=bookextract.ExtractEvents(on_error=errors.append),
+    )
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(expected=expected, actual=output_files[pathlib.PurePath("book/table.csv")])
+
+
+def test_extract_book_csv_bom() -> None:
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "Item"}], [{"text": "Café"}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        config_files=config_files,
+        csv_encoding=csvutil.CsvEncoding.UTF8_BOM,
+        stats_thresholds=tablestats.Thresholds(warn_touches_edge=False),
+    )
+
+    testfixtures.compare(expected=[], actual=errors)
+    testfixtures.compare(
+        expected="\ufeffItem\r\nCafé\r\n",
+        actual=output_files[pathlib.PurePath("book/table.csv")],
+    )
+    # The byte order mark is not part of the data read back from the CSV file.
+    testfixtures.compare(
+        expected={"table": [["Item"], ["Café"]]},
+        actual=json.loads(output_files[pathlib.PurePath("book.json")]),
+    )
+
+
+@pytest.mark.parametrize(
+    "name,text,expect_csv,expect_error_prefix",
+    [
+        ("Encodable text.", "Café", b"Item\r\nCaf\xe9\r\n", None),
+        (
+            "Text that cannot be encoded.",
+            "North \u2192",
+            None,
+            "Table book/table cannot be written as windows-1252: ",
+        ),
+    ],
+)
+def test_extract_book_csv_cp1252(
+    name: str,
+    text: str,
+    expect_csv: Optional[bytes],
+    expect_error_prefix: Optional[str],
+) -> None:
+    print(name)
+    tables: list[tabulautil.TabulaTable] = [
+        {"data": [[{"text": "Item"}], [{"text": text}]]},
+    ]
+    config_files = dict(_CONFIG_FILES)
+    config_files[pathlib.PurePath("book/book.yaml")] = textwrap.dedent(
+        """
+        !Group
+        aggregate_json: true
+        tables:
+          table: !Table
+            extraction: !TableExtraction []
+        """
+    )
+    output_files: dict[pathlib.PurePath, str] = {}
+
+    errors = _extract_book(
+        output_files,
+        tables,
+        config_files=config_files,
+        csv_encoding=csvutil.CsvEncoding.CP1252,
+        stats_thresholds=tablestats.Thresholds(warn_touches_edge=False),
+    )
+
+    if expect_error_prefix is None:
+        testfixtures.compare(expected=[], actual=errors)
+    else:
+        assert len(errors) == 1
+        assert errors[0].startswith(expect_error_prefix)
+    with filesio.MemReader.new_reader(output_files) as reader:
+        csv_path = pathlib.PurePath("book/table.csv")
+        if expect_csv is None:
+            assert not reader.exists(csv_path)
+            return
+        with reader.open_read_binary(csv_path) as f:
+            testfixtures.compare(expected=expect_csv, actual=f.read())
+    # The CSV file is read back as Windows-1252 into the aggregate JSON.
+    testfixtures.compare(
+        expected={"table": [["Item"], [text]]},
+        actual=json.loads(output_files[pathlib.PurePath("book.json")]),
+    )
+
+
+class RecordingObserver(bookextract.Observer):
+    def __init__(self) -> None:
+        self.calls: list[str] = []
+
+    def progress(self, event: bookextract.Progress) -> None:
+        self.calls.append(f"progress {event.completed}/{event.total}")
+
+    def table_finished(self, event: bookextract.TableFinished) -> None:
+        self.calls.append(f"table_finished {event.output_path} {event.success}")
+
+    def run_finished(self, event: bookextract.RunFinished) -> None:
+        self.calls.append(f"run_finished stopped={event.stopped}")
+
+
+@pytest.mark.parametrize(
+    "name,book_id,expected_calls",
+    [
+        (
+            "Complete run.",
+            "book",
+            [
+                "progress 0/1",
+                "table_finished book/table.csv True",
+                "progress 1/1",
+                "run_finished stopped=False",
+            ],
+        ),
+        (
+            "Unknown book.",
+            "unknown",
+            ["run_finished stopped=True"],
+        ),
+    ],
+)
+def test_extract_book_observers(name: str, book_id: str, expected_calls: list[str]) -> None:
+    print(name)
+    observer = RecordingObserver()
+    events = bookextract.ExtractEvents()
+    events.add_observer(observer)
+
+    bookextract.extract_book(
+        table_reader=FakeTableReader([{"data": [[{"text": "a"}]]}]),
+        ext_cfg=bookextract.ExtractionConfig(
+            cfg_reader_ctx=filesio.MemReader.new_reader(dict(_CONFIG_FILES)),
+            out_writer_ctx=filesio.MemReadWriter.new_read_writer({}),
+            input_pdf=pathlib.Path("book.pdf"),
+            book_id=book_id,
+            overwrite_existing=True,
+            with_tags=frozenset(),
+            without_tags=frozenset(),
+        ),
+        events=events,
+    )
+
+    testfixtures.compare(expected=expected_calls, actual=observer.calls)
@@ -0,0 +1,48 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+from typing import Any
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata.extraction import jsonrows
+
+
+@pytest.mark.parametrize(
+    "cell,expected",
+    [
+        ("", None),
+        ("12", 12),
+        ("-3", -3),
+        ("1.5", 1.5),
+        ("Cr100", "Cr100"),
+        ("1,000", "1,000"),
+        ("2D", "2D"),
+    ],
+)
+def test_coerce_cell(cell: str, expected: Any) -> None:
+    testfixtures.compare(expected=expected, actual=jsonrows.coerce_cell(cell))
+
+
+def test_rows_to_objects() -> None:
+    rows = [
+        ["Name", "", "Name", "TL"],
+        ["Laser", "x", "Rifle", "9"],
+        ["Club"],
+        ["Axe", "", "", "1", "extra"],
+    ]
+
+    actual = jsonrows.rows_to_objects(rows, coerce_types=True)
+
+    testfixtures.compare(
+        expected=[
+            {"Name": "Laser", "column_2": "x", "Name_2": "Rifle", "TL": 9},
+            {"Name": "Club"},
+            {"Name": "Axe", "column_2": None, "Name_2": None, "TL": 1, "column_5": "extra"},
+        ],
+        actual=actual,
+    )
+
+
+def test_rows_to_objects_empty() -> None:
+    testfixtures.compare(expected=[], actual=jsonrows.rows_to_objects([], coerce_types=False))
@@ -0,0 +1,52 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import io
+import pathlib
+from typing import IO, Callable, Optional
+
+import testfixtures  # type: ignore[import-untyped]
+from travdata.config import cfgextract
+from travdata.extraction import bookextract, metrics, tabulautil
+
+
+class FakeTableReader:
+    def read_pdf_with_template(
+        self,
+        *,
+        pdf_path: pathlib.Path,
+        template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
+    ) -> tuple[set[int], list[tabulautil.TabulaTable]]:
+        del pdf_path  # unused
+        del template_file  # unused
+        del options  # unused
+        del check_deadline  # unused
+        return {1}, []
+
+
+def test_metrics() -> None:
+    times = iter([1.0, 3.5])
+    run_metrics = metrics.Metrics()
+    reader = metrics.TimedTableReader(FakeTableReader(), run_metrics, clock=lambda: next(times))
+
+    reader.read_pdf_with_template(pdf_path=pathlib.Path("a.pdf"), template_file=io.StringIO())
+    path = pathlib.PurePath("a.csv")
+    run_metrics(bookextract.TableStarted(path))
+    run_metrics(bookextract.TableFinished(path, success=True, duration=3.0))
+    run_metrics(bookextract.TableFinished(path, success=False, duration=0.5))
+    run_metrics(bookextract.Progress(2, 2))
+    run_metrics(bookextract.RunFinished(stopped=False))
+
+    testfixtures.compare(
+        expected=metrics.Metrics(
+            started=True,
+            tables_succeeded=1,
+            tables_failed=1,
+            table_seconds=3.5,
+            tabula_calls=1,
+            tabula_seconds=2.5,
+        ),
+        actual=run_metrics,
+    )
@@ -0,0 +1,64 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import pathlib
+import sys
+import tempfile
+
+import testfixtures  # type: ignore[import-untyped]
+from travdata import config, filesio
+from travdata.extraction import postprocess
+
+
+def test_run_commands() -> None:
+    path = pathlib.PurePath("sub/table.csv")
+    commands = [
+        config.PostProcessCommand(
+            command=[
+                sys.executable,
+                "-c",
+                "import sys; open(sys.argv[1], 'a').write('appended')",
+                "{path}",
+            ],
+        ),
+    ]
+
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        filesio.DirReadWriter.new_read_writer(pathlib.Path(tmpdir)) as out_writer,
+    ):
+        with out_writer.open_write(path) as f:
+            f.write("written,")
+
+        errors = list(postprocess.run_commands(commands, out_writer, path))
+
+        testfixtures.compare(expected=[], actual=errors)
+        with out_writer.open_read(path) as f:
+            testfixtures.compare(expected="written,appended", actual=f.read())
+
+
+def test_run_commands_reports_failure() -> None:
+    commands = [
+        config.PostProcessCommand(
+            command=[sys.executable, "-c", "import sys; print('oops'); sys.exit(3)"],
+        ),
+    ]
+
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        filesio.DirReadWriter.new_read_writer(pathlib.Path(tmpdir)) as out_writer,
+    ):
+        errors = list(postprocess.run_commands(commands, out_writer, pathlib.PurePath("a.csv")))
+
+    assert len(errors) == 1
+    assert "exit code 3" in errors[0]
+    assert "oops" in errors[0]
+
+
+def test_run_commands_requires_dir() -> None:
+    commands = [config.PostProcessCommand(command=["true"])]
+
+    with filesio.MemReadWriter.new_read_writer({}) as out_writer:
+        errors = list(postprocess.run_commands(commands, out_writer, pathlib.PurePath("a.csv")))
+
+    assert len(errors) == 1
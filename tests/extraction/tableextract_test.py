@@ -3,7 +3,7 @@
 
 import dataclasses
 import pathlib
-from typing import IO
+from typing import IO, Callable, Optional
 
 import pytest
 import testfixtures  # type: ignore[import-untyped]
@@ -16,6 +16,7 @@ from travdata.extraction import tableextract, tabulautil
 class Call:
     pdf_path: pathlib.Path
     template_content: str
+    options: Optional[cfgextract.TabulaOptions] = None
 
 
 class FakeTableReader:
@@ -41,8 +42,12 @@ class FakeTableReader:
         *,
         pdf_path: pathlib.Path,
         template_file: IO[str],
+        options: Optional[cfgextract.TabulaOptions] = None,
+        check_deadline: Optional[Callable[[], None]] = None,
     ) -> tuple[set[int], list[tabulautil.TabulaTable]]:
-        self.calls.append(Call(pdf_path, template_file.read()))
+        self.calls.append(Call(pdf_path, template_file.read(), options))
+        if check_deadline:
+            check_deadline()
         return {1}, self.return_tables
 
 
@@ -399,6 +404,314 @@ class FakeTableReader:
                 ["r1c3", "", "r3c3"],
             ],
         ),
+        (
+            "Drops repeated single header row.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.DropRepeatedHeader()],
+            ),
+            [
+                [
+                    ["header 1", "header 2"],
+                    ["r1c1", "r1c2"],
+                ],
+                [
+                    ["header 1", " header  2"],
+                    ["r2c1", "r2c2"],
+                ],
+            ],
+            [
+                ["header 1", "header 2"],
+                ["r1c1", "r1c2"],
+                ["r2c1", "r2c2"],
+            ],
+        ),
+        (
+            "Drops repeated multi-row header, keeping partial matches.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.DropRepeatedHeader(header_rows=2)],
+            ),
+            [
+                [
+                    ["h1", "h2"],
+                    ["h3", "h4"],
+                    ["r1c1", "r1c2"],
+                    ["h1", "h2"],
+                    ["r2c1", "r2c2"],
+                ],
+                [
+                    ["h1", "h2"],
+                    ["h3", "h4"],
+                    ["r3c1", "r3c2"],
+                    ["h1", "h2"],
+                ],
+            ],
+            [
+                ["h1", "h2"],
+                ["h3", "h4"],
+                ["r1c1", "r1c2"],
+                ["h1", "h2"],
+                ["r2c1", "r2c2"],
+                ["r3c1", "r3c2"],
+                ["h1", "h2"],
+            ],
+        ),
+        (
+            "Drops repeated multi-row header starting within a partial match.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.DropRepeatedHeader(header_rows=2)],
+            ),
+            [
+                [
+                    ["A"],
+                    ["B"],
+                    ["r1"],
+                    ["A"],
+                    ["A"],
+                    ["B"],
+                    ["r2"],
+                ],
+            ],
+            [
+                ["A"],
+                ["B"],
+                ["r1"],
+                ["A"],
+                ["r2"],
+            ],
+        ),
+        (
+            "Merges rows split across a repeated header.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.DropRepeatedHeader(merge_split_rows_column=0)],
+            ),
+            [
+                [
+                    ["Item", "Effect"],
+                    ["Knife", "Cuts"],
+                    ["Rope", "Ties things"],
+                ],
+                [
+                    ["Item", "Effect"],
+                    ["", "together"],
+                    ["", "Second line"],
+                    ["Torch", "Lights"],
+                ],
+                [
+                    ["Item", "Effect"],
+                    ["Tent", "Shelters"],
+                ],
+            ],
+            [
+                ["Item", "Effect"],
+                ["Knife", "Cuts"],
+                ["Rope", "Ties things together"],
+                ["", "Second line"],
+                ["Torch", "Lights"],
+                ["Tent", "Shelters"],
+            ],
+        ),
+        (
+            "Passes a table that meets its assertions through unchanged.",
+            cfgextract.TableExtraction(
+                transforms=[
+                    cfgextract.AssertTable(
+                        min_rows=2,
+                        required_columns=["Name", "TL"],
+                        non_empty_columns=[0],
+                        cell_patterns=[cfgextract.CellPattern(column=1, pattern=r"\d+")],
+                    ),
+                ],
+            ),
+            [
+                [
+                    ["Name", "TL"],
+                    ["a", "12"],
+                    ["b", ""],
+                ],
+            ],
+            [
+                ["Name", "TL"],
+                ["a", "12"],
+                ["b", ""],
+            ],
+        ),
+        (
+            "Fills down merged cells, keeping header.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.FillDown(columns=[0, 2], skip_rows=1)],
+            ),
+            [
+                [
+                    ["Career", "Skill", "Note"],
+                    ["", "Admin", ""],
+                    ["Agent", "Deception", "x"],
+                    ["", "Recon", ""],
+                    ["", "Streetwise"],
+                    ["Army", "Gun Combat", ""],
+                ],
+            ],
+            [
+                ["Career", "Skill", "Note"],
+                ["", "Admin", ""],
+                ["Agent", "Deception", "x"],
+                ["Agent", "Recon", "x"],
+                ["Agent", "Streetwise", "x"],
+                ["Army", "Gun Combat", "x"],
+            ],
+        ),
+        (
+            "Fills right merged cells.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.FillRight(columns=[3, 2, 0])],
+            ),
+            [
+                [
+                    ["Roll", "1", "", ""],
+                    ["Result", "a", "b", ""],
+                    ["Other", "c"],
+                ],
+            ],
+            [
+                ["Roll", "1", "1", "1"],
+                ["Result", "a", "b", "b"],
+                ["Other", "c", "c", "c"],
+            ],
+        ),
+        (
+            "Filters rows by column, keeping header.",
+            cfgextract.TableExtraction(
+                transforms=[cfgextract.FilterRows(column=1, pattern="", invert=True, skip_rows=1)],
+            ),
+            [
+                [
+                    ["Name", "TL"],
+                    ["a", "1"],
+                    ["b", ""],
+                    ["c"],
+                    ["d", "3"],
+                ],
+            ],
+            [
+                ["Name", "TL"],
+                ["a", "1"],
+                ["d", "3"],
+            ],
+        ),
+        (
+            "Sorts rows by multiple keys, numeric-aware.",
+            cfgextract.TableExtraction(
+                transforms=[
+                    cfgextract.SortRows(
+                        keys=[
+                            cfgextract.SortKey(column=1, numeric=True),
+                            cfgextract.SortKey(column=0, descending=True),
+                        ],
+                        skip_rows=1,
+                    ),
+                ],
+            ),
+            [
+                [
+                    ["Name", "Cost"],
+                    ["a", "Cr1,000"],
+                    ["b", "Cr200"],
+                    ["c", "-"],
+                    ["d", "Cr200"],
+                    ["e", "Cr9.5"],
+                ],
+            ],
+            [
+                ["Name", "Cost"],
+                ["e", "Cr9.5"],
+                ["d", "Cr200"],
+                ["b", "Cr200"],
+                ["a", "Cr1,000"],
+                ["c", "-"],
+            ],
+        ),
+        (
+            "Sorts rows by a descending numeric key, with missing numbers last.",
+            cfgextract.TableExtraction(
+                transforms=[
+                    cfgextract.SortRows(
+                        keys=[cfgextract.SortKey(column=1, numeric=True, descending=True)],
+                    ),
+                ],
+            ),
+            [
+                [
+                    ["a", "2"],
+                    ["b", ""],
+                    ["c", "10"],
+                    ["d", "-"],
+                    ["e", "5"],
+                ],
+            ],
+            [
+                ["c", "10"],
+                ["e", "5"],
+                ["a", "2"],
+                ["b", ""],
+                ["d", "-"],
+            ],
+        ),
+        (
+            "Extracts key values from records.",
+            cfgextract.TableExtraction(
+                transforms=[
+                    cfgextract.ExtractKeyValues(
+                        fields=[
+                            cfgextract.KeyValueField(key="Animal", pattern=r"^\S.*$"),
+                            cfgextract.KeyValueField(key="Hits", pattern=r"HITS (\d+)"),
+                            cfgextract.KeyValueField(key="Speed", pattern=r"SPEED (\S+)"),
+                        ],
+                        record_start=r"[A-Z][a-z]",
+                    ),
+                ],
+            ),
+            [
+                [
+                    ["ANIMALS OF THE WILDS"],
+                    ["Antelope"],
+                    ["HITS", "", "12"],
+                    ["SPEED 12m"],
+                    ["Bear"],
+                    ["HITS 30"],
+                ],
+            ],
+            [
+                ["Animal", "Hits", "Speed"],
+                ["Antelope", "12", "12m"],
+                ["Bear", "30", ""],
+            ],
+        ),
+        (
+            "Extracts key values with an optional group that does not participate.",
+            cfgextract.TableExtraction(
+                transforms=[
+                    cfgextract.ExtractKeyValues(
+                        fields=[
+                            cfgextract.KeyValueField(key="Animal", pattern=r"^\S+"),
+                            cfgextract.KeyValueField(key="Armour", pattern=r"ARMOUR( \d+)?"),
+                        ],
+                        record_start=r"[A-Z][a-z]",
+                    ),
+                ],
+            ),
+            [
+                [
+                    ["Antelope"],
+                    ["ARMOUR 2"],
+                    ["Bear"],
+                    ["ARMOUR"],
+                ],
+            ],
+            [
+                ["Animal", "Armour"],
+                ["Antelope", "2"],
+                ["Bear", ""],
+            ],
+        ),
     ],
 )
 def test_extract_table(
@@ -429,3 +742,299 @@ def test_extract_table(
     testfixtures.compare(expected=[Call(pdf_path, tmpl_content)], actual=table_reader.calls)
     # Check output.
     testfixtures.compare(expected=expected, actual=actual)
+
+
+def test_extract_table_passes_tabula_options() -> None:
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    options = cfgextract.TabulaOptions(columns=[100.0, 250.5], use_line_returns=True)
+    table_reader = FakeTableReader(tables_in=[[["a"]]])
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        tableextract.extract_table(
+            cfg_reader=cfg_reader,
+            table=config.Table(
+                file_stem=pathlib.Path("foo/bar"),
+                extraction=cfgextract.TableExtraction(),
+                tabula_options=options,
+            ),
+            pdf_path=pathlib.Path("some.pdf"),
+            table_reader=table_reader,
+        )
+
+    testfixtures.compare(
+        expected=[Call(pathlib.Path("some.pdf"), "{}", options)],
+        actual=table_reader.calls,
+    )
+
+
+def test_extract_table_intermediates() -> None:
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    intermediates: dict[str, list[list[str]]] = {}
+    intermediate_boxes: dict[str, Optional[tableextract.CellBoxes]] = {}
+
+    def on_intermediate(
+        step_id: str,
+        rows: list[list[str]],
+        boxes: Optional[tableextract.CellBoxes],
+    ) -> None:
+        intermediates[step_id] = rows
+        intermediate_boxes[step_id] = boxes
+
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        _, rows = tableextract.extract_table(
+            cfg_reader=cfg_reader,
+            table=config.Table(
+                file_stem=pathlib.Path("foo/bar"),
+                extraction=cfgextract.TableExtraction(
+                    transforms=[
+                        cfgextract.PrependRow(["header"]),
+                        cfgextract.Transpose(),
+                    ],
+                ),
+            ),
+            pdf_path=pathlib.Path("some.pdf"),
+            table_reader=FakeTableReader(tables_in=[[["a"], ["b"]]]),
+            on_intermediate=on_intermediate,
+        )
+        actual = list(rows)
+
+    testfixtures.compare(expected=[["header", "a", "b"]], actual=actual)
+    testfixtures.compare(
+        expected={
+            "00-tabula": [["a"], ["b"]],
+            "01-PrependRow": [["header"], ["a"], ["b"]],
+            "02-Transpose": [["header", "a", "b"]],
+        },
+        actual=intermediates,
+    )
+    # The fake reader's cells have no geometry.
+    testfixtures.compare(
+        expected={"00-tabula": [[None], [None]], "01-PrependRow": None, "02-Transpose": None},
+        actual=intermediate_boxes,
+    )
+
+
+@pytest.mark.parametrize(
+    "name,assert_cfg,expected_message",
+    [
+        (
+            "Too few rows.",
+            cfgextract.AssertTable(min_rows=3),
+            "has 2 rows after the header, fewer than 3",
+        ),
+        (
+            "Missing required column.",
+            cfgextract.AssertTable(required_columns=["Name", "Cost"]),
+            "has no 'Cost' column in the header",
+        ),
+        (
+            "Empty and missing cells.",
+            cfgextract.AssertTable(non_empty_columns=[1, 2]),
+            "row 2 column 2 is empty; row 3 column 2 is empty",
+        ),
+        (
+            "Cell not matching pattern.",
+            cfgextract.AssertTable(
+                cell_patterns=[cfgextract.CellPattern(column=1, pattern=r"\d+")],
+            ),
+            "row 3 column 1 value 'x' does not match '\\\\d+'",
+        ),
+        (
+            "Failures beyond the limit are counted.",
+            cfgextract.AssertTable(header_rows=0, non_empty_columns=[3, 4, 5]),
+            "row 1 column 3 is empty; row 1 column 4 is empty; row 1 column 5 is empty; "
+            "row 2 column 3 is empty; row 2 column 4 is empty; and 4 more",
+        ),
+    ],
+)
+def test_extract_table_assertion_failure(
+    name: str,
+    assert_cfg: cfgextract.AssertTable,
+    expected_message: str,
+) -> None:
+    print(name)
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        _, rows = tableextract.extract_table(
+            cfg_reader=cfg_reader,
+            table=config.Table(
+                file_stem=pathlib.Path("foo/bar"),
+                extraction=cfgextract.TableExtraction(transforms=[assert_cfg]),
+            ),
+            pdf_path=pathlib.Path("some.pdf"),
+            table_reader=FakeTableReader(tables_in=[[["Name", "TL"], ["a", "12", ""], ["b", "x"]]]),
+        )
+        with pytest.raises(tableextract.TableAssertionError) as exc_info:
+            list(rows)
+
+    testfixtures.compare(expected=expected_message, actual=str(exc_info.value))
+
+
+@pytest.mark.parametrize(
+    "name,transform",
+    [
+        (
+            "AnnotateCellTypes.",
+            cfgextract.AnnotateCellTypes(types=[cfgextract.CellType(name="a", pattern="(")]),
+        ),
+        ("FilterRows.", cfgextract.FilterRows(column=0, pattern="(")),
+        (
+            "ExtractKeyValues field.",
+            cfgextract.ExtractKeyValues(fields=[cfgextract.KeyValueField(key="a", pattern="(")]),
+        ),
+        (
+            "ExtractKeyValues record start.",
+            cfgextract.ExtractKeyValues(
+                fields=[cfgextract.KeyValueField(key="a", pattern=".*")],
+                record_start="[",
+            ),
+        ),
+    ],
+)
+def test_extract_table_invalid_pattern(name: str, transform: cfgextract.TableTransform) -> None:
+    print(name)
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        _, rows = tableextract.extract_table(
+            cfg_reader=cfg_reader,
+            table=config.Table(
+                file_stem=pathlib.Path("foo/bar"),
+                extraction=cfgextract.TableExtraction(transforms=[transform]),
+            ),
+            pdf_path=pathlib.Path("some.pdf"),
+            table_reader=FakeTableReader(tables_in=[[["a"]]]),
+        )
+        with pytest.raises(tableextract.ConfigurationError, match="invalid"):
+            list(rows)
+
+
+@pytest.mark.parametrize(
+    "name,transform,tables_in,expected_types",
+    [
+        (
+            "Built-in types.",
+            cfgextract.AnnotateCellTypes(),
+            [
+                [
+                    ["Item", "TL", "Cost", "Roll", "Damage", "Size", "Range", "Weight"],
+                    ["a", "12", "Cr1,000", "2-4", "3D", "20 tons", "", "1.5"],
+                    ["b", "-1", "2.5MCr", "12+", "1D+2", "1,000  tons", "", "2"],
+                ],
+            ],
+            [
+                ["", "", "", "", "", "", "", ""],
+                ["text", "integer", "credits", "range", "dice", "tonnage", "", "number"],
+                ["text", "integer", "credits", "range", "dice", "tonnage", "", "integer"],
+            ],
+        ),
+        (
+            "Configured types.",
+            cfgextract.AnnotateCellTypes(
+                types=[cfgextract.CellType(name="code", pattern=r"[A-Z]-\d")],
+                header_rows=0,
+            ),
+            [
+                [
+                    ["A-1", "x"],
+                    ["B-2"],
+                ],
+            ],
+            [
+                ["code", "text"],
+                ["code"],
+            ],
+        ),
+    ],
+)
+def test_extract_table_cell_types(
+    name: str,
+    transform: cfgextract.AnnotateCellTypes,
+    tables_in: list[list[list[str]]],
+    expected_types: list[list[str]],
+) -> None:
+    print(name)
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    actual_types: list[list[list[str]]] = []
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        _, rows = tableextract.extract_table(
+            cfg_reader=cfg_reader,
+            table=config.Table(
+                file_stem=pathlib.Path("foo/bar"),
+                extraction=cfgextract.TableExtraction(transforms=[transform]),
+            ),
+            pdf_path=pathlib.Path("some.pdf"),
+            table_reader=FakeTableReader(tables_in=tables_in),
+            on_cell_types=actual_types.append,
+        )
+        actual_rows = list(rows)
+
+    # The rows are left unchanged.
+    testfixtures.compare(
+        expected=[[" ".join(cell.split()) for cell in row] for row in tables_in[0]],
+        actual=actual_rows,
+    )
+    testfixtures.compare(expected=[expected_types], actual=actual_types)
+
+
+def test_extract_table_cell_types_not_last() -> None:
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        with pytest.raises(tableextract.ConfigurationError, match="must be the last"):
+            tableextract.extract_table(
+                cfg_reader=cfg_reader,
+                table=config.Table(
+                    file_stem=pathlib.Path("foo/bar"),
+                    extraction=cfgextract.TableExtraction(
+                        transforms=[cfgextract.AnnotateCellTypes(), cfgextract.Transpose()],
+                    ),
+                ),
+                pdf_path=pathlib.Path("some.pdf"),
+                table_reader=FakeTableReader(tables_in=[[["a"]]]),
+            )
+
+
+class DeadlineExceeded(Exception):
+    pass
+
+
+@pytest.mark.parametrize(
+    "name,allowed_checks,expect_raised_by_read",
+    [
+        ("Stops while reading the PDF.", 0, True),
+        ("Stops while transforming rows.", 3, False),
+    ],
+)
+def test_extract_table_check_deadline(
+    name: str,
+    allowed_checks: int,
+    expect_raised_by_read: bool,
+) -> None:
+    print(name)
+    checks = 0
+
+    def check_deadline() -> None:
+        nonlocal checks
+        checks += 1
+        if checks > allowed_checks:
+            raise DeadlineExceeded()
+
+    tmpl_path = pathlib.PurePath("foo/bar.tabula-template.json")
+    raised_by_read = False
+    with filesio.MemReadWriter.new_reader({tmpl_path: "{}"}) as cfg_reader:
+        with pytest.raises(DeadlineExceeded):
+            try:
+                _, rows = tableextract.extract_table(
+                    cfg_reader=cfg_reader,
+                    table=config.Table(
+                        file_stem=pathlib.Path("foo/bar"),
+                        extraction=cfgextract.TableExtraction(transforms=[cfgextract.Transpose()]),
+                    ),
+                    pdf_path=pathlib.Path("some.pdf"),
+                    table_reader=FakeTableReader(tables_in=[[["a"], ["b"], ["c"], ["d"]]]),
+                    check_deadline=check_deadline,
+                )
+            except DeadlineExceeded:
+                raised_by_read = True
+                raise
+            list(rows)
+
+    assert raised_by_read == expect_raised_by_read
@@ -0,0 +1,77 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata.extraction import tablestats, tabulautil
+
+
+_AREA = tabulautil.TemplateArea(page=1, top=100.0, left=50.0, bottom=200.0, right=300.0)
+
+
+def _cell(text: str, top: float, left: float) -> tabulautil.TabulaCell:
+    return {"text": text, "top": top, "left": left, "width": 20.0, "height": 10.0}
+
+
+@pytest.mark.parametrize(
+    "name,rows,tables,expected",
+    [
+        (
+            "empty",
+            [],
+            [],
+            tablestats.TableStats(rows=0, columns=0, empty_cells=0, touches_edge=False),
+        ),
+        (
+            "ragged rows",
+            [["a", "b", ""], ["c"]],
+            [],
+            tablestats.TableStats(rows=2, columns=3, empty_cells=3, touches_edge=False),
+        ),
+        (
+            "text inside area",
+            [["a"]],
+            [{"data": [[_cell("a", 150.0, 100.0)]]}],
+            tablestats.TableStats(rows=1, columns=1, empty_cells=0, touches_edge=False),
+        ),
+        (
+            "text at area edge",
+            [["a"]],
+            [{"data": [[_cell("a", 150.0, 50.5)]]}],
+            tablestats.TableStats(rows=1, columns=1, empty_cells=0, touches_edge=True),
+        ),
+        (
+            "empty cell at area edge",
+            [["a"]],
+            [{"data": [[_cell("", 100.0, 50.0), _cell("a", 150.0, 100.0)]]}],
+            tablestats.TableStats(rows=1, columns=1, empty_cells=0, touches_edge=False),
+        ),
+    ],
+)
+def test_compute(
+    name: str,
+    rows: list[list[str]],
+    tables: list[tabulautil.TabulaTable],
+    expected: tablestats.TableStats,
+) -> None:
+    print(name)
+    testfixtures.compare(expected=expected, actual=tablestats.compute(rows, tables, [_AREA]))
+
+
+def test_thresholds_warnings() -> None:
+    stats = tablestats.TableStats(rows=1, columns=4, empty_cells=3, touches_edge=True)
+
+    testfixtures.compare(
+        expected=[
+            "has 1 rows, fewer than 2",
+            "has 75% empty cells, more than 50%",
+            "has text at the edge of its template area, which may be too small",
+        ],
+        actual=list(tablestats.Thresholds(min_rows=2).warnings(stats)),
+    )
+    testfixtures.compare(
+        expected=[],
+        actual=list(
+            tablestats.Thresholds(max_empty_fraction=None, warn_touches_edge=False).warnings(stats)
+        ),
+    )
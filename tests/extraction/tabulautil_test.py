@@ -0,0 +1,212 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import io
+import json
+import pathlib
+from typing import Any, Optional
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata.config import cfgextract
+from travdata.extraction import tabulautil
+
+
+def test_table_rows_boxes() -> None:
+    rows: list[tabulautil.TabulaRow] = [
+        [
+            {"text": "a", "top": 10.0, "left": 20.0, "width": 30.0, "height": 5.0},
+            {"text": "", "top": 0.0, "left": 0.0, "width": 0.0, "height": 0.0},
+        ],
+        [
+            {"text": "no geometry"},
+        ],
+    ]
+
+    actual = list(tabulautil.table_rows_boxes(rows))
+
+    testfixtures.compare(
+        expected=[
+            [tabulautil.CellBox(top=10.0, left=20.0, width=30.0, height=5.0), None],
+            [None],
+        ],
+        actual=actual,
+    )
+    box = actual[0][0]
+    assert box is not None
+    assert box.bottom == 15.0
+    assert box.right == 50.0
+
+
+class RecordingTabulaClient(tabulautil.TabulaClient):
+    def __init__(self, batch_strategy: tabulautil.BatchStrategy) -> None:
+        super().__init__(force_subprocess=True, batch_strategy=batch_strategy)
+        self.calls: list[tuple[list[int], list[list[float]], bool]] = []
+
+    def _read_pdf(self, **kwargs: Any) -> list[tabulautil.TabulaTable]:
+        self.calls.append((kwargs["pages"], kwargs["area"], kwargs["lattice"]))
+        return [{"data": [[{"text": f"area {i}"}]]} for i, _ in enumerate(kwargs["area"])]
+
+
+class KwargsRecordingTabulaClient(tabulautil.TabulaClient):
+    def __init__(self) -> None:
+        super().__init__(force_subprocess=True)
+        self.calls: list[dict[str, Any]] = []
+
+    def _read_pdf(self, **kwargs: Any) -> list[tabulautil.TabulaTable]:
+        self.calls.append(kwargs)
+        return [{"data": []} for _ in kwargs["area"]]
+
+
+def _entry(page: int, method: str, y1: float) -> dict[str, Any]:
+    return {
+        "page": page,
+        "extraction_method": method,
+        "x1": 1.0,
+        "x2": 2.0,
+        "y1": y1,
+        "y2": y1 + 1.0,
+        "width": 1.0,
+        "height": 1.0,
+    }
+
+
+@pytest.mark.parametrize(
+    "strategy,expected_calls",
+    [
+        (
+            tabulautil.BatchStrategy.NONE,
+            [
+                ([1], [[10.0, 1.0, 11.0, 2.0]], False),
+                ([1], [[20.0, 1.0, 21.0, 2.0]], False),
+                ([1], [[30.0, 1.0, 31.0, 2.0]], True),
+                ([2], [[40.0, 1.0, 41.0, 2.0]], False),
+            ],
+        ),
+        (
+            tabulautil.BatchStrategy.PAGE_METHOD,
+            [
+                ([1], [[10.0, 1.0, 11.0, 2.0], [20.0, 1.0, 21.0, 2.0]], False),
+                ([1], [[30.0, 1.0, 31.0, 2.0]], True),
+                ([2], [[40.0, 1.0, 41.0, 2.0]], False),
+            ],
+        ),
+    ],
+)
+def test_read_pdf_with_template_batching(
+    strategy: tabulautil.BatchStrategy,
+    expected_calls: list[tuple[list[int], list[list[float]], bool]],
+) -> None:
+    template = [
+        _entry(1, "stream", 10.0),
+        _entry(1, "stream", 20.0),
+        _entry(1, "lattice", 30.0),
+        _entry(2, "stream", 40.0),
+    ]
+    client = RecordingTabulaClient(strategy)
+
+    pages, tables = client.read_pdf_with_template(
+        pdf_path=pathlib.Path("book.pdf"),
+        template_file=io.StringIO(json.dumps(template)),
+    )
+
+    testfixtures.compare(expected=expected_calls, actual=client.calls)
+    assert pages == {1, 2}
+    assert len(tables) == len(template)
+
+
+def test_read_pdf_with_template_checks_deadline_per_call() -> None:
+    template = [_entry(1, "stream", 10.0), _entry(2, "stream", 20.0)]
+    client = RecordingTabulaClient(tabulautil.BatchStrategy.NONE)
+    calls_at_check: list[int] = []
+
+    client.read_pdf_with_template(
+        pdf_path=pathlib.Path("book.pdf"),
+        template_file=io.StringIO(json.dumps(template)),
+        check_deadline=lambda: calls_at_check.append(len(client.calls)),
+    )
+
+    testfixtures.compare(expected=[0, 1], actual=calls_at_check)
+
+
+def test_read_pdf_with_template_page_ranges() -> None:
+    template = [
+        _entry(1, "stream", 10.0) | {"page": "3-4"},
+        _entry(1, "stream", 20.0) | {"page": [7, "9 - 10"]},
+    ]
+    client = RecordingTabulaClient(tabulautil.BatchStrategy.NONE)
+
+    pages, tables = client.read_pdf_with_template(
+        pdf_path=pathlib.Path("book.pdf"),
+        template_file=io.StringIO(json.dumps(template)),
+    )
+
+    testfixtures.compare(
+        expected=[
+            ([3], [[10.0, 1.0, 11.0, 2.0]], False),
+            ([4], [[10.0, 1.0, 11.0, 2.0]], False),
+            ([7], [[20.0, 1.0, 21.0, 2.0]], False),
+            ([9], [[20.0, 1.0, 21.0, 2.0]], False),
+            ([10], [[20.0, 1.0, 21.0, 2.0]], False),
+        ],
+        actual=client.calls,
+    )
+    assert pages == {3, 4, 7, 9, 10}
+    assert len(tables) == 5
+
+
+@pytest.mark.parametrize(
+    "name,options,expected_kwargs",
+    [
+        ("No options.", None, [{}, {}]),
+        ("Default options.", cfgextract.TabulaOptions(), [{}, {}]),
+        (
+            "Columns only apply to stream areas.",
+            cfgextract.TabulaOptions(columns=[5.0, 7.5]),
+            [{"columns": [5.0, 7.5]}, {}],
+        ),
+        (
+            "Line returns apply to all areas.",
+            cfgextract.TabulaOptions(use_line_returns=True),
+            [{"options": "--use-line-returns"}, {"options": "--use-line-returns"}],
+        ),
+    ],
+)
+def test_read_pdf_with_template_options(
+    name: str,
+    options: Optional[cfgextract.TabulaOptions],
+    expected_kwargs: list[dict[str, Any]],
+) -> None:
+    print(name)
+    template = [_entry(1, "stream", 10.0), _entry(1, "lattice", 20.0)]
+    client = KwargsRecordingTabulaClient()
+
+    client.read_pdf_with_template(
+        pdf_path=pathlib.Path("book.pdf"),
+        template_file=io.StringIO(json.dumps(template)),
+        options=options,
+    )
+
+    testfixtures.compare(
+        expected=expected_kwargs,
+        actual=[
+            {key: kwargs[key] for key in ("columns", "options") if key in kwargs}
+            for kwargs in client.calls
+        ],
+    )
+
+
+@pytest.mark.parametrize(
+    "name,spec",
+    [
+        ("Reversed range.", "5-3"),
+        ("Page zero.", 0),
+        ("Not a page.", "five"),
+        ("Boolean.", True),
+        ("Invalid list item.", [1, None]),
+    ],
+)
+def test_parse_pages_errors(name: str, spec: Any) -> None:
+    print(name)
+    with pytest.raises(tabulautil.TemplateError):
+        tabulautil.parse_pages(spec)
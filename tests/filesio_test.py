@@ -8,7 +8,9 @@ import dataclasses
 import functools
 import pathlib
 import tempfile
+import threading
 from typing import Callable, Iterator, TypeAlias
+import zipfile
 
 import pytest
 from pytest_subtests import SubTests
@@ -84,6 +86,32 @@ def test_zip(subtests: SubTests) -> None:
     _test_io(subtests, make_zip_context)
 
 
+def test_null(subtests: SubTests) -> None:
+    factories: list[tuple[str, ReaderFactory]] = [
+        ("Reader", filesio.NullReadWriter.new_reader),
+        ("ReadWriter", filesio.NullReadWriter.new_read_writer),
+    ]
+    for reader_case in [
+        _empty_reader_has_no_files,
+        _empty_reader_not_exists,
+        _empty_reader_open_read_raises_not_found,
+        _empty_reader_open_read_binary_raises_not_found,
+    ]:
+        for reader_desc, reader_factory in factories:
+            with subtests.test(f"{reader_case.__name__} {reader_desc}"):
+                reader_case(reader_factory)
+
+    with subtests.test("discards_writes"):
+        path = pathlib.PurePath("file.txt")
+        with filesio.NullReadWriter.new_read_writer() as read_writer:
+            with read_writer.open_write(path) as w:
+                assert w.write("content") == len("content")
+            with read_writer.open_write_binary(path) as wb:
+                assert wb.write(b"content") == len(b"content")
+            assert not read_writer.exists(path)
+            assert not list(read_writer.iter_files())
+
+
 def _test_io(
     subtests: SubTests,
     make_io_context: MakeIoContext,
@@ -95,6 +123,7 @@ def _test_io(
         _empty_reader_has_no_files,
         _empty_reader_not_exists,
         _empty_reader_open_read_raises_not_found,
+        _empty_reader_open_read_binary_raises_not_found,
     ]
     for reader_case in empty_reader_cases:
         with make_io_context() as io_ctx:
@@ -106,6 +135,8 @@ def _test_io(
     full_cases: list[Callable[[IOContext], None]] = [
         _read_writer_reads_own_file,
         _reads_created_files,
+        _reads_created_files_binary,
+        _reads_binary_written_files,
         _readers_iter_files,
         _read_writer_overwrites_file,
         _created_files_exist,
@@ -137,6 +168,14 @@ def _empty_reader_open_read_raises_not_found(rf: ReaderFactory) -> None:
                 pass
 
 
+def _empty_reader_open_read_binary_raises_not_found(rf: ReaderFactory) -> None:
+    with rf() as reader:
+        path = pathlib.PurePath("not-exist")
+        with pytest.raises(filesio.NotFoundError):
+            with reader.open_read_binary(path):
+                pass
+
+
 def _read_writer_reads_own_file(io_ctx: IOContext) -> None:
     with io_ctx.read_writer_factory() as read_writer:
         path = pathlib.PurePath("file.txt")
@@ -175,6 +214,41 @@ def _reads_created_files(io_ctx: IOContext) -> None:
                     assert contents == got_contents, f"{path=} {reader_desc=}"
 
 
+def _reads_created_files_binary(io_ctx: IOContext) -> None:
+    path = pathlib.PurePath("caf\u00e9.txt")
+    contents = "caf\u00e9\r\n"
+
+    with io_ctx.read_writer_factory() as read_writer:
+        with read_writer.open_write(path, newline="") as w:
+            w.write(contents)
+
+        with read_writer.open_read_binary(path) as r:
+            assert r.read() == contents.encode("utf-8")
+
+    for reader_desc, reader_factory in io_ctx.reader_factories():
+        with reader_factory() as reader:
+            with reader.open_read_binary(path) as r:
+                assert r.read() == contents.encode("utf-8"), reader_desc
+
+
+def _reads_binary_written_files(io_ctx: IOContext) -> None:
+    path = pathlib.PurePath("subdir/file.csv")
+    # Not valid UTF-8.
+    contents = "caf\u00e9\r\n".encode("cp1252")
+
+    with io_ctx.read_writer_factory() as read_writer:
+        with read_writer.open_write_binary(path) as w:
+            w.write(contents)
+
+        with read_writer.open_read_binary(path) as r:
+            assert r.read() == contents
+
+    for reader_desc, reader_factory in io_ctx.reader_factories():
+        with reader_factory() as reader:
+            with reader.open_read_binary(path) as r:
+                assert r.read() == contents, reader_desc
+
+
 def _readers_iter_files(io_ctx: IOContext) -> None:
     paths: list[pathlib.PurePath] = sorted(
         [
@@ -247,3 +321,379 @@ def _created_files_exist(io_ctx: IOContext) -> None:
             for path in paths:
                 # Should exist in ReadWriter that created them.
                 assert reader.exists(path), reader_desc
+
+
+@pytest.mark.parametrize(
+    "path",
+    [
+        pathlib.PurePath("/etc/passwd"),
+        pathlib.PurePath("../outside.txt"),
+        pathlib.PurePath("subdir/../../outside.txt"),
+    ],
+)
+def test_dir_rejects_unsafe_paths(path: pathlib.PurePath) -> None:
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        filesio.DirReadWriter.new_read_writer(pathlib.Path(tmpdir) / "dir") as read_writer,
+    ):
+        with pytest.raises(filesio.UnsafePathError):
+            read_writer.open_write(path)
+        with pytest.raises(filesio.UnsafePathError):
+            read_writer.open_read(path)
+        with pytest.raises(filesio.UnsafePathError):
+            read_writer.exists(path)
+
+
+@pytest.mark.parametrize("policy", list(filesio.SymlinkPolicy))
+def test_dir_symlink_escaping_directory(policy: filesio.SymlinkPolicy) -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir) / "dir"
+        outside = pathlib.Path(tmpdir) / "outside"
+        dir_path.mkdir()
+        outside.mkdir()
+        (dir_path / "subdir").symlink_to(outside, target_is_directory=True)
+
+        with filesio.DirReadWriter.new_read_writer(dir_path, policy) as read_writer:
+            with pytest.raises(filesio.UnsafePathError):
+                read_writer.open_write(pathlib.PurePath("subdir/file.txt"))
+
+        assert not (outside / "file.txt").exists()
+
+
+@pytest.mark.parametrize(
+    "policy,expect_error,expect_target_content,expect_is_symlink",
+    [
+        (filesio.SymlinkPolicy.FOLLOW, False, "new", True),
+        (filesio.SymlinkPolicy.REFUSE, True, "old", True),
+        (filesio.SymlinkPolicy.REPLACE, False, "old", False),
+    ],
+)
+def test_dir_symlink_policy_on_write(
+    policy: filesio.SymlinkPolicy,
+    expect_error: bool,
+    expect_target_content: str,
+    expect_is_symlink: bool,
+) -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / "target.txt").write_text("old")
+        (dir_path / "link.txt").symlink_to(dir_path / "target.txt")
+        path = pathlib.PurePath("link.txt")
+
+        with filesio.DirReadWriter.new_read_writer(dir_path, policy) as read_writer:
+            if expect_error:
+                with pytest.raises(filesio.UnsafePathError):
+                    read_writer.open_write(path)
+            else:
+                with read_writer.open_write(path) as w:
+                    w.write("new")
+                with read_writer.open_read(path) as r:
+                    assert r.read() == "new"
+
+        assert (dir_path / "target.txt").read_text() == expect_target_content
+        assert (dir_path / "link.txt").is_symlink() == expect_is_symlink
+
+
+class _WriteInterrupted(Exception):
+    pass
+
+
+def test_dir_symlink_replace_keeps_link_on_interrupted_write() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / "target.txt").write_text("old")
+        (dir_path / "link.txt").symlink_to(dir_path / "target.txt")
+
+        with filesio.DirReadWriter.new_read_writer(
+            dir_path, filesio.SymlinkPolicy.REPLACE
+        ) as read_writer:
+            with pytest.raises(_WriteInterrupted):
+                with read_writer.open_write(pathlib.PurePath("link.txt")) as w:
+                    w.write("new")
+                    raise _WriteInterrupted()
+
+        assert (dir_path / "link.txt").is_symlink()
+        assert (dir_path / "link.txt").read_text() == "old"
+        assert sorted(p.name for p in dir_path.iterdir()) == ["link.txt", "target.txt"]
+
+
+def test_new_read_writer_symlink_policy() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / "target.txt").write_text("old")
+        (dir_path / "link.txt").symlink_to(dir_path / "target.txt")
+
+        with filesio.new_read_writer(
+            dir_path, filesio.IOType.DIR, filesio.SymlinkPolicy.REFUSE
+        ) as read_writer:
+            with pytest.raises(filesio.UnsafePathError):
+                read_writer.open_write(pathlib.PurePath("link.txt"))
+
+
+@pytest.mark.parametrize(
+    "name,make_read_writer",
+    [
+        (
+            "dir",
+            lambda tmp_path: filesio.DirReadWriter.new_read_writer(tmp_path / "dir"),
+        ),
+        (
+            "zip",
+            lambda tmp_path: filesio.ZipReadWriter.new_read_writer(tmp_path / "files.zip"),
+        ),
+    ],
+)
+def test_interrupted_write_keeps_existing_file(
+    name: str,
+    make_read_writer: Callable[[pathlib.Path], AbstractContextManager[filesio.ReadWriter]],
+) -> None:
+    print(name)
+    path = pathlib.PurePath("group/table.csv")
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp_path = pathlib.Path(tmpdir)
+        with make_read_writer(tmp_path) as read_writer:
+            with read_writer.open_write(path) as w:
+                w.write("old")
+
+        with pytest.raises(_WriteInterrupted):
+            with make_read_writer(tmp_path) as read_writer:
+                with read_writer.open_write(path) as w:
+                    w.write("partial")
+                    raise _WriteInterrupted()
+
+        with make_read_writer(tmp_path) as read_writer:
+            with read_writer.open_read(path) as r:
+                assert r.read() == "old"
+            assert list(read_writer.iter_files()) == [path]
+
+
+def test_dir_iter_files_skips_in_flight_writes() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        tmp_path = pathlib.Path(tmpdir)
+        # Looks like a temporary file, but is not named as one.
+        (tmp_path / ".notes.tmp").write_text("notes", encoding="utf-8")
+        with filesio.DirReadWriter.new_read_writer(tmp_path) as read_writer:
+            with read_writer.open_write(pathlib.PurePath("table.csv")) as w:
+                w.write("partial")
+                w.flush()
+                in_flight = sorted(read_writer.iter_files())
+            assert len(list(tmp_path.iterdir())) == 2
+            written = sorted(read_writer.iter_files())
+
+    assert in_flight == [pathlib.PurePath(".notes.tmp")]
+    assert written == [pathlib.PurePath(".notes.tmp"), pathlib.PurePath("table.csv")]
+
+
+def test_mem_interrupted_write_is_discarded() -> None:
+    files = {pathlib.PurePath("table.csv"): "old"}
+    with filesio.MemReadWriter.new_read_writer(files) as read_writer:
+        with pytest.raises(_WriteInterrupted):
+            with read_writer.open_write(pathlib.PurePath("table.csv")) as w:
+                w.write("partial")
+                raise _WriteInterrupted()
+    assert files == {pathlib.PurePath("table.csv"): "old"}
+
+
+def test_mem_snapshot_and_export_zip() -> None:
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        filesio.MemReadWriter.new_read_writer() as read_writer,
+    ):
+        with read_writer.open_write(pathlib.PurePath("group/table.csv")) as w:
+            w.write("a,b\r\n")
+        snapshot = read_writer.snapshot()
+        with read_writer.open_write(pathlib.PurePath("other.csv")) as w:
+            w.write("c\r\n")
+        assert snapshot == {pathlib.PurePath("group/table.csv"): "a,b\r\n"}
+
+        zip_path = pathlib.Path(tmpdir) / "out" / "files.zip"
+        read_writer.export_zip(zip_path)
+        with zipfile.ZipFile(zip_path) as zf:
+            assert sorted(zf.namelist()) == ["group/table.csv", "other.csv"]
+            assert zf.read("group/table.csv") == b"a,b\r\n"
+
+
+def test_mem_normalises_paths() -> None:
+    with filesio.MemReadWriter.new_read_writer() as read_writer:
+        with read_writer.open_write(pathlib.PurePath("cafe\u0301.csv")) as w:
+            w.write("x")
+        assert read_writer.exists(pathlib.PurePath("caf\u00e9.csv"))
+        with read_writer.open_read(pathlib.PurePath("caf\u00e9.csv")) as r:
+            assert r.read() == "x"
+
+
+def test_mem_concurrent_writes() -> None:
+    num_threads = 8
+    files_per_thread = 50
+    with filesio.MemReadWriter.new_read_writer() as read_writer:
+
+        def write_files(thread_index: int) -> None:
+            for i in range(files_per_thread):
+                with read_writer.open_write(pathlib.PurePath(f"{thread_index}/{i}.csv")) as w:
+                    w.write(f"{thread_index},{i}")
+                # Iterating while other threads write must not fail.
+                list(read_writer.iter_files())
+
+        threads = [threading.Thread(target=write_files, args=(t,)) for t in range(num_threads)]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+        assert len(read_writer.snapshot()) == num_threads * files_per_thread
+        with read_writer.open_read(pathlib.PurePath("3/7.csv")) as r:
+            assert r.read() == "3,7"
+
+
+def test_dir_write_keeps_file_mode() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / "table.csv").write_text("old")
+        (dir_path / "table.csv").chmod(0o640)
+
+        with filesio.DirReadWriter.new_read_writer(dir_path) as read_writer:
+            with read_writer.open_write(pathlib.PurePath("table.csv")) as w:
+                w.write("new")
+
+        assert (dir_path / "table.csv").read_text() == "new"
+        assert (dir_path / "table.csv").stat().st_mode & 0o777 == 0o640
+
+
+@pytest.mark.parametrize(
+    "path,expected",
+    [
+        ("group/table.csv", "group/table.csv"),
+        ("group/CON.csv", "group/CON_.csv"),
+        ("aux/lpt1", "aux_/lpt1_"),
+        ("group/com1.tabula.json", "group/com1_.tabula.json"),
+        ("console.csv", "console.csv"),
+        ('what?/a:b "c".csv', "what_/a_b _c_.csv"),
+        ("trailing. /name..", "trailing/name"),
+    ],
+)
+def test_sanitise_path(path: str, expected: str) -> None:
+    actual = filesio.sanitise_path(pathlib.PurePath(path))
+    assert actual == pathlib.PurePath(expected)
+    assert filesio.sanitise_path(actual) == actual
+
+
+def test_dir_renames_unportable_paths() -> None:
+    path = pathlib.PurePath("group/NUL.csv")
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        with filesio.DirReadWriter.new_read_writer(dir_path) as read_writer:
+            with read_writer.open_write(pathlib.PurePath("group/ok.csv")) as w:
+                w.write("ok")
+            with read_writer.open_write(path) as w:
+                w.write("content")
+
+            assert read_writer.renamed_paths == {path: pathlib.PurePath("group/NUL_.csv")}
+
+        assert (dir_path / "group" / "NUL_.csv").read_text() == "content"
+        with filesio.DirReader.new_reader(dir_path) as reader:
+            assert reader.exists(path)
+            with reader.open_read(path) as r:
+                assert r.read() == "content"
+
+
+@pytest.mark.parametrize(
+    "name",
+    ["a:b.txt", "CON.txt", "trailing."],
+)
+def test_dir_reads_existing_unportable_names(name: str) -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / name).write_text("content")
+
+        with filesio.DirReader.new_reader(dir_path) as reader:
+            paths = list(reader.iter_files())
+            assert paths == [pathlib.PurePath(name)]
+            assert reader.exists(paths[0])
+            with reader.open_read(paths[0]) as r:
+                assert r.read() == "content"
+
+
+@pytest.mark.parametrize(
+    "path,expected",
+    [
+        ("book/table.csv", "book/table.csv"),
+        ("core_rulebook_2022\\book.yaml", "core_rulebook_2022/book.yaml"),
+        ("cafe\u0301/table.csv", "caf\u00e9/table.csv"),
+    ],
+)
+def test_normalise_path(path: str, expected: str) -> None:
+    actual = filesio.normalise_path(pathlib.PurePath(path))
+    assert actual == pathlib.PurePath(expected)
+    assert filesio.normalise_path(actual) == actual
+
+
+def test_zip_reads_unnormalised_entries() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        zip_path = pathlib.Path(tmpdir) / "cfg.zip"
+        with zipfile.ZipFile(zip_path, "w") as zf:
+            zf.writestr("core_rulebook_2022\\book.yaml", "book")
+            zf.writestr("cafe\u0301.csv", "table")
+
+        with filesio.ZipReader.new_reader(zip_path) as reader:
+            assert sorted(reader.iter_files()) == [
+                pathlib.PurePath("caf\u00e9.csv"),
+                pathlib.PurePath("core_rulebook_2022/book.yaml"),
+            ]
+            assert reader.exists(pathlib.PurePath("core_rulebook_2022/book.yaml"))
+            with reader.open_read(pathlib.PurePath("core_rulebook_2022/book.yaml")) as r:
+                assert r.read() == "book"
+            with reader.open_read(pathlib.PurePath("caf\u00e9.csv")) as r:
+                assert r.read() == "table"
+
+
+def test_dir_reads_unnormalised_names() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        dir_path = pathlib.Path(tmpdir)
+        (dir_path / "cafe\u0301").mkdir()
+        (dir_path / "cafe\u0301" / "table.csv").write_text("table", encoding="utf-8")
+
+        path = pathlib.PurePath("caf\u00e9/table.csv")
+        with filesio.DirReader.new_reader(dir_path) as reader:
+            assert list(reader.iter_files()) == [path]
+            assert reader.exists(path)
+            with reader.open_read(path) as r:
+                assert r.read() == "table"
+
+
+def test_copy_files() -> None:
+    files = {
+        pathlib.PurePath("index.csv"): "table_path\r\n",
+        pathlib.PurePath("book/table.csv"): "a,b\r\nc,d\r\n",
+    }
+    dest: dict[pathlib.PurePath, str] = {}
+    with (
+        filesio.MemReader.new_reader(files) as reader,
+        filesio.MemReadWriter.new_read_writer(dest) as read_writer,
+    ):
+        paths = filesio.copy_files(reader, read_writer)
+
+    assert paths == sorted(files)
+    assert dest == files
+
+
+def test_zip_reader_missing_file() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        zip_path = pathlib.Path(tmpdir) / "missing.zip"
+        with pytest.raises(filesio.NotFoundError):
+            with filesio.ZipReader.new_reader(zip_path):
+                pass
+
+
+def test_zip_reader_corrupt_archive() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        zip_path = pathlib.Path(tmpdir) / "corrupt.zip"
+        zip_path.write_text("not a zip file", encoding="utf-8")
+        with pytest.raises(filesio.CorruptArchiveError):
+            with filesio.ZipReader.new_reader(zip_path):
+                pass
+
+
+@pytest.mark.parametrize("method", ["new_reader", "new_read_writer"])
+def test_auto_io_type_unsupported(method: str) -> None:
+    with pytest.raises(filesio.UnsupportedOperationError):
+        getattr(filesio.IOType.AUTO, method)(pathlib.Path("out"))
@@ -0,0 +1,58 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import os
+import pathlib
+import tempfile
+from unittest import mock
+
+import testfixtures  # type: ignore[import-untyped]
+from travdata import project
+from travdata.gui.extraction import history
+
+
+def _record(started: str) -> history.RunRecord:
+    return history.RunRecord(
+        started=started,
+        project=project.Project(
+            book_id="core_rulebook_2022",
+            input_pdf=pathlib.Path("/books/core.pdf"),
+            output_path=pathlib.Path("/out/core"),
+            with_tags={"career"},
+        ),
+        outcome="complete",
+        duration=12.5,
+        tables_succeeded=10,
+        tables_failed=1,
+    )
+
+
+def test_record_list_round_trip() -> None:
+    older = _record("2026-10-15T09:00:00")
+    newer = _record("2026-10-16T10:30:00.250000")
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        mock.patch.dict(os.environ, {"XDG_DATA_HOME": tmpdir}),
+    ):
+        history.record_run(older)
+        history.record_run(newer)
+        actual = history.list_runs()
+
+    testfixtures.compare(expected=[newer, older], actual=actual)
+    testfixtures.compare(
+        expected="2026-10-16 10:30:00 core_rulebook_2022: complete, 10 tables (1 failed) in 12s",
+        actual=newer.summary(),
+    )
+
+
+def test_record_removes_oldest() -> None:
+    with (
+        tempfile.TemporaryDirectory() as tmpdir,
+        mock.patch.dict(os.environ, {"XDG_DATA_HOME": tmpdir}),
+        mock.patch.object(history, "MAX_RUNS", 2),
+    ):
+        for day in range(1, 4):
+            history.record_run(_record(f"2026-10-0{day}T00:00:00"))
+        actual = [record.started for record in history.list_runs()]
+
+    testfixtures.compare(expected=["2026-10-03T00:00:00", "2026-10-02T00:00:00"], actual=actual)
@@ -0,0 +1,129 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import json
+import pathlib
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import filesio
+from travdata.extraction import bookextract, tablestats
+from travdata.gui.extraction import results
+
+
+@pytest.mark.parametrize(
+    "name,path,content,expected",
+    [
+        (
+            "CSV with header.",
+            "book/table.csv",
+            "\ufeffName,TL\r\na,1\r\nb\r\n",
+            results.ResultTable(header=["Name", "TL"], rows=[["a", "1"], ["b"]]),
+        ),
+        (
+            "Empty CSV.",
+            "book/table.csv",
+            "",
+            results.ResultTable(header=[], rows=[]),
+        ),
+        (
+            "Aggregate JSON of arrays.",
+            "book/group.json",
+            json.dumps({"t1": [["Name", "TL"], ["a", 1]], "sub/t2": [["x"]]}),
+            results.ResultTable(
+                header=["table", "1", "2"],
+                rows=[["t1", "Name", "TL"], ["t1", "a", "1"], ["sub/t2", "x"]],
+            ),
+        ),
+        (
+            "Aggregate JSON of objects.",
+            "book/group.json",
+            json.dumps({"t1": [{"Name": "a", "TL": None}], "t2": [{"Name": "b", "Cost": 2.5}]}),
+            results.ResultTable(
+                header=["table", "Name", "TL", "Cost"],
+                rows=[["t1", "a", ""], ["t2", "b", "", "2.5"]],
+            ),
+        ),
+        (
+            "Aggregate JSON with attribution.",
+            "book/group.json",
+            json.dumps({"_attribution": "Notice.\n", "t1": [["x"]]}),
+            results.ResultTable(header=["table", "1"], rows=[["t1", "x"]]),
+        ),
+    ],
+)
+def test_load_result(
+    name: str,
+    path: str,
+    content: str,
+    expected: results.ResultTable,
+) -> None:
+    print(name)
+    files = {pathlib.PurePath(path): content}
+    with filesio.MemReader.new_reader(files) as reader:
+        actual = results.load_result(reader, pathlib.PurePath(path))
+    testfixtures.compare(expected=expected, actual=actual)
+
+
+@pytest.mark.parametrize(
+    "path,content",
+    [
+        ("book/group.json", "[1, 2]"),
+        ("book/group.json", "{"),
+        ("book/group.json", '{"t1": [1]}'),
+        ("book/table.txt", ""),
+    ],
+)
+def test_load_result_errors(path: str, content: str) -> None:
+    files = {pathlib.PurePath(path): content}
+    with filesio.MemReader.new_reader(files) as reader:
+        with pytest.raises(results.ResultError):
+            results.load_result(reader, pathlib.PurePath(path))
+
+
+@pytest.mark.parametrize(
+    "name,event,errors,expected",
+    [
+        (
+            "Extracted table.",
+            bookextract.TableFinished(
+                output_path=pathlib.PurePath("book/table.csv"),
+                success=True,
+                duration=1.25,
+                stats=tablestats.TableStats(rows=4, columns=2, empty_cells=2, touches_edge=False),
+            ),
+            [],
+            "book/table.csv\nExtracted in 1.2s: 4 rows, 2 columns, 25% empty cells",
+        ),
+        (
+            "Failed table.",
+            bookextract.TableFinished(
+                output_path=pathlib.PurePath("book/table.csv"),
+                success=False,
+                duration=0.5,
+            ),
+            ["first", "second"],
+            "book/table.csv\nFailed in 0.5s\nLast error: second",
+        ),
+        (
+            "Documented table.",
+            bookextract.TableFinished(
+                output_path=pathlib.PurePath("book/table.csv"),
+                success=True,
+                duration=0.5,
+                title="Table Title",
+                doc="What the table is.\n",
+            ),
+            [],
+            "book/table.csv\nTable Title\nExtracted in 0.5s\n\nWhat the table is.",
+        ),
+    ],
+)
+def test_describe_table_outcome(
+    name: str,
+    event: bookextract.TableFinished,
+    errors: list[str],
+    expected: str,
+) -> None:
+    print(name)
+    assert results.describe_table_outcome(event, errors) == expected
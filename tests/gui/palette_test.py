@@ -0,0 +1,77 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+from typing import Optional
+
+import pytest
+from travdata.gui import palette
+
+
+def _noop() -> None:
+    pass
+
+
+@pytest.mark.parametrize(
+    "name,text,expected",
+    [
+        ("No mnemonic.", "Open project", "Open project"),
+        ("Mnemonic.", "&Open project...", "Open project..."),
+        ("Mnemonic within a word.", "Save as p&reset...", "Save as preset..."),
+        ("Literal ampersand.", "Tables && &figures", "Tables & figures"),
+    ],
+)
+def test_strip_mnemonic(name: str, text: str, expected: str) -> None:
+    print(name)
+    assert palette.strip_mnemonic(text) == expected
+
+
+@pytest.mark.parametrize(
+    "name,query,text,expected",
+    [
+        ("Empty query.", "", "Project > Open project", 0),
+        ("Not a subsequence.", "xyz", "Project > Open project", None),
+        ("Out of order.", "po", "Open", None),
+        ("Exact prefix.", "ope", "Open", 1 + 3 + 1 + 5 + 1 + 5),
+        ("Ignores case.", "OPE", "open", 1 + 3 + 1 + 5 + 1 + 5),
+        ("Ignores whitespace in query.", "o p", "open", 1 + 3 + 1 + 5),
+        ("Skipped characters.", "on", "open", 1 + 3 + (1 - 2)),
+        ("Word start.", "r", "run preset", 1 + 3),
+    ],
+)
+def test_match_score(name: str, query: str, text: str, expected: Optional[int]) -> None:
+    print(name)
+    assert palette.match_score(query, text) == expected
+
+
+@pytest.mark.parametrize(
+    "name,query,better,worse",
+    [
+        ("Word starts beat mid-word.", "op", "Open project", "Shopping"),
+        ("Consecutive beats scattered.", "save", "Save project", "Set a value"),
+    ],
+)
+def test_match_score_prefers(name: str, query: str, better: str, worse: str) -> None:
+    print(name)
+    better_score = palette.match_score(query, better)
+    worse_score = palette.match_score(query, worse)
+    assert better_score is not None
+    assert worse_score is not None
+    assert better_score > worse_score
+
+
+def test_rank() -> None:
+    commands = [
+        palette.Command("History > Show past runs", _noop),
+        palette.Command("Project > Save project", _noop),
+        palette.Command("Project > Open project", _noop),
+        palette.Command("View > Larger text", _noop),
+    ]
+    actual = [command.label for command in palette.rank("open", commands)]
+    assert actual == ["Project > Open project"]
+
+    # Equal matches keep their given order.
+    actual = [command.label for command in palette.rank("project", commands)]
+    assert actual == ["Project > Save project", "Project > Open project"]
+
+    actual = [command.label for command in palette.rank("", commands)]
+    assert actual == [command.label for command in commands]
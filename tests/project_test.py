@@ -0,0 +1,179 @@
+# -*- coding: utf-8 -*-
+# pylint: disable=missing-class-docstring,missing-function-docstring,missing-module-docstring
+
+import dataclasses
+import pathlib
+import tempfile
+
+import pytest
+import testfixtures  # type: ignore[import-untyped]
+from travdata import envinterp, filesio, project, travdatarelease, yamlutil
+
+
+def test_save_load_round_trip() -> None:
+    proj = project.Project(
+        book_id="core_rulebook_2022",
+        input_pdf=pathlib.Path("/books/core.pdf"),
+        output_path=pathlib.Path("/out/core.zip"),
+        config_path=pathlib.Path("/cfg/config.zip"),
+        output_type=filesio.IOType.ZIP,
+        overwrite_existing=True,
+        with_tags={"career"},
+        without_tags={"type/career-progress"},
+    )
+
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = pathlib.Path(tmpdir) / f"test{project.PROJECT_SUFFIX}"
+        project.save_project(path, proj)
+        actual = project.load_project(path)
+
+    testfixtures.compare(expected=proj, actual=actual)
+
+
+def test_load_defaults() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = pathlib.Path(tmpdir) / f"test{project.PROJECT_SUFFIX}"
+        path.write_text(
+            "!Project\nbook_id: book\ninput_pdf: in.pdf\noutput_path: out\n",
+            encoding="utf-8",
+        )
+        actual = project.load_project(path)
+
+    testfixtures.compare(
+        expected=project.Project(
+            book_id="book",
+            input_pdf=pathlib.Path("in.pdf"),
+            output_path=pathlib.Path("out"),
+        ),
+        actual=actual,
+    )
+
+
+def test_save_stamps_format_version() -> None:
+    proj = project.Project(
+        book_id="book",
+        input_pdf=pathlib.Path("in.pdf"),
+        output_path=pathlib.Path("out"),
+    )
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = pathlib.Path(tmpdir) / f"test{project.PROJECT_SUFFIX}"
+        project.save_project(path, proj)
+        content = path.read_text(encoding="utf-8")
+
+    assert "format_version: 1\n" in content
+    assert f"written_by: travdata {travdatarelease.EXECUTABLE_VERSION}\n" in content
+
+
+def test_load_newer_format_version() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        path = pathlib.Path(tmpdir) / f"test{project.PROJECT_SUFFIX}"
+        path.write_text(
+            "!Project\nformat_version: 2\nwritten_by: travdata 9.0.0\nbook_id: book\n"
+            "input_pdf: in.pdf\noutput_path: out\nnew_field: !NewType {}\n",
+            encoding="utf-8",
+        )
+        with pytest.raises(yamlutil.NewerFormatError, match=r"newer travdata \(travdata 9.0.0\)"):
+            project.load_project(path)
+
+
+def test_interpolated() -> None:
+    proj = project.Project(
+        book_id="${BOOK:-core_rulebook_2022}",
+        input_pdf=pathlib.Path("${BOOKS_DIR}/core.pdf"),
+        output_path=pathlib.Path("${OUT_DIR}/core"),
+    )
+    interpolator = envinterp.Interpolator({"BOOKS_DIR": "/books", "OUT_DIR": "/ci/out"})
+
+    actual = proj.interpolated(interpolator)
+
+    testfixtures.compare(
+        expected=project.Project(
+            book_id="core_rulebook_2022",
+            input_pdf=pathlib.Path("/books/core.pdf"),
+            output_path=pathlib.Path("/ci/out/core"),
+        ),
+        actual=actual,
+    )
+    testfixtures.compare(
+        expected=[
+            "${BOOK} = 'core_rulebook_2022' (default)",
+            "${BOOKS_DIR} = '/books'",
+            "${OUT_DIR} = '/ci/out'",
+        ],
+        actual=interpolator.report(),
+    )
+
+
+def test_uninterpolated() -> None:
+    source = project.Project(
+        book_id="${BOOK:-core_rulebook_2022}",
+        input_pdf=pathlib.Path("${BOOKS_DIR}/core.pdf"),
+        output_path=pathlib.Path("${OUT_DIR}/core"),
+        config_path=pathlib.Path("/cfg/config.zip"),
+        input_pdf_sha256="abc123",
+    )
+    interpolator = envinterp.Interpolator({"BOOKS_DIR": "/books", "OUT_DIR": "/ci/out"})
+    expanded = source.interpolated(interpolator).resolved(pathlib.Path("/projects"))
+    edited = dataclasses.replace(
+        expanded,
+        output_path=pathlib.Path("/elsewhere/core"),
+        input_pdf_sha256=None,
+    )
+
+    actual = edited.uninterpolated(source, expanded)
+
+    testfixtures.compare(
+        expected=project.Project(
+            book_id="${BOOK:-core_rulebook_2022}",
+            input_pdf=pathlib.Path("${BOOKS_DIR}/core.pdf"),
+            output_path=pathlib.Path("/elsewhere/core"),
+            config_path=pathlib.Path("/cfg/config.zip"),
+            input_pdf_sha256="abc123",
+        ),
+        actual=actual,
+    )
+
+
+def test_relativised_resolved_round_trip() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        project_dir = pathlib.Path(tmpdir) / "projects"
+        input_pdf = pathlib.Path(tmpdir) / "books/core.pdf"
+        input_pdf.parent.mkdir()
+        input_pdf.write_bytes(b"%PDF-1.4 fake")
+        proj = project.Project(
+            book_id="core_rulebook_2022",
+            input_pdf=input_pdf,
+            output_path=pathlib.Path(tmpdir) / "out/core",
+            config_path=pathlib.Path("/elsewhere/config.zip"),
+            root=pathlib.Path(".."),
+        )
+
+        saved = proj.relativised(project_dir)
+        actual = saved.resolved(project_dir)
+
+    testfixtures.compare(expected=pathlib.Path("books/core.pdf"), actual=saved.input_pdf)
+    testfixtures.compare(expected=pathlib.Path("out/core"), actual=saved.output_path)
+    testfixtures.compare(expected=pathlib.Path("/elsewhere/config.zip"), actual=saved.config_path)
+    assert saved.input_pdf_sha256 is not None
+    testfixtures.compare(expected=input_pdf, actual=actual.input_pdf)
+    testfixtures.compare(expected=pathlib.Path(tmpdir) / "out/core", actual=actual.output_path)
+    testfixtures.compare(expected=pathlib.Path("/elsewhere/config.zip"), actual=actual.config_path)
+
+
+def test_resolved_finds_moved_pdf_by_checksum() -> None:
+    with tempfile.TemporaryDirectory() as tmpdir:
+        project_dir = pathlib.Path(tmpdir)
+        (project_dir / "core.pdf").write_bytes(b"%PDF-1.4 core")
+        (project_dir / "other.pdf").write_bytes(b"%PDF-1.4 other")
+        proj = project.Project(
+            book_id="core_rulebook_2022",
+            input_pdf=project_dir / "core.pdf",
+            output_path=pathlib.Path("out"),
+        ).relativised(project_dir)
+        moved_pdf = project_dir / "renamed/core-2022.pdf"
+        moved_pdf.parent.mkdir()
+        (project_dir / "core.pdf").rename(moved_pdf)
+
+        actual = proj.resolved(project_dir)
+
+    testfixtures.compare(expected=moved_pdf, actual=actual.input_pdf)